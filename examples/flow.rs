@@ -133,7 +133,7 @@ fn main() {
         for target in targets {
             move_to(e, target).await;
         }
-        let _ = e.insert(Finish);
+        e.insert(Finish);
     });
 
     let mut scheduler = Scheduler::new();
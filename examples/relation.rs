@@ -52,7 +52,7 @@ fn main() {
 
     world.despawn(b).unwrap();
 
-    assert_eq!(world.is_alive(a), false);
+    assert!(!world.is_alive(a));
 
     let a = world.spawn(()).id();
     let b = world.spawn(()).id();
@@ -116,5 +116,5 @@ fn main() {
     world.insert_relation(a, LifeBound, b).unwrap();
 
     world.despawn(a).unwrap();
-    assert_eq!(world.is_alive(b), false);
+    assert!(!world.is_alive(b));
 }
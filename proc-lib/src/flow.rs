@@ -2,6 +2,13 @@ use proc_macro2::TokenStream;
 use syn::spanned::Spanned;
 
 pub fn flow_fn(closure: syn::ExprClosure, edict_path: &syn::Path) -> syn::Result<TokenStream> {
+    if let Some(asyncness) = closure.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span(),
+            "expected a non-async closure, its body is already executed as async block",
+        ));
+    }
+
     if closure.inputs.len() != 1 {
         return Err(syn::Error::new(
             closure.span(),
@@ -19,18 +26,26 @@ pub fn flow_fn(closure: syn::ExprClosure, edict_path: &syn::Path) -> syn::Result
         }
     }
 
-    let arg = &closure.inputs[0];
-    let body = closure.body;
-
-    Ok(quote::quote! {
-        unsafe {
-            #edict_path::flow::FlowClosure::new(move |token| async move {
-                #[allow(unused)]
-                let #arg = #edict_path::flow::FlowContext::cx(&token);
-                {
-                    #body
-                }
-            })
+    let (pat, ty) = match &closure.inputs[0] {
+        syn::Pat::Type(pat_type) => (&pat_type.pat, &pat_type.ty),
+        arg => {
+            return Err(syn::Error::new(
+                arg.span(),
+                "expected an argument with type, e.g. `world: FlowWorld` or `e: FlowEntity`",
+            ));
         }
+    };
+
+    let attrs = &closure.attrs;
+    let body = &closure.body;
+
+    // Hygienic name that can't shadow user's variables.
+    let cx = syn::Ident::new("cx", proc_macro2::Span::mixed_site());
+
+    Ok(quote::quote_spanned! { closure.span() =>
+        #edict_path::flow::FlowClosure::<_, #ty>::new(#(#attrs)* move |#cx: #ty| async move {
+            let #pat = #cx;
+            #body
+        })
     })
 }
@@ -1,6 +1,7 @@
 use proc_macro2::TokenStream;
 
 mod component;
+mod flow;
 // mod query;
 mod relation;
 mod system;
@@ -9,11 +10,16 @@ mod kw {
     proc_easy::easy_token!(name);
     proc_easy::easy_token!(borrow);
     proc_easy::easy_token!(on_drop);
+    proc_easy::easy_token!(on_origin_drop);
     proc_easy::easy_token!(on_target_drop);
     proc_easy::easy_token!(on_replace);
+    proc_easy::easy_token!(on_insert);
     proc_easy::easy_token!(exclusive);
     proc_easy::easy_token!(symmetric);
     proc_easy::easy_token!(owned);
+    proc_easy::easy_token!(ordered);
+    proc_easy::easy_token!(on_target_despawn);
+    proc_easy::easy_token!(on_origin_despawn);
 }
 
 proc_easy::easy_argument_value! {
@@ -46,6 +52,22 @@ proc_easy::easy_argument! {
     }
 }
 
+proc_easy::easy_argument! {
+    struct OnInsert {
+        kw: kw::on_insert,
+        eq: syn::Token![=],
+        function: syn::Expr,
+    }
+}
+
+proc_easy::easy_argument! {
+    struct OnOriginDrop {
+        kw: kw::on_origin_drop,
+        eq: syn::Token![=],
+        function: syn::Expr,
+    }
+}
+
 proc_easy::easy_argument! {
     struct OnTargetDrop {
         kw: kw::on_target_drop,
@@ -54,6 +76,22 @@ proc_easy::easy_argument! {
     }
 }
 
+proc_easy::easy_argument! {
+    struct OnTargetDespawn {
+        kw: kw::on_target_despawn,
+        eq: syn::Token![=],
+        policy: syn::Expr,
+    }
+}
+
+proc_easy::easy_argument! {
+    struct OnOriginDespawn {
+        kw: kw::on_origin_despawn,
+        eq: syn::Token![=],
+        policy: syn::Expr,
+    }
+}
+
 proc_easy::easy_argument! {
     struct Exclusive {
         kw: kw::exclusive,
@@ -117,8 +155,8 @@ pub fn derive_component(
 ) -> TokenStream {
     match syn::parse2(item).and_then(|input| component::derive(input, edict_path, edict_namespace))
     {
-        Ok(output) => output.into(),
-        Err(err) => err.to_compile_error().into(),
+        Ok(output) => output,
+        Err(err) => err.to_compile_error(),
     }
 }
 
@@ -128,8 +166,8 @@ pub fn derive_relation(
     edict_namespace: &syn::Ident,
 ) -> TokenStream {
     match syn::parse2(item).and_then(|input| relation::derive(input, edict_path, edict_namespace)) {
-        Ok(output) => output.into(),
-        Err(err) => err.to_compile_error().into(),
+        Ok(output) => output,
+        Err(err) => err.to_compile_error(),
     }
 }
 
@@ -139,11 +177,15 @@ pub fn derive_relation(
 //     edict_namespace: &syn::Ident,
 // ) -> TokenStream {
 //     match syn::parse2(item).and_then(|input| query::derive(input, edict_path, edict_namespace)) {
-//         Ok(output) => output.into(),
-//         Err(err) => err.to_compile_error().into(),
+//         Ok(output) => output,
+//         Err(err) => err.to_compile_error(),
 //     }
 // }
 
 pub fn system(item: syn::ItemFn, edict_path: &syn::Path) -> syn::Result<TokenStream> {
     system::system(item, edict_path)
 }
+
+pub fn flow_fn(closure: syn::ExprClosure, edict_path: &syn::Path) -> syn::Result<TokenStream> {
+    flow::flow_fn(closure, edict_path)
+}
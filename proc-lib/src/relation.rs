@@ -1,7 +1,10 @@
 use proc_easy::EasyAttributes;
 use syn::spanned::Spanned;
 
-use crate::{kw, merge_where_clauses, Name, OnDrop, OnReplace, OnTargetDrop, WhereClause};
+use crate::{
+    kw, merge_where_clauses, Name, OnDrop, OnInsert, OnOriginDespawn, OnOriginDrop, OnReplace,
+    OnTargetDespawn, OnTargetDrop, WhereClause,
+};
 
 proc_easy::easy_attributes! {
     @(edict)
@@ -10,9 +13,14 @@ proc_easy::easy_attributes! {
         exclusive: Option<kw::exclusive>,
         symmetric: Option<kw::symmetric>,
         owned: Option<kw::owned>,
+        ordered: Option<kw::ordered>,
         on_drop: Option<OnDrop>,
         on_replace: Option<OnReplace>,
+        on_insert: Option<OnInsert>,
+        on_origin_drop: Option<OnOriginDrop>,
         on_target_drop: Option<OnTargetDrop>,
+        on_target_despawn: Option<OnTargetDespawn>,
+        on_origin_despawn: Option<OnOriginDespawn>,
         where_clauses: Vec<WhereClause>,
     }
 }
@@ -40,6 +48,20 @@ pub fn derive(
         .owned
         .map(|_| quote::quote! { const OWNED: bool = true; });
 
+    let ordered = attributes
+        .ordered
+        .map(|_| quote::quote! { const ORDERED: bool = true; });
+
+    let on_target_despawn = attributes.on_target_despawn.map(|on_target_despawn| {
+        let policy = despawn_policy(&on_target_despawn.policy, edict_path);
+        quote::quote! { const ON_TARGET_DESPAWN: #edict_path::relation::DespawnPolicy = #policy; }
+    });
+
+    let on_origin_despawn = attributes.on_origin_despawn.map(|on_origin_despawn| {
+        let policy = despawn_policy(&on_origin_despawn.policy, edict_path);
+        quote::quote! { const ON_ORIGIN_DESPAWN: #edict_path::relation::DespawnPolicy = #policy; }
+    });
+
     let fn_name = attributes.name.map(|name| {
         let name = name.literal;
         Some(quote::quote! {
@@ -55,8 +77,8 @@ pub fn derive(
             quote::quote! {
                 #[allow(unused_variables)]
                 #[inline]
-                fn on_drop(&mut self, entity: #edict_path::entity::EntityId, target: #edict_path::entity::EntityId, encoder: #edict_path::action::LocalActionEncoder<'_>) {
-                    (#on_drop)(self, entity, target, encoder)
+                fn on_drop(self, origin: #edict_path::entity::EntityId, target: #edict_path::entity::EntityId, encoder: #edict_path::action::LocalActionEncoder<'_>) {
+                    (#on_drop)(self, origin, target, encoder)
                 }
             }
         });
@@ -66,20 +88,42 @@ pub fn derive(
             quote::quote! {
                 #[allow(unused_variables)]
                 #[inline]
-                fn on_replace(&mut self, value: &Self, entity: #edict_path::entity::EntityId, target: #edict_path::entity::EntityId, new_target: #edict_path::entity::EntityId, encoder: #edict_path::action::LocalActionEncoder<'_>) -> bool {
-                    (#on_replace)(self, value, entity, target, new_target, encoder)
+                fn on_replace(old_value: &mut Self, new_value: &Self, origin: #edict_path::entity::EntityId, old_target: #edict_path::entity::EntityId, new_target: #edict_path::entity::EntityId, encoder: #edict_path::action::LocalActionEncoder<'_>) -> bool {
+                    (#on_replace)(old_value, new_value, origin, old_target, new_target, encoder)
                 }
             }
         }
     );
 
+    let on_insert = attributes.on_insert.map(|on_insert| {
+        let on_insert = &on_insert.function;
+        quote::quote! {
+            #[allow(unused_variables)]
+            #[inline]
+            fn on_insert(&self, origin: #edict_path::entity::EntityId, target: #edict_path::entity::EntityId, encoder: #edict_path::action::LocalActionEncoder<'_>) {
+                (#on_insert)(self, origin, target, encoder)
+            }
+        }
+    });
+
+    let on_origin_drop = attributes.on_origin_drop.map(|on_origin_drop| {
+        let on_origin_drop = &on_origin_drop.function;
+        quote::quote! {
+            #[allow(unused_variables)]
+            #[inline]
+            fn on_origin_drop(origin: #edict_path::entity::EntityId, targets: &[(#edict_path::entity::EntityId, Self)], encoder: #edict_path::action::LocalActionEncoder<'_>) {
+                (#on_origin_drop)(origin, targets, encoder)
+            }
+        }
+    });
+
     let on_target_drop = attributes.on_target_drop.map(|on_target_drop| {
         let on_target_drop = &on_target_drop.function;
         quote::quote! {
             #[allow(unused_variables)]
             #[inline]
-            fn on_target_drop(entity: #edict_path::entity::EntityId, target: #edict_path::entity::EntityId, encoder: #edict_path::action::LocalActionEncoder<'_>) {
-                (#on_target_drop)(entity, target, encoder)
+            fn on_target_drop(origins: &[(#edict_path::entity::EntityId, Self)], target: #edict_path::entity::EntityId, encoder: #edict_path::action::LocalActionEncoder<'_>) {
+                (#on_target_drop)(origins, target, encoder)
             }
         }
     });
@@ -94,12 +138,22 @@ pub fn derive(
 
             #owned
 
+            #ordered
+
+            #on_target_despawn
+
+            #on_origin_despawn
+
             #fn_name
 
             #on_drop
 
             #on_replace
 
+            #on_insert
+
+            #on_origin_drop
+
             #on_target_drop
         }
     };
@@ -114,3 +168,35 @@ pub fn derive(
 
     Ok(output)
 }
+
+/// Expands shorthand despawn policy.
+///
+/// `keep`, `despawn` and `deny` are expanded to corresponding variants
+/// and `drop(Type)` to policy that drops component `Type`.
+/// Any other expression is used as is.
+fn despawn_policy(policy: &syn::Expr, edict_path: &syn::Path) -> proc_macro2::TokenStream {
+    match policy {
+        syn::Expr::Path(path) if path.qself.is_none() => {
+            if path.path.is_ident("keep") {
+                return quote::quote! { #edict_path::relation::DespawnPolicy::Keep };
+            }
+            if path.path.is_ident("despawn") {
+                return quote::quote! { #edict_path::relation::DespawnPolicy::Despawn };
+            }
+            if path.path.is_ident("deny") {
+                return quote::quote! { #edict_path::relation::DespawnPolicy::Deny };
+            }
+        }
+        syn::Expr::Call(call) if call.args.len() == 1 => {
+            if let syn::Expr::Path(func) = &*call.func {
+                if func.qself.is_none() && func.path.is_ident("drop") {
+                    let ty = &call.args[0];
+                    return quote::quote! { #edict_path::relation::DespawnPolicy::drop_component::<#ty>() };
+                }
+            }
+        }
+        _ => {}
+    }
+
+    quote::quote! { #policy }
+}
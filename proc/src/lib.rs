@@ -40,3 +40,20 @@ pub fn system(attr: TokenStream, item: TokenStream) -> TokenStream {
         Err(err) => err.to_compile_error().into(),
     }
 }
+
+/// Converts closure into a flow that can be spawned
+/// with `spawn_flow` or `spawn_flow_for`.
+///
+/// Closure must accept exactly one argument with type `FlowWorld` or `FlowEntity`.
+/// Its body is executed as async block, so it may `.await` futures.
+/// Closure captures its environment by move.
+#[proc_macro]
+pub fn flow_fn(item: TokenStream) -> TokenStream {
+    let closure = syn::parse_macro_input!(item as syn::ExprClosure);
+
+    let path: syn::Path = syn::parse_quote!(edict);
+    match edict_proc_lib::flow_fn(closure, &path) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -274,6 +274,12 @@ impl ActionSender {
         self.shared.non_empty.store(true, Ordering::Relaxed);
     }
 
+    /// Returns `true` if both senders are bound to the same [`World`] instance.
+    #[inline]
+    pub fn same_world(&self, other: &ActionSender) -> bool {
+        Arc::ptr_eq(&self.shared, &other.shared)
+    }
+
     /// Returns `true` if the channel is still connected to a [`World`] instance.
     #[inline]
     pub fn is_connected(&self) -> bool {
@@ -19,11 +19,18 @@ use core::{
 use atomicell::borrow::{
     new_lock, release_borrow, release_borrow_mut, try_borrow, try_borrow_mut, Lock,
 };
-use hashbrown::HashMap;
+use hashbrown::{hash_map::Values, HashMap};
 
 use crate::{
-    action::LocalActionEncoder, bundle::DynamicBundle, clamp_usize_to_u32,
-    component::ComponentInfo, entity::EntityId, epoch::EpochId, hash::NoOpHasherBuilder, type_id,
+    action::LocalActionEncoder,
+    bundle::DynamicBundle,
+    clamp_usize_to_u32,
+    component::ComponentInfo,
+    entity::EntityId,
+    epoch::EpochId,
+    hash::NoOpHasherBuilder,
+    type_id,
+    wakers::{EntityWakers, Wakers},
     Access, MAX_U32_USIZE,
 };
 
@@ -32,6 +39,12 @@ pub(crate) struct ComponentData {
     pub epoch: EpochId,
     pub entity_epochs: Box<[EpochId]>,
     pub chunk_epochs: Box<[EpochId]>,
+
+    /// Woken when component is borrowed mutably.
+    pub wakers: Wakers,
+
+    /// Woken when component of an entity is dropped or replaced.
+    pub drop_wakers: EntityWakers,
 }
 
 pub(crate) struct ArchetypeComponent {
@@ -88,6 +101,8 @@ impl ArchetypeComponent {
                 epoch: EpochId::start(),
                 chunk_epochs: Box::new([]),
                 entity_epochs: Box::new([]),
+                wakers: Wakers::default(),
+                drop_wakers: EntityWakers::default(),
             }),
             lock: new_lock(),
             info: info.clone(),
@@ -187,6 +202,9 @@ pub struct Archetype {
     components: HashMap<TypeId, ArchetypeComponent, NoOpHasherBuilder>,
     borrows: HashMap<TypeId, Vec<(TypeId, usize)>, NoOpHasherBuilder>,
     borrows_mut: HashMap<TypeId, Vec<(TypeId, usize)>, NoOpHasherBuilder>,
+
+    /// Woken when entities are added to or removed from the archetype.
+    wakers: Wakers,
 }
 
 impl Drop for Archetype {
@@ -231,6 +249,49 @@ impl Archetype {
             components,
             borrows,
             borrows_mut,
+            wakers: Wakers::default(),
+        }
+    }
+
+    /// Registers waker to be woken when entities are added to or removed
+    /// from the archetype or when any of specified components is borrowed mutably.
+    #[cfg(feature = "flow")]
+    pub(crate) fn register_waker(
+        &mut self,
+        types: impl Iterator<Item = TypeId>,
+        token: &crate::wakers::WakeToken,
+        waker: &core::task::Waker,
+    ) {
+        self.wakers.register(token, waker);
+        for ty in types {
+            if let Some(component) = self.components.get_mut(&ty) {
+                component.data.get_mut().wakers.register(token, waker);
+            }
+        }
+    }
+
+    /// Registers waker to be woken when specified component of the entity
+    /// is dropped or replaced.
+    ///
+    /// Returns `false` if archetype does not contain the component.
+    #[cfg(feature = "flow")]
+    pub(crate) fn register_drop_waker(
+        &mut self,
+        entity: EntityId,
+        ty: TypeId,
+        token: &crate::wakers::WakeToken,
+        waker: &core::task::Waker,
+    ) -> bool {
+        match self.components.get_mut(&ty) {
+            None => false,
+            Some(component) => {
+                component
+                    .data
+                    .get_mut()
+                    .drop_wakers
+                    .register(entity, token, waker);
+                true
+            }
         }
     }
 
@@ -311,6 +372,7 @@ impl Archetype {
         }
 
         self.entities.push(id);
+        self.wakers.wake();
         entity_idx
     }
 
@@ -333,6 +395,7 @@ impl Archetype {
         }
 
         self.entities.push(id);
+        self.wakers.wake();
         entity_idx
     }
 
@@ -356,6 +419,7 @@ impl Archetype {
         }
 
         self.entities.push(id);
+        self.wakers.wake();
         entity_idx
     }
 
@@ -404,6 +468,7 @@ impl Archetype {
             };
 
             component.info.drop_one(ptr, id, encoder.reborrow());
+            data.drop_wakers.wake(id);
 
             if entity_idx != last_entity_idx {
                 let chunk_idx = chunk_idx(entity_idx);
@@ -434,6 +499,7 @@ impl Archetype {
         }
 
         self.entities.swap_remove(entity_idx as usize);
+        self.wakers.wake();
         if entity_idx != last_entity_idx {
             Some(self.entities[entity_idx as usize])
         } else {
@@ -545,6 +611,7 @@ impl Archetype {
         // Safety: `epoch` must be advanced before first call to this function.
         // Some World methods may call this function multiple times.
         data.epoch.bump_again(epoch);
+        data.wakers.wake();
         chunk_epoch.bump_again(epoch);
         entity_epoch.bump_again(epoch);
 
@@ -635,6 +702,8 @@ impl Archetype {
 
         let entity = self.entities.swap_remove(src_entity_idx as usize);
         dst.entities.push(entity);
+        self.wakers.wake();
+        dst.wakers.wake();
 
         if src_entity_idx != self.entities.len() as u32 {
             (dst_entity_idx, Some(self.entities[src_entity_idx as usize]))
@@ -687,6 +756,8 @@ impl Archetype {
 
         let entity = self.entities.swap_remove(src_entity_idx as usize);
         dst.entities.push(entity);
+        self.wakers.wake();
+        dst.wakers.wake();
 
         if src_entity_idx != self.entities.len() as u32 {
             (dst_entity_idx, Some(self.entities[src_entity_idx as usize]))
@@ -739,6 +810,8 @@ impl Archetype {
 
         let entity = self.entities.swap_remove(src_entity_idx as usize);
         dst.entities.push(entity);
+        self.wakers.wake();
+        dst.wakers.wake();
 
         if src_entity_idx != self.entities.len() as u32 {
             (
@@ -785,6 +858,8 @@ impl Archetype {
 
         let entity = self.entities.swap_remove(src_entity_idx as usize);
         dst.entities.push(entity);
+        self.wakers.wake();
+        dst.wakers.wake();
 
         if src_entity_idx != self.entities.len() as u32 {
             (dst_entity_idx, Some(self.entities[src_entity_idx as usize]))
@@ -804,6 +879,12 @@ impl Archetype {
         self.components.get(&ty)
     }
 
+    /// Returns iterator over archetype components.
+    #[inline]
+    pub(crate) fn components(&self) -> Values<'_, TypeId, ArchetypeComponent> {
+        self.components.values()
+    }
+
     #[inline]
     pub(crate) fn len(&self) -> u32 {
         debug_assert!(u32::try_from(self.entities.len()).is_ok());
@@ -867,6 +948,7 @@ impl Archetype {
             let entity_epoch = unsafe { data.entity_epochs.get_unchecked_mut(entity_idx as usize) };
 
             data.epoch.bump_again(epoch); // Batch spawn would happen with same epoch.
+            data.wakers.wake();
             chunk_epoch.bump_again(epoch); // Batch spawn would happen with same epoch.
             entity_epoch.bump(epoch);
 
@@ -875,6 +957,7 @@ impl Archetype {
             };
             if occupied(tid) {
                 if replace {
+                    data.drop_wakers.wake(id);
                     component.set_one(dst, src, id, encoder.as_mut().unwrap().reborrow());
                 } else {
                     component.final_drop(src, 1);
@@ -906,6 +989,7 @@ impl Archetype {
         let entity_epoch = unsafe { data.entity_epochs.get_unchecked_mut(entity_idx as usize) };
 
         data.epoch.bump_again(epoch);
+        data.wakers.wake();
         chunk_epoch.bump_again(epoch);
         entity_epoch.bump(epoch);
 
@@ -918,6 +1002,7 @@ impl Archetype {
         };
 
         if let Some(encoder) = occupied {
+            data.drop_wakers.wake(id);
             let value = ManuallyDrop::new(value);
             component.set_one(dst, NonNull::from(&*value).cast(), id, encoder)
         } else {
@@ -940,6 +1025,7 @@ impl Archetype {
         let dst_chunk_idx = chunk_idx(dst_entity_idx);
 
         let last_entity_idx = (self.entities.len() - 1) as u32;
+        let entity = self.entities[src_entity_idx as usize];
 
         for (type_id, src_component) in &mut self.components {
             let src_data = src_component.data.get_mut();
@@ -977,6 +1063,10 @@ impl Archetype {
                 unsafe {
                     ptr::copy_nonoverlapping(src_ptr, dst_ptr, size);
                 }
+
+                src_data
+                    .drop_wakers
+                    .relocate(entity, &mut dst_data.drop_wakers);
             } else {
                 let src_ptr = unsafe {
                     NonNull::new_unchecked(
@@ -984,6 +1074,7 @@ impl Archetype {
                     )
                 };
                 missing(&src_component.info, src_ptr);
+                src_data.drop_wakers.wake(entity);
             }
 
             if src_entity_idx != last_entity_idx {
@@ -1040,7 +1131,7 @@ pub(crate) const fn chunks_count(entities: u32) -> u32 {
 
 #[inline]
 pub(crate) const fn first_of_chunk(idx: u32) -> Option<u32> {
-    if idx % CHUNK_LEN == 0 {
+    if idx.is_multiple_of(CHUNK_LEN) {
         Some(chunk_idx(idx))
     } else {
         None
@@ -1049,7 +1140,7 @@ pub(crate) const fn first_of_chunk(idx: u32) -> Option<u32> {
 
 #[inline]
 pub(crate) fn starts_of_chunks(start: u32, end: u32, mut f: impl FnMut(u32)) {
-    if start % CHUNK_LEN == 0 {
+    if start.is_multiple_of(CHUNK_LEN) {
         f(chunk_idx(start));
     }
 
@@ -12,7 +12,10 @@ use core::{
 
 use hashbrown::hash_map::{Entry, HashMap};
 
-use crate::{action::LocalActionEncoder, entity::EntityId, hash::NoOpHasherBuilder, type_id};
+use crate::{
+    action::LocalActionEncoder, entity::EntityId, hash::NoOpHasherBuilder, relation::RelationInfo,
+    type_id,
+};
 
 pub use edict_proc::Component;
 
@@ -449,6 +452,12 @@ pub struct ComponentInfo {
 
     /// An array of possible component borrows.
     borrows: Arc<[ComponentBorrow]>,
+
+    /// Relation stored in this component.
+    relation: Option<RelationInfo>,
+
+    /// `true` if relation is stored on the origin side.
+    relation_origin: bool,
 }
 
 impl ComponentInfo {
@@ -469,6 +478,8 @@ impl ComponentInfo {
             on_replace: Arc::new(DefaultSetHook),
             final_drop: final_drop::<T>,
             borrows: Arc::from(T::borrows()),
+            relation: None,
+            relation_origin: false,
         }
     }
 
@@ -489,6 +500,8 @@ impl ComponentInfo {
             on_replace: Arc::new(ExternalSetHook),
             final_drop: final_drop::<T>,
             borrows: vec![ComponentBorrow::auto::<T>()].into(),
+            relation: None,
+            relation_origin: false,
         }
     }
 
@@ -516,6 +529,26 @@ impl ComponentInfo {
         &self.borrows
     }
 
+    /// Returns information about relation if this component
+    /// stores relation on either origin or target side.
+    #[inline]
+    pub fn relation(&self) -> Option<&RelationInfo> {
+        self.relation.as_ref()
+    }
+
+    /// Returns `true` if this component stores relation on the origin side.
+    #[inline]
+    pub(crate) fn is_relation_origin(&self) -> bool {
+        self.relation_origin
+    }
+
+    #[inline]
+    pub(crate) fn with_relation(mut self, relation: RelationInfo, origin: bool) -> Self {
+        self.relation = Some(relation);
+        self.relation_origin = origin;
+        self
+    }
+
     /// Returns `true` if specified type can be borrowed from this component.
     #[inline]
     #[must_use]
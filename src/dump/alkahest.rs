@@ -1,15 +1,30 @@
 //! World serialization with [`alkahest`].
 
+use alloc::vec::Vec;
+
 use alkahest::{
     advanced::{slice_writer, write_field, BareFormula, Buffer, Deserializer, Sizes, SliceWriter},
     deserialize_with_size, serialize_to_vec, DeIter, Deserialize, DeserializeError, Formula, Lazy,
     Serialize,
 };
 
-use crate::{action::ActionEncoder, component::Component, query::SendImmutableQuery};
+use crate::{
+    action::ActionEncoder, component::Component, entity::EntityId, query::SendImmutableQuery,
+    relation::Relation,
+};
+
+#[cfg(feature = "flow")]
+use alloc::string::String;
+
+#[cfg(feature = "flow")]
+use alkahest::SerializeRef;
+
+#[cfg(feature = "flow")]
+use crate::flow::{FlowPriority, SavedFlow};
 
 use super::{
-    DumpSet, DumpSlot, Dumper, EntityDump, LoadSet, LoadSlot, Loader, Mark, WorldDump, WorldLoad,
+    DumpSet, DumpSlot, Dumper, EntityDump, LoadSet, LoadSlot, Loader, Mark, RelationSet, WorldDump,
+    WorldLoad,
 };
 
 /// Formula for single entity.
@@ -29,6 +44,15 @@ impl<F> BareFormula for DumpFormula<F> where F: Formula {}
 /// Formula for serializing world with set of components.
 pub type WorldFormula<F> = [([u64; 3], DumpFormula<F>)];
 
+/// Formula for relations of one type dumped from an entity.
+/// List of target ids with relation values.
+pub type RelationFormula<F> = Vec<(u64, F)>;
+
+/// Formula for [`SavedFlow`].
+/// Tuple of flow name, priority index and state data.
+#[cfg(feature = "flow")]
+pub type SavedFlowFormula = (String, u8, Vec<u8>);
+
 #[allow(clippy::type_complexity)]
 struct LoaderAlkahest<'de, F> {
     iter: DeIter<'de, ([u64; 3], DumpFormula<F>), ([u64; 3], Lazy<'de, DumpFormula<F>>)>,
@@ -41,7 +65,7 @@ macro_rules! dumper {
         #[allow(non_snake_case)]
         impl<'a $(, $f)+ $(, $c)+> Serialize<DumpFormula<($($f,)+)>> for ($(DumpSlot<'a, $c>,)+)
         where
-            $($f: Formula, &'a $c: Serialize<$f>,)+
+            $($f: Formula, $c: ?Sized, &'a $c: Serialize<$f>,)+
         {
             fn serialize<Bu>(self, sizes: &mut Sizes, mut buffer: Bu) -> Result<(), Bu::Error>
             where
@@ -209,6 +233,220 @@ macro_rules! dumper {
 // dumper!(A, B);
 for_tuple_2!(dumper);
 
+macro_rules! relations {
+    (,) => {};
+    ($($c:ident)+, $($f:ident)+) => {
+        impl<'a, Bu $(, $f)+ $(, $c)+> Dumper<RelationSet<($($c,)+)>> for SliceWriter<'a, ([u64; 3], DumpFormula<($(RelationFormula<$f>,)+)>), Bu>
+        where
+            Bu: Buffer + ?Sized,
+            $($f: Formula, $c: Relation + Sync, for<'b> &'b $c: Serialize<$f>,)+
+        {
+            type Error = Bu::Error;
+            fn dump(&mut self, entity: EntityDump, tuple: ($(DumpSlot<'_, [(EntityId, $c)]>,)+)) -> Result<(), Bu::Error> {
+                self.write_elem((entity.0, tuple))
+            }
+        }
+
+        impl<'a $(, $c)+, Fi> WorldDump<'a, RelationSet<($($c,)+)>, Fi>
+        where
+            Fi: SendImmutableQuery,
+        {
+            /// Serialize relations with the given alkahest formulas.
+            ///
+            /// Relations of each type are serialized with [`RelationFormula`].
+            pub fn dump_alkahest<$($f),+>(self, output: &mut Vec<u8>) -> (usize, usize)
+            where
+                $($f: Formula,)+
+                $($c: Relation + Sync, for<'b> &'b $c: Serialize<$f>,)+
+            {
+                serialize_to_vec::<WorldFormula<($(RelationFormula<$f>,)+)>, _>(self, output)
+            }
+        }
+
+        impl<Fi $(, $f)+ $(, $c)+> Serialize<WorldFormula<($(RelationFormula<$f>,)+)>> for WorldDump<'_, RelationSet<($($c,)+)>, Fi>
+        where
+            Fi: SendImmutableQuery,
+            $($f: Formula,)+
+            $($c: Relation + Sync, for<'a> &'a $c: Serialize<$f>,)+
+        {
+            fn serialize<Bu>(self, sizes: &mut Sizes, mut buffer: Bu) -> Result<(), Bu::Error>
+            where
+                Self: Sized,
+                Bu: Buffer,
+            {
+                let mut writer = slice_writer::<([u64; 3], DumpFormula<($(RelationFormula<$f>,)+)>), _>(sizes, &mut buffer);
+                <RelationSet<($($c,)+)>>::dump_world(self.world, self.filter, self.epoch, &mut writer)?;
+                writer.finish()
+            }
+
+            fn size_hint(&self) -> Option<Sizes> {
+                None
+            }
+        }
+
+        impl<'de $(, $f)+ $(, $c)+> Loader<RelationSet<($($c,)+)>> for LoaderAlkahest<'de, ($(RelationFormula<$f>,)+)>
+        where
+            $($f: Formula,)+
+            $($c: Deserialize<'de, $f> + Relation + Send,)+
+        {
+            type Error = DeserializeError;
+
+            fn next(&mut self) -> Result<Option<EntityDump>, DeserializeError> {
+                match self.iter.next() {
+                    Some(Ok((entity, lazy))) => {
+                        self.next = Some(lazy);
+                        Ok(Some(EntityDump(entity)))
+                    }
+                    Some(Err(e)) => Err(e),
+                    None => Ok(None),
+                }
+            }
+
+            fn load(&mut self, slots: &mut ($(LoadSlot<'_, Vec<(EntityId, $c)>>,)+)) -> Result<(), DeserializeError> {
+                let lazy = self.next.take().unwrap();
+                lazy.get_in_place(slots)
+            }
+        }
+
+        impl<Ma $(, $c)+> WorldLoad<'_, RelationSet<($($c,)+)>, Ma>
+        where
+            Ma: Mark,
+            $($c: Relation + Send,)+
+        {
+            /// Deserialize relations with the given alkahest deserializer.
+            pub fn load_alkahest_lazy<'de $(, $f)+>(
+                &self,
+                actions: &mut ActionEncoder,
+                lazy: Lazy<'de, WorldFormula<($(RelationFormula<$f>,)+)>>,
+            ) -> Result<(), DeserializeError>
+            where
+                $($f: Formula,)+
+                $($c: Deserialize<'de, $f>,)+
+            {
+                let iter = lazy.iter::<([u64; 3], Lazy<'de, DumpFormula<($(RelationFormula<$f>,)+)>>)>();
+
+                <RelationSet<($($c,)+)>>::load_world(self.world, self.marker, actions, &mut LoaderAlkahest {
+                    iter,
+                    next: None,
+                })
+            }
+
+            /// Deserialize relations with the given alkahest deserializer.
+            ///
+            /// Relations of each type are deserialized with [`RelationFormula`].
+            pub fn load_alkahest<'de $(, $f)+>(
+                &self,
+                actions: &mut ActionEncoder,
+                buffer: &'de [u8],
+                root: usize,
+            ) -> Result<(), DeserializeError>
+            where
+                $($f: Formula,)+
+                $($c: Deserialize<'de, $f>,)+
+            {
+                let lazy = deserialize_with_size::<WorldFormula<($(RelationFormula<$f>,)+)>, _>(buffer, root)?;
+                self.load_alkahest_lazy(actions, lazy)
+            }
+        }
+    };
+}
+
+for_tuple_2!(relations);
+
+impl Serialize<u64> for EntityId {
+    #[inline]
+    fn serialize<Bu>(self, sizes: &mut Sizes, buffer: Bu) -> Result<(), Bu::Error>
+    where
+        Bu: Buffer,
+    {
+        <u64 as Serialize<u64>>::serialize(self.bits(), sizes, buffer)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<Sizes> {
+        <u64 as Serialize<u64>>::size_hint(&self.bits())
+    }
+}
+
+impl Serialize<u64> for &EntityId {
+    #[inline]
+    fn serialize<Bu>(self, sizes: &mut Sizes, buffer: Bu) -> Result<(), Bu::Error>
+    where
+        Bu: Buffer,
+    {
+        <EntityId as Serialize<u64>>::serialize(*self, sizes, buffer)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<Sizes> {
+        <EntityId as Serialize<u64>>::size_hint(self)
+    }
+}
+
+impl<'de> Deserialize<'de, u64> for EntityId {
+    #[inline]
+    fn deserialize(de: Deserializer<'de>) -> Result<Self, DeserializeError> {
+        let bits = <u64 as Deserialize<'de, u64>>::deserialize(de)?;
+        EntityId::from_bits(bits).ok_or(DeserializeError::Incompatible)
+    }
+
+    #[inline]
+    fn deserialize_in_place(&mut self, de: Deserializer<'de>) -> Result<(), DeserializeError> {
+        *self = <EntityId as Deserialize<'de, u64>>::deserialize(de)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "flow")]
+impl SerializeRef<SavedFlowFormula> for SavedFlow {
+    fn serialize<Bu>(&self, sizes: &mut Sizes, buffer: Bu) -> Result<(), Bu::Error>
+    where
+        Bu: Buffer,
+    {
+        let priority = self.priority().index() as u8;
+        <_ as Serialize<SavedFlowFormula>>::serialize(
+            (self.name(), priority, self.data()),
+            sizes,
+            buffer,
+        )
+    }
+
+    fn size_hint(&self) -> Option<Sizes> {
+        let priority = self.priority().index() as u8;
+        <_ as Serialize<SavedFlowFormula>>::size_hint(&(self.name(), priority, self.data()))
+    }
+}
+
+#[cfg(feature = "flow")]
+impl Serialize<SavedFlowFormula> for SavedFlow {
+    fn serialize<Bu>(self, sizes: &mut Sizes, buffer: Bu) -> Result<(), Bu::Error>
+    where
+        Bu: Buffer,
+    {
+        SerializeRef::serialize(&self, sizes, buffer)
+    }
+
+    fn size_hint(&self) -> Option<Sizes> {
+        SerializeRef::size_hint(self)
+    }
+}
+
+#[cfg(feature = "flow")]
+impl<'de> Deserialize<'de, SavedFlowFormula> for SavedFlow {
+    fn deserialize(de: Deserializer<'de>) -> Result<Self, DeserializeError> {
+        let (name, priority, data) =
+            <(String, u8, Vec<u8>) as Deserialize<'de, SavedFlowFormula>>::deserialize(de)?;
+        let priority = FlowPriority::from_index(priority as usize)
+            .ok_or(DeserializeError::WrongVariant(priority as u32))?;
+        Ok(SavedFlow::new(name, priority, data))
+    }
+
+    fn deserialize_in_place(&mut self, de: Deserializer<'de>) -> Result<(), DeserializeError> {
+        *self = <SavedFlow as Deserialize<'de, SavedFlowFormula>>::deserialize(de)?;
+        Ok(())
+    }
+}
+
 #[test]
 fn test_dump() {
     use ::alkahest_proc::{Deserialize, Formula, SerializeRef};
@@ -288,3 +526,75 @@ fn test_dump() {
         Ok((Some(&Foo), Some(&Bar(155)), Some(&Baz("123456".into()))))
     );
 }
+
+#[test]
+fn test_dump_relations() {
+    use ::alkahest_proc::{Deserialize, Formula, SerializeRef};
+
+    use super::NoMark;
+    use crate::{action::ActionBuffer, epoch::EpochId, relation::Relates, world::World};
+
+    #[derive(Clone, Debug, PartialEq, Eq, Formula, SerializeRef, Deserialize)]
+    struct Likes(u32);
+
+    impl Relation for Likes {}
+
+    #[derive(Clone, Debug, PartialEq, Eq, Formula, SerializeRef, Deserialize)]
+    struct Friend;
+
+    impl Relation for Friend {
+        const SYMMETRIC: bool = true;
+    }
+
+    let mut world = World::new();
+
+    let a = world.spawn(()).id();
+    let b = world.spawn(()).id();
+
+    world.insert_relation(a, Likes(7), b).unwrap();
+    world.insert_relation(b, Friend, a).unwrap();
+
+    type Set = RelationSet<(Likes, Friend)>;
+
+    let mut data = Vec::new();
+    let (size, root) = WorldDump::<Set, _>::new(&world, (), EpochId::start())
+        .dump_alkahest::<Likes, Friend>(&mut data);
+
+    let mut world2 = World::new();
+
+    let mut buffer = ActionBuffer::new();
+    let mut actions = buffer.encoder(&world2);
+
+    WorldLoad::<Set, _>::new(&world2, NoMark)
+        .load_alkahest::<Likes, Friend>(&mut actions, &data[..size], root)
+        .unwrap();
+    buffer.execute(&mut world2);
+
+    let likes = world2
+        .view::<Relates<&Likes>>()
+        .try_get(a)
+        .unwrap()
+        .map(|(r, e)| (r.clone(), e.id()))
+        .collect::<Vec<_>>();
+    assert_eq!(likes, [(Likes(7), b)]);
+
+    assert_eq!(world2.view::<Relates<&Friend>>().iter().count(), 2);
+}
+
+#[cfg(feature = "flow")]
+#[test]
+fn test_dump_flows() {
+    use alkahest::deserialize;
+
+    use crate::flow::{FlowPriority, SavedFlow};
+
+    let saved = vec![
+        SavedFlow::new("quest".into(), FlowPriority::High, vec![1, 2, 3]),
+        SavedFlow::new("cutscene".into(), FlowPriority::Low, Vec::new()),
+    ];
+
+    let mut data = Vec::new();
+    let (size, _) = serialize_to_vec::<[SavedFlowFormula], _>(&saved, &mut data);
+    let loaded = deserialize::<[SavedFlowFormula], Vec<SavedFlow>>(&data[..size]).unwrap();
+    assert_eq!(loaded, saved);
+}
@@ -27,8 +27,12 @@
 )]
 //!
 //! Each can be enabled with a feature named as serialization crate.
+//!
+//! Components are dumped with [`WorldDump`] and loaded with [`WorldLoad`].
+//! Relations are dumped and loaded with the same wrappers using [`RelationSet`].
 
 mod query;
+mod relation;
 
 use core::marker::PhantomData;
 
@@ -37,8 +41,8 @@ use crate::{
     query::SendImmutableQuery, world::World, EntityError,
 };
 
-pub use self::query::DumpItem;
 use self::query::DumpQuery;
+pub use self::{query::DumpItem, relation::RelationSet};
 
 #[cfg(feature = "alkahest")]
 pub mod alkahest;
@@ -62,7 +66,7 @@ pub trait Dumper<T: DumpSet + ?Sized> {
 }
 
 /// Slot of component value to be serialized.
-pub enum DumpSlot<'a, T> {
+pub enum DumpSlot<'a, T: ?Sized> {
     /// Skip this slot.
     Skipped,
 
@@ -4,10 +4,20 @@ use core::convert::Infallible;
 
 pub use nanoserde::{DeBin, DeBinErr, SerBin};
 
-use crate::{action::ActionEncoder, component::Component, query::SendImmutableQuery};
+use crate::{
+    action::ActionEncoder, component::Component, entity::EntityId, query::SendImmutableQuery,
+    relation::Relation,
+};
+
+#[cfg(feature = "flow")]
+use alloc::string::String;
+
+#[cfg(feature = "flow")]
+use crate::flow::{FlowPriority, SavedFlow};
 
 use super::{
-    DumpSet, DumpSlot, Dumper, EntityDump, LoadSet, LoadSlot, Loader, Mark, WorldDump, WorldLoad,
+    DumpSet, DumpSlot, Dumper, EntityDump, LoadSet, LoadSlot, Loader, Mark, RelationSet, WorldDump,
+    WorldLoad,
 };
 
 /// Dumps world using [`nanoserde::SerBin`].
@@ -124,6 +134,126 @@ macro_rules! dumper {
 
 for_tuple!(dumper);
 
+macro_rules! relations {
+    () => {};
+    ($($a:ident)+) => {
+        #[allow(non_snake_case)]
+        impl<'a $(, $a)+> Dumper<RelationSet<($($a,)+)>> for DumperBin<'a>
+        where
+            $($a: Relation + SerBin + Sync,)+
+        {
+            type Error = Infallible;
+            fn dump(&mut self, entity: EntityDump, slots: ($(DumpSlot<'_, [(EntityId, $a)]>,)+)) -> Result<(), Infallible> {
+                entity.0.ser_bin(self.0);
+                let ($($a,)+) = slots;
+                $(
+                    match $a {
+                        DumpSlot::Skipped => {}
+                        DumpSlot::Component($a) => {
+                            $a.len().ser_bin(self.0);
+                            for (target, relation) in $a {
+                                target.bits().ser_bin(self.0);
+                                relation.ser_bin(self.0);
+                            }
+                        }
+                    }
+                )+
+                Ok(())
+            }
+        }
+
+        impl<'a $(, $a)+, Fi> SerBin for WorldDump<'a, RelationSet<($($a,)+)>, Fi>
+        where
+            Fi: SendImmutableQuery + Copy,
+            $($a: Relation + SerBin + Sync,)+
+        {
+            fn ser_bin(&self, buf: &mut Vec<u8>) {
+                let result = <RelationSet<($($a,)+)> as DumpSet>::dump_world(self.world, self.filter, self.epoch, &mut DumperBin(buf));
+                match result {
+                    Ok(()) => {}
+                    Err(never) => match never {},
+                }
+            }
+        }
+
+        #[allow(non_snake_case)]
+        impl<'a $(, $a)+> Loader<RelationSet<($($a,)+)>> for LoaderBin<'a>
+        where
+            $($a: Relation + DeBin + Send,)+
+        {
+            type Error = DeBinErr;
+
+            fn next(&mut self) -> Result<Option<EntityDump>, DeBinErr> {
+                if self.offset == self.buf.len() {
+                    return Ok(None);
+                }
+                let idxs = <[u64;3]>::de_bin(&mut self.offset, self.buf)?;
+                Ok(Some(EntityDump(idxs)))
+            }
+
+            fn load(&mut self, slots: &mut ($(LoadSlot<'_, Vec<(EntityId, $a)>>,)+)) -> Result<(), DeBinErr> {
+                let ($($a,)+) = slots;
+                $(
+                    match $a {
+                        LoadSlot::Skipped => {}
+                        LoadSlot::Missing => {
+                            let len = usize::de_bin(&mut self.offset, self.buf)?;
+                            let mut relations = Vec::with_capacity(len);
+                            for _ in 0..len {
+                                let target = u64::de_bin(&mut self.offset, self.buf)?;
+                                let relation = $a::de_bin(&mut self.offset, self.buf)?;
+                                if let Some(target) = EntityId::from_bits(target) {
+                                    relations.push((target, relation));
+                                }
+                            }
+                            *$a = LoadSlot::Created(relations);
+                        }
+                        LoadSlot::Existing(_) | LoadSlot::Created(_) => unreachable!(),
+                    }
+                )+
+                Ok(())
+            }
+        }
+
+        impl<'a $(, $a)+, Ma> WorldLoad<'a, RelationSet<($($a,)+)>, Ma>
+        where
+            Ma: Mark,
+            $($a: Relation + DeBin + Send,)+
+        {
+            /// Loads relations from buffer using [`nanoserde::DeBin`].
+            pub fn load_bin(&self, actions: &mut ActionEncoder, buf: &[u8]) -> Result<(), DeBinErr> {
+                <RelationSet<($($a,)+)> as LoadSet>::load_world(self.world, self.marker, actions, &mut LoaderBin::new(buf))
+            }
+        }
+    };
+}
+
+for_tuple!(relations);
+
+#[cfg(feature = "flow")]
+impl SerBin for SavedFlow {
+    fn ser_bin(&self, buf: &mut Vec<u8>) {
+        self.name().len().ser_bin(buf);
+        buf.extend_from_slice(self.name().as_bytes());
+        (self.priority().index() as u8).ser_bin(buf);
+        self.data().len().ser_bin(buf);
+        buf.extend_from_slice(self.data());
+    }
+}
+
+#[cfg(feature = "flow")]
+impl DeBin for SavedFlow {
+    fn de_bin(offset: &mut usize, buf: &[u8]) -> Result<Self, DeBinErr> {
+        let name = String::de_bin(offset, buf)?;
+        let priority = u8::de_bin(offset, buf)?;
+        let data = Vec::<u8>::de_bin(offset, buf)?;
+
+        // `DeBinErr` can't describe invalid value, fallback to default priority.
+        let priority = FlowPriority::from_index(priority as usize).unwrap_or_default();
+        Ok(SavedFlow::new(name, priority, data))
+    }
+}
+
 #[test]
 fn test_dump() {
     use ::nanoserde::{DeBin, SerBin};
@@ -200,3 +330,98 @@ fn test_dump() {
         Ok((Some(&Foo), Some(&Bar(155)), Some(&Baz("123456".into()))))
     );
 }
+
+#[test]
+fn test_dump_relations() {
+    use ::nanoserde::{DeBin, SerBin};
+
+    use super::NoMark;
+    use crate::{action::ActionBuffer, epoch::EpochId, relation::Relates, world::World};
+
+    #[derive(Clone, Debug, PartialEq, Eq, SerBin, DeBin)]
+    struct Likes(u32);
+
+    impl Relation for Likes {}
+
+    #[derive(Clone, Debug, PartialEq, Eq, SerBin, DeBin)]
+    struct OwnedBy;
+
+    impl Relation for OwnedBy {
+        const EXCLUSIVE: bool = true;
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, SerBin, DeBin)]
+    struct Friend;
+
+    impl Relation for Friend {
+        const SYMMETRIC: bool = true;
+    }
+
+    let mut world = World::new();
+
+    let a = world.spawn(()).id();
+    let b = world.spawn(()).id();
+    let c = world.spawn(()).id();
+
+    world.insert_relation(a, Likes(1), b).unwrap();
+    world.insert_relation(a, Likes(2), c).unwrap();
+    world.insert_relation(b, OwnedBy, c).unwrap();
+    world.insert_relation(a, Friend, c).unwrap();
+
+    type Set = RelationSet<(Likes, OwnedBy, Friend)>;
+
+    let data = WorldDump::<Set, _>::new(&world, (), EpochId::start()).serialize_bin();
+
+    let mut world2 = World::new();
+
+    let mut buffer = ActionBuffer::new();
+    let mut actions = buffer.encoder(&world2);
+
+    WorldLoad::<Set, _>::new(&world2, NoMark)
+        .load_bin(&mut actions, &data)
+        .unwrap();
+    buffer.execute(&mut world2);
+
+    let likes = world2
+        .view::<Relates<&Likes>>()
+        .try_get(a)
+        .unwrap()
+        .map(|(r, e)| (r.clone(), e.id()))
+        .collect::<Vec<_>>();
+    assert_eq!(likes, [(Likes(1), b), (Likes(2), c)]);
+
+    let owner = world2
+        .view::<Relates<&OwnedBy>>()
+        .try_get(b)
+        .unwrap()
+        .map(|(_, e)| e.id())
+        .collect::<Vec<EntityId>>();
+    assert_eq!(owner, [c]);
+
+    for (x, y) in [(a, c), (c, a)] {
+        let friends = world2
+            .view::<Relates<&Friend>>()
+            .try_get(x)
+            .unwrap()
+            .map(|(_, e)| e.id())
+            .collect::<Vec<EntityId>>();
+        assert_eq!(friends, [y]);
+    }
+}
+
+#[cfg(feature = "flow")]
+#[test]
+fn test_dump_flows() {
+    use ::nanoserde::{DeBin, SerBin};
+
+    use crate::flow::{FlowPriority, SavedFlow};
+
+    let saved = vec![
+        SavedFlow::new("quest".into(), FlowPriority::High, vec![1, 2, 3]),
+        SavedFlow::new("cutscene".into(), FlowPriority::Low, Vec::new()),
+    ];
+
+    let data = saved.serialize_bin();
+    let loaded = Vec::<SavedFlow>::deserialize_bin(&data).unwrap();
+    assert_eq!(loaded, saved);
+}
@@ -0,0 +1,146 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::{
+    action::ActionEncoder,
+    entity::EntityId,
+    epoch::EpochId,
+    query::SendImmutableQuery,
+    relation::{OriginComponent, Relation},
+    world::World,
+};
+
+use super::{
+    query::DumpQuery, DumpItem, DumpSet, DumpSlot, Dumper, EntityDump, LoadSet, LoadSlot, Loader,
+    Mark,
+};
+
+/// Set of relation types that can be dumped and loaded
+/// with [`WorldDump`](super::WorldDump) and [`WorldLoad`](super::WorldLoad).
+///
+/// Each relation type is dumped from the origin side
+/// as a list of target ids with relation values.
+/// Symmetric relations are dumped from both entities.
+///
+/// Loaded relations are inserted between entities with dumped ids.
+/// Targets that do not exist in the world are spawned and marked.
+/// Existing relations are not removed and relation values are replaced.
+///
+/// Relations are inserted in dumped order, so for [ordered](crate::relation::Relation::ORDERED)
+/// relations the order of targets is restored for each origin.
+pub struct RelationSet<T>(PhantomData<fn() -> T>);
+
+macro_rules! set {
+    () => {
+        /* Don't implement for empty tuple */
+    };
+    ($($a:ident)+) => {
+        #[allow(non_snake_case)]
+        #[allow(unused_assignments)]
+        #[allow(unused_parens)]
+        impl<$($a),+> DumpSet for RelationSet<($($a,)+)>
+        where
+            $($a: Relation + Sync,)+
+        {
+            type DumpSlots<'a> = ($(DumpSlot<'a, [(EntityId, $a)]>,)+);
+
+            #[inline]
+            fn dump_world<Fi, Du, Er>(world: &World, filter: Fi, after_epoch: EpochId, dumper: &mut Du) -> Result<(), Er>
+            where
+                Fi: SendImmutableQuery,
+                Du: for<'a> Dumper<Self, Error = Er>,
+            {
+                let view = world.view_with(DumpQuery::<($(OriginComponent<$a>,)+)>::new(after_epoch)).filter(filter);
+                let mut iter = view.iter();
+
+                iter.try_for_each(|(e, ($($a),+))| {
+                    let mut present = 0;
+                    let mut modified = 0;
+
+                    let slots = indexed_tuple!(idx => $(match $a {
+                        DumpItem::Missing => DumpSlot::Skipped,
+                        DumpItem::Modified(comp) => {
+                            modified |= (1 << idx);
+                            DumpSlot::Component(comp.targets())
+                        }
+                        DumpItem::Unmodified => {
+                            present |= (1 << idx);
+                            DumpSlot::Skipped
+                        }
+                    }),+);
+                    let bits = e.id().bits();
+                    dumper.dump(EntityDump([bits, present | modified, modified]), slots)
+                })
+            }
+        }
+
+        #[allow(non_snake_case)]
+        #[allow(unused_assignments)]
+        #[allow(unused_parens)]
+        impl<$($a),+> LoadSet for RelationSet<($($a,)+)>
+        where
+            $($a: Relation + Send,)+
+        {
+            type LoadSlots<'a> = ($(LoadSlot<'a, Vec<(EntityId, $a)>>,)+);
+
+            fn load_world<Lo, Er, Ma>(
+                _world: &World,
+                marker: Ma,
+                actions: &mut ActionEncoder,
+                loader: &mut Lo,
+            ) -> Result<(), Er>
+            where
+                Lo: for<'a> Loader<Self, Error = Er>,
+                Ma: Mark,
+            {
+                while let Some(next) = loader.next()? {
+                    let EntityDump([bits, _, modified]) = next;
+
+                    let mut slots = indexed_tuple!(idx => $(
+                        if modified & (1 << idx) != 0 {
+                            LoadSlot::<Vec<(EntityId, $a)>>::Missing
+                        } else {
+                            LoadSlot::<Vec<(EntityId, $a)>>::Skipped
+                        }
+                    ),+);
+
+                    loader.load(&mut slots)?;
+
+                    let Some(id) = EntityId::from_bits(bits) else {
+                        continue;
+                    };
+
+                    let ($($a,)+) = slots;
+                    $(
+                        let $a = match $a {
+                            LoadSlot::Skipped => Vec::new(),
+                            LoadSlot::Missing => {
+                                unreachable!("Must be created by loader");
+                            }
+                            LoadSlot::Existing(_) => unreachable!("Relations are never loaded in place"),
+                            LoadSlot::Created(relations) => relations,
+                        };
+                    )+
+
+                    actions.closure(move |world| {
+                        world.spawn_or_insert(id, ());
+                        marker.mark(world, id);
+                        $(
+                            for (target, relation) in $a {
+                                if !world.is_alive(target) {
+                                    world.spawn_or_insert(target, ());
+                                    marker.mark(world, target);
+                                }
+                                let _ = world.insert_relation(id, relation, target);
+                            }
+                        )+
+                    });
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+for_tuple!(set);
@@ -1,10 +1,27 @@
 //! World serialization with [`serde`].
 
-use serde::ser::{Serialize, SerializeSeq, Serializer};
+use core::{convert::Infallible, fmt, marker::PhantomData};
 
-use crate::query::SendImmutableQuery;
+use alloc::vec::{self, Vec};
+use serde::{
+    de::{Deserialize, Deserializer, Error as _, SeqAccess, Visitor},
+    ser::{Serialize, SerializeSeq, Serializer},
+};
 
-use super::{DumpSet, DumpSlot, Dumper, EntityDump, WorldDump};
+use crate::{
+    action::ActionEncoder, entity::EntityId, query::SendImmutableQuery, relation::Relation,
+};
+
+#[cfg(feature = "flow")]
+use alloc::string::String;
+
+#[cfg(feature = "flow")]
+use crate::flow::{FlowPriority, SavedFlow};
+
+use super::{
+    DumpSet, DumpSlot, Dumper, EntityDump, LoadSet, LoadSlot, Loader, Mark, RelationSet, WorldDump,
+    WorldLoad,
+};
 
 /// Wrapper for `serde::ser::SerializeSeq` that implements `Dumper`.
 pub struct SerdeDumper<'a, S>(pub &'a mut S);
@@ -17,7 +34,7 @@ macro_rules! dumper {
         #[allow(non_snake_case)]
         impl<'a $(, $a)+> Serialize for SerializeDump<($(DumpSlot<'a, $a>,)+)>
         where
-            $($a: Serialize,)+
+            $($a: Serialize + ?Sized,)+
         {
             fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
             where
@@ -66,3 +83,262 @@ macro_rules! dumper {
 }
 
 for_tuple!(dumper);
+
+/// Entity dumped with [`RelationSet`] with relations of modified types.
+struct DeserializeRelations<S> {
+    entity: [u64; 3],
+    slots: S,
+}
+
+/// Buffered loader for relations deserialized with [`serde`].
+struct SerdeRelationsLoader<S> {
+    iter: vec::IntoIter<DeserializeRelations<S>>,
+    next: Option<S>,
+}
+
+macro_rules! relations {
+    () => {};
+    ($($a:ident)+) => {
+        impl<'a $(, $a)+, Se> Dumper<RelationSet<($($a,)+)>> for SerdeDumper<'_, Se>
+        where
+            $($a: Relation + Serialize + Sync,)+
+            Se: SerializeSeq,
+        {
+            type Error = Se::Error;
+            fn dump(&mut self, entity: EntityDump, slots: ($(DumpSlot<'_, [(EntityId, $a)]>,)+)) -> Result<(), Se::Error> {
+                self.0.serialize_element(&SerializeDump(entity.0, slots))
+            }
+        }
+
+        impl<'a $(, $a)+, Fi> Serialize for WorldDump<'a, RelationSet<($($a,)+)>, Fi>
+        where
+            Fi: SendImmutableQuery + Copy,
+            $($a: Relation + Serialize + Sync,)+
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                let mut seq = serializer.serialize_seq(None)?;
+                let mut dumper = SerdeDumper(&mut seq);
+                <RelationSet<($($a,)+)> as DumpSet>::dump_world(self.world, self.filter, self.epoch, &mut dumper)?;
+                seq.end()
+            }
+        }
+
+        #[allow(non_snake_case)]
+        #[allow(unused_assignments)]
+        impl<'de $(, $a)+> Deserialize<'de> for DeserializeRelations<($(LoadSlot<'static, Vec<(EntityId, $a)>>,)+)>
+        where
+            $($a: Relation + Deserialize<'de>,)+
+        {
+            fn deserialize<De>(deserializer: De) -> Result<Self, De::Error>
+            where
+                De: Deserializer<'de>,
+            {
+                struct RelationsVisitor<T>(PhantomData<fn() -> T>);
+
+                impl<'de $(, $a)+> Visitor<'de> for RelationsVisitor<($($a,)+)>
+                where
+                    $($a: Relation + Deserialize<'de>,)+
+                {
+                    type Value = ([u64; 3], ($(LoadSlot<'static, Vec<(EntityId, $a)>>,)+));
+
+                    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                        f.write_str("entity with relations")
+                    }
+
+                    fn visit_seq<Sa>(self, mut seq: Sa) -> Result<Self::Value, Sa::Error>
+                    where
+                        Sa: SeqAccess<'de>,
+                    {
+                        let entity: [u64; 3] = seq.next_element()?.ok_or_else(|| Sa::Error::invalid_length(0, &self))?;
+                        let mut len = 1;
+                        let slots = indexed_tuple!(idx => $(
+                            if entity[2] & (1 << idx) != 0 {
+                                let relations: Vec<(EntityId, $a)> = seq.next_element()?.ok_or_else(|| Sa::Error::invalid_length(len, &self))?;
+                                len += 1;
+                                LoadSlot::Created(relations)
+                            } else {
+                                LoadSlot::Skipped
+                            }
+                        ),+);
+                        Ok((entity, slots))
+                    }
+                }
+
+                let (entity, slots) = deserializer.deserialize_seq(RelationsVisitor::<($($a,)+)>(PhantomData))?;
+                Ok(DeserializeRelations { entity, slots })
+            }
+        }
+
+        impl<$($a),+> Loader<RelationSet<($($a,)+)>> for SerdeRelationsLoader<($(LoadSlot<'static, Vec<(EntityId, $a)>>,)+)>
+        where
+            $($a: Relation + Send,)+
+        {
+            type Error = Infallible;
+
+            fn next(&mut self) -> Result<Option<EntityDump>, Infallible> {
+                match self.iter.next() {
+                    None => Ok(None),
+                    Some(next) => {
+                        self.next = Some(next.slots);
+                        Ok(Some(EntityDump(next.entity)))
+                    }
+                }
+            }
+
+            fn load(&mut self, slots: &mut ($(LoadSlot<'_, Vec<(EntityId, $a)>>,)+)) -> Result<(), Infallible> {
+                *slots = self.next.take().unwrap();
+                Ok(())
+            }
+        }
+
+        impl<$($a,)+ Ma> WorldLoad<'_, RelationSet<($($a,)+)>, Ma>
+        where
+            Ma: Mark,
+            $($a: Relation + Send,)+
+        {
+            /// Loads relations dumped with [`WorldDump`] from `deserializer`.
+            pub fn load_serde<'de, De>(&self, actions: &mut ActionEncoder, deserializer: De) -> Result<(), De::Error>
+            where
+                De: Deserializer<'de>,
+                $($a: Deserialize<'de>,)+
+            {
+                let entities = Vec::<DeserializeRelations<($(LoadSlot<'static, Vec<(EntityId, $a)>>,)+)>>::deserialize(deserializer)?;
+                let mut loader = SerdeRelationsLoader {
+                    iter: entities.into_iter(),
+                    next: None,
+                };
+                match <RelationSet<($($a,)+)> as LoadSet>::load_world(self.world, self.marker, actions, &mut loader) {
+                    Ok(()) => Ok(()),
+                    Err(never) => match never {},
+                }
+            }
+        }
+    };
+}
+
+for_tuple!(relations);
+
+/// Serialized as tuple of flow name, priority index and state data.
+#[cfg(feature = "flow")]
+impl Serialize for SavedFlow {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (self.name(), self.priority().index() as u8, self.data()).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "flow")]
+impl<'de> Deserialize<'de> for SavedFlow {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (name, priority, data) = <(String, u8, Vec<u8>)>::deserialize(deserializer)?;
+        let priority = FlowPriority::from_index(priority as usize)
+            .ok_or_else(|| D::Error::custom("invalid flow priority"))?;
+        Ok(SavedFlow::new(name, priority, data))
+    }
+}
+
+#[test]
+fn test_dump_relations() {
+    use ::serde::{Deserialize, Serialize};
+
+    use super::NoMark;
+    use crate::{action::ActionBuffer, epoch::EpochId, relation::Relates, world::World};
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Likes(u32);
+
+    impl Relation for Likes {
+        const ORDERED: bool = true;
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct OwnedBy;
+
+    impl Relation for OwnedBy {
+        const EXCLUSIVE: bool = true;
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Friend;
+
+    impl Relation for Friend {
+        const SYMMETRIC: bool = true;
+    }
+
+    let mut world = World::new();
+
+    let a = world.spawn(()).id();
+    let b = world.spawn(()).id();
+    let c = world.spawn(()).id();
+
+    world.insert_relation(a, Likes(1), c).unwrap();
+    world.insert_relation(a, Likes(2), b).unwrap();
+    world.insert_relation(b, OwnedBy, c).unwrap();
+    world.insert_relation(a, Friend, c).unwrap();
+
+    type Set = RelationSet<(Likes, OwnedBy, Friend)>;
+
+    let json =
+        serde_json::to_string(&WorldDump::<Set, _>::new(&world, (), EpochId::start())).unwrap();
+
+    let mut world2 = World::new();
+
+    let mut buffer = ActionBuffer::new();
+    let mut actions = buffer.encoder(&world2);
+
+    WorldLoad::<Set, _>::new(&world2, NoMark)
+        .load_serde(&mut actions, &mut serde_json::Deserializer::from_str(&json))
+        .unwrap();
+    buffer.execute(&mut world2);
+
+    let likes = world2
+        .view::<Relates<&Likes>>()
+        .try_get(a)
+        .unwrap()
+        .map(|(r, e)| (r.clone(), e.id()))
+        .collect::<Vec<_>>();
+    assert_eq!(likes, [(Likes(1), c), (Likes(2), b)]);
+
+    let owner = world2
+        .view::<Relates<&OwnedBy>>()
+        .try_get(b)
+        .unwrap()
+        .map(|(_, e)| e.id())
+        .collect::<Vec<EntityId>>();
+    assert_eq!(owner, [c]);
+
+    for (x, y) in [(a, c), (c, a)] {
+        let friends = world2
+            .view::<Relates<&Friend>>()
+            .try_get(x)
+            .unwrap()
+            .map(|(_, e)| e.id())
+            .collect::<Vec<EntityId>>();
+        assert_eq!(friends, [y]);
+    }
+}
+
+#[cfg(feature = "flow")]
+#[test]
+fn test_dump_flows() {
+    let saved = vec![
+        SavedFlow::new("quest".into(), FlowPriority::High, vec![1, 2, 3]),
+        SavedFlow::new("cutscene".into(), FlowPriority::Low, Vec::new()),
+    ];
+
+    let json = serde_json::to_string(&saved).unwrap();
+    let loaded = serde_json::from_str::<Vec<SavedFlow>>(&json).unwrap();
+    assert_eq!(loaded, saved);
+
+    // Flow with priority index out of range.
+    let invalid = serde_json::to_string(&[("quest", 7u8, Vec::<u8>::new())]).unwrap();
+    assert!(serde_json::from_str::<Vec<SavedFlow>>(&invalid).is_err());
+}
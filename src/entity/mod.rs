@@ -18,6 +18,7 @@ use crate::{
     query::{DefaultQuery, ImmutableQuery, IntoQuery, QueryItem},
     view::ViewOne,
     world::{World, WorldLocal},
+    DespawnError,
     NoSuchEntity,
     ResultEntityError,
 };
@@ -1111,8 +1112,20 @@ impl<'a> EntityRef<'a> {
     }
 
     /// Despawns the entity.
+    ///
+    /// Entity protected from despawning by a relation is left alive.
+    /// Use [`EntityRef::try_despawn`] to detect this.
     #[inline]
     pub fn despawn(self) {
+        let _ = unsafe { self.world.despawn_ref(self.id, self.loc) };
+    }
+
+    /// Despawns the entity.
+    ///
+    /// Fails with `Err(DespawnError::Denied)` if entity is protected
+    /// from despawning by a relation.
+    #[inline]
+    pub fn try_despawn(self) -> Result<(), DespawnError> {
         unsafe { self.world.despawn_ref(self.id, self.loc) }
     }
 
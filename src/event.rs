@@ -0,0 +1,112 @@
+//! Event logs read with independent cursors.
+//!
+//! [`Events`] is an append-only log of events.
+//! Each consumer keeps its own [`EventCursor`] and reads events
+//! added after the cursor position, so any number of consumers
+//! may read the same events.
+
+use alloc::vec::Vec;
+
+/// Position of a reader in [`Events`].
+///
+/// Each consumer of the events keeps its own cursor,
+/// for example in system's [`State`](crate::system::State).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EventCursor {
+    next: u64,
+}
+
+/// Log of events of type `E`.
+///
+/// Usually stored as a resource.
+/// Systems may send and read events with [`ResMut`](crate::resources::ResMut)
+/// and [`Res`](crate::resources::Res).
+///
+/// Events are never removed automatically.
+/// Call [`Events::clear`] periodically, e.g. once per frame
+/// after all consumers have read events.
+/// Cursors skip events cleared before they were read.
+pub struct Events<E> {
+    events: Vec<E>,
+
+    /// Sequence number of the first event in `events`.
+    start: u64,
+}
+
+impl<E> Default for Events<E> {
+    #[inline]
+    fn default() -> Self {
+        Events::new()
+    }
+}
+
+impl<E> Events<E> {
+    /// Returns new empty event log.
+    #[inline]
+    pub const fn new() -> Self {
+        Events {
+            events: Vec::new(),
+            start: 0,
+        }
+    }
+
+    /// Returns number of events in the log.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if log contains no events.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Returns all events in the log.
+    #[inline]
+    pub fn events(&self) -> &[E] {
+        &self.events
+    }
+
+    /// Adds event to the log.
+    #[inline]
+    pub fn send(&mut self, event: E) {
+        self.events.push(event);
+    }
+
+    /// Returns events not yet read with the cursor
+    /// and advances cursor past them.
+    #[inline]
+    pub fn read(&self, cursor: &mut EventCursor) -> &[E] {
+        let skip = cursor.next.saturating_sub(self.start) as usize;
+        cursor.next = self.start + self.events.len() as u64;
+        &self.events[skip.min(self.events.len())..]
+    }
+
+    /// Returns next event not yet read with the cursor
+    /// and advances cursor past it.
+    #[inline]
+    pub fn read_one(&self, cursor: &mut EventCursor) -> Option<&E> {
+        let idx = cursor.next.saturating_sub(self.start) as usize;
+        let event = self.events.get(idx)?;
+        cursor.next = self.start + idx as u64 + 1;
+        Some(event)
+    }
+
+    /// Returns cursor positioned after the last event in the log.
+    #[inline]
+    pub fn cursor(&self) -> EventCursor {
+        EventCursor {
+            next: self.start + self.events.len() as u64,
+        }
+    }
+
+    /// Removes all events from the log.
+    ///
+    /// Cursors remain valid.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.start += self.events.len() as u64;
+        self.events.clear();
+    }
+}
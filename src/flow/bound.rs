@@ -0,0 +1,275 @@
+use core::{
+    any::type_name,
+    marker::PhantomData,
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, Waker},
+};
+
+use alloc::{sync::Arc, task::Wake};
+use amity::spin::Spin;
+
+use crate::{
+    entity::{EntityId, EntityRef},
+    type_id,
+    wakers::WakeToken,
+    world::World,
+};
+
+use super::{
+    get_flow_world, Flow, FlowEntity, FlowHandle, FlowPriority, FlowWorld, IntoEntityFlow,
+    IntoEntityFlowOutput, MakeFlow,
+};
+
+/// Waker registered for bound component.
+/// Marks binding as broken when woken.
+struct Binding {
+    broken: AtomicBool,
+
+    /// Waker of the flow task.
+    /// Set when flow is polled.
+    task: Spin<Option<Waker>>,
+}
+
+impl Wake for Binding {
+    #[inline]
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    #[inline]
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.broken.store(true, Ordering::Release);
+
+        let task = self.task.lock().clone();
+        if let Some(task) = task {
+            task.wake();
+        }
+    }
+}
+
+/// Binds flow to the component of the entity.
+///
+/// Binding is broken when component is dropped or replaced.
+/// Returns broken binding if entity does not have the component.
+fn bind<T>(world: &mut World, entity: EntityId, token: &WakeToken) -> Arc<Binding>
+where
+    T: 'static,
+{
+    let binding = Arc::new(Binding {
+        broken: AtomicBool::new(false),
+        task: Spin::new(None),
+    });
+
+    let bound = match world.entities().get_location(entity) {
+        // Reserved entities have no components.
+        Some(loc) if loc.arch != u32::MAX => world.archetypes_mut()[loc.arch as usize]
+            .register_drop_waker(entity, type_id::<T>(), token, &Waker::from(binding.clone())),
+        _ => false,
+    };
+
+    if !bound {
+        binding.broken.store(true, Ordering::Relaxed);
+    }
+
+    binding
+}
+
+/// Flow that is cancelled when bound component is dropped or replaced.
+///
+/// Registration in the component is discarded when token is dropped.
+struct BoundFlow<F> {
+    binding: Arc<Binding>,
+    _token: WakeToken,
+    flow: F,
+}
+
+impl<F> Flow for BoundFlow<F>
+where
+    F: Flow,
+{
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<F::Output>> {
+        // Safety: `flow` is never moved.
+        let me = unsafe { self.get_unchecked_mut() };
+
+        {
+            let mut task = me.binding.task.lock();
+            match &*task {
+                Some(task) if task.will_wake(cx.waker()) => {}
+                _ => *task = Some(cx.waker().clone()),
+            }
+        }
+
+        if me.binding.broken.load(Ordering::Acquire) {
+            // Terminate flow if component is dropped or replaced.
+            return Poll::Ready(None);
+        }
+
+        // Safety: `flow` is never moved and this is called from flow execution.
+        let flow = unsafe { Pin::new_unchecked(&mut me.flow) };
+        unsafe { flow.poll(cx) }
+    }
+}
+
+struct BoundIntoFlow<T, F> {
+    entity: EntityId,
+    binding: Arc<Binding>,
+    token: WakeToken,
+    f: F,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T, F> MakeFlow for BoundIntoFlow<T, F>
+where
+    T: 'static,
+    F: IntoEntityFlow,
+{
+    type Flow = BoundFlow<F::Flow>;
+
+    fn priority(&self) -> FlowPriority {
+        self.f.priority()
+    }
+
+    fn entity(&self) -> Option<EntityId> {
+        Some(self.entity)
+    }
+
+    fn type_name(&self) -> &'static str {
+        type_name::<F>()
+    }
+
+    fn make_flow(self) -> Option<BoundFlow<F::Flow>> {
+        if self.binding.broken.load(Ordering::Acquire) {
+            // Component was dropped or replaced before flow started.
+            return None;
+        }
+
+        let flow = self.f.into_entity_flow(FlowEntity::new(self.entity))?;
+        Some(BoundFlow {
+            binding: self.binding,
+            _token: self.token,
+            flow,
+        })
+    }
+}
+
+impl World {
+    /// Spawns a flow for an entity bound to the entity's component.
+    /// It will be polled during [`Flows::execute`](super::Flows::execute) until completion,
+    /// until the entity is despawned or until component is dropped or replaced.
+    ///
+    /// Any component type can be used.
+    /// Binding is registered immediately, so component dropped or replaced
+    /// before the flow is first polled cancels the flow as well.
+    /// Flow is cancelled without polling if entity does not have the component.
+    ///
+    /// Returns [`FlowHandle`] to await flow's output or abort it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use core::time::Duration;
+    /// # use edict::{entity::EntityId, flow::{FlowEntity, FlowTime, Flows}, world::World};
+    /// struct Chasing {
+    ///     target: EntityId,
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.ensure_external_registered::<Chasing>();
+    /// let mut flows = Flows::new();
+    /// world.insert_resource(FlowTime::with_step(Duration::from_millis(16)));
+    ///
+    /// let target = world.spawn(()).id();
+    /// let e = world.spawn_external((Chasing { target },)).id();
+    ///
+    /// let mut chase = world.spawn_flow_bound::<Chasing, _>(e, |e: FlowEntity| async move {
+    ///     loop {
+    ///         e.sleep_ticks(1).await;
+    ///     }
+    /// });
+    ///
+    /// flows.execute(&mut world);
+    /// world.drop::<Chasing>(e).unwrap();
+    /// flows.execute(&mut world);
+    ///
+    /// assert!(chase.is_finished());
+    /// ```
+    #[track_caller]
+    pub fn spawn_flow_bound<T, F>(
+        &mut self,
+        entity: EntityId,
+        flow: F,
+    ) -> FlowHandle<IntoEntityFlowOutput<F>>
+    where
+        T: 'static,
+        F: IntoEntityFlow,
+    {
+        let token = WakeToken::default();
+        let binding = bind::<T>(self, entity, &token);
+
+        self.new_flows.get_mut().add(BoundIntoFlow::<T, F> {
+            entity,
+            binding,
+            token,
+            f: flow,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl FlowWorld {
+    /// Spawns a flow for an entity bound to the entity's component.
+    /// It will be polled during [`Flows::execute`](super::Flows::execute) until completion,
+    /// until the entity is despawned or until component is dropped or replaced.
+    ///
+    /// Returns [`FlowHandle`] to await flow's output or abort it.
+    #[track_caller]
+    pub fn spawn_flow_bound<T, F>(
+        self,
+        entity: EntityId,
+        flow: F,
+    ) -> FlowHandle<IntoEntityFlowOutput<F>>
+    where
+        T: 'static,
+        F: IntoEntityFlow,
+    {
+        // Safety: world reference does not escape this scope.
+        let world = unsafe { self.get() };
+
+        world.spawn_flow_bound::<T, F>(entity, flow)
+    }
+}
+
+impl FlowEntity {
+    /// Spawns a new flow for the entity bound to the entity's component.
+    ///
+    /// Returns [`FlowHandle`] to await flow's output or abort it.
+    #[track_caller]
+    pub fn spawn_flow_bound<T, F>(self, flow: F) -> FlowHandle<IntoEntityFlowOutput<F>>
+    where
+        T: 'static,
+        F: IntoEntityFlow,
+    {
+        // Safety: world reference does not escape this scope.
+        let world = unsafe { get_flow_world() };
+
+        world.spawn_flow_bound::<T, F>(self.id(), flow)
+    }
+}
+
+impl EntityRef<'_> {
+    /// Spawns a new flow for the entity bound to the entity's component.
+    ///
+    /// Returns [`FlowHandle`] to await flow's output or abort it.
+    #[track_caller]
+    pub fn spawn_flow_bound<T, F>(&mut self, flow: F) -> FlowHandle<IntoEntityFlowOutput<F>>
+    where
+        T: 'static,
+        F: IntoEntityFlow,
+    {
+        let id = self.id();
+        self.world().spawn_flow_bound::<T, F>(id, flow)
+    }
+}
@@ -0,0 +1,104 @@
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+/// Limits amount of work done by single [`Flows::execute`](super::Flows::execute) call.
+///
+/// Ready flows that were not polled due to exhausted budget
+/// are carried over to the next call and polled first.
+///
+/// # Example
+///
+/// ```
+/// # use edict::flow::{FlowBudget, Flows};
+/// let mut flows = Flows::new();
+/// flows.set_budget(FlowBudget::unlimited().max_polls(100));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FlowBudget {
+    max_polls: Option<usize>,
+
+    #[cfg(feature = "std")]
+    max_time: Option<Duration>,
+}
+
+impl FlowBudget {
+    /// Returns budget without limits.
+    /// All ready flows are polled.
+    pub const fn unlimited() -> Self {
+        FlowBudget {
+            max_polls: None,
+
+            #[cfg(feature = "std")]
+            max_time: None,
+        }
+    }
+
+    /// Limits number of flow polls.
+    pub const fn max_polls(mut self, polls: usize) -> Self {
+        self.max_polls = Some(polls);
+        self
+    }
+
+    /// Limits time spent polling flows.
+    ///
+    /// Time is checked before each poll,
+    /// so long polls may exceed the budget.
+    #[cfg(feature = "std")]
+    pub const fn max_time(mut self, time: Duration) -> Self {
+        self.max_time = Some(time);
+        self
+    }
+
+    /// Returns limit of flow polls.
+    pub const fn polls_limit(&self) -> Option<usize> {
+        self.max_polls
+    }
+
+    /// Returns limit of time spent polling flows.
+    #[cfg(feature = "std")]
+    pub const fn time_limit(&self) -> Option<Duration> {
+        self.max_time
+    }
+
+    pub(super) fn start(&self) -> BudgetTracker {
+        BudgetTracker {
+            polls: self.max_polls,
+
+            #[cfg(feature = "std")]
+            deadline: self.max_time.map(|time| Instant::now() + time),
+        }
+    }
+}
+
+/// Tracks budget spending during one execution.
+pub(super) struct BudgetTracker {
+    polls: Option<usize>,
+
+    #[cfg(feature = "std")]
+    deadline: Option<Instant>,
+}
+
+impl BudgetTracker {
+    /// Returns `true` if no more flows may be polled.
+    pub fn exhausted(&self) -> bool {
+        if self.polls == Some(0) {
+            return true;
+        }
+
+        #[cfg(feature = "std")]
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Spends budget on one poll.
+    pub fn spend(&mut self) {
+        if let Some(polls) = &mut self.polls {
+            *polls -= 1;
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use core::{future::Future, marker::PhantomData};
+
+use super::{FlowEntity, FlowWorld, IntoEntityFlow, IntoFlow};
+
+/// Flow context that [`flow_fn!`](super::flow_fn) closures accept.
+///
+/// Implemented for [`FlowWorld`] and [`FlowEntity`].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a flow context",
+    label = "expected `FlowWorld` or `FlowEntity`",
+    note = "`flow_fn!` closure argument must be `FlowWorld` or `FlowEntity`"
+)]
+pub trait FlowContext: Copy + Send + 'static {}
+
+impl FlowContext for FlowWorld {}
+impl FlowContext for FlowEntity {}
+
+/// Closure that can be spawned as a flow.
+///
+/// Created with [`flow_fn!`](super::flow_fn).
+/// Implements [`IntoFlow`] when context is [`FlowWorld`]
+/// and [`IntoEntityFlow`] when context is [`FlowEntity`].
+///
+/// # Example
+///
+/// ```
+/// # use core::time::Duration;
+/// # use edict::{flow::{flow_fn, FlowEntity, FlowTime, FlowWorld}, world::World};
+/// let mut world = World::new();
+/// world.insert_resource(FlowTime::with_step(Duration::from_millis(16)));
+/// let e = world.spawn(()).id();
+///
+/// let name = String::from("flow");
+/// world.spawn_flow(flow_fn!(|world: FlowWorld| {
+///     world.sleep_ticks(1).await;
+///     world.insert_resource(name);
+/// }));
+///
+/// world.spawn_flow_for(e, flow_fn!(|e: FlowEntity| {
+///     e.despawn();
+/// }));
+/// ```
+pub struct FlowClosure<F, C> {
+    f: F,
+    marker: PhantomData<fn(C)>,
+}
+
+impl<F, C> FlowClosure<F, C> {
+    /// Wraps closure that accepts flow context and returns a future.
+    ///
+    /// Prefer [`flow_fn!`](super::flow_fn) that generates the closure.
+    #[inline]
+    pub fn new<Fut>(f: F) -> Self
+    where
+        C: FlowContext,
+        F: FnOnce(C) -> Fut + 'static,
+        Fut: Future + Send + 'static,
+    {
+        FlowClosure {
+            f,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<F, Fut> IntoFlow for FlowClosure<F, FlowWorld>
+where
+    F: FnOnce(FlowWorld) -> Fut + 'static,
+    Fut: Future + Send + 'static,
+{
+    type Flow = <F as IntoFlow>::Flow;
+
+    #[inline]
+    fn into_flow(self, world: FlowWorld) -> Option<Self::Flow> {
+        self.f.into_flow(world)
+    }
+}
+
+impl<F, Fut> IntoEntityFlow for FlowClosure<F, FlowEntity>
+where
+    F: FnOnce(FlowEntity) -> Fut + 'static,
+    Fut: Future + Send + 'static,
+{
+    type Flow = <F as IntoEntityFlow>::Flow;
+
+    #[inline]
+    fn into_entity_flow(self, e: FlowEntity) -> Option<Self::Flow> {
+        self.f.into_entity_flow(e)
+    }
+}
@@ -0,0 +1,262 @@
+use core::{
+    future::{Future, IntoFuture},
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use alloc::{boxed::Box, vec::Vec};
+
+/// Future that is either in progress or has completed with output.
+enum MaybeDone<F: Future> {
+    Pending(F),
+    Done(F::Output),
+    Taken,
+}
+
+impl<F: Future> MaybeDone<F> {
+    /// Polls the future if it is not done yet.
+    /// Returns `true` if output is ready.
+    ///
+    /// # Safety
+    ///
+    /// `self` must be pinned.
+    unsafe fn poll(&mut self, cx: &mut Context<'_>) -> bool {
+        match self {
+            MaybeDone::Pending(fut) => {
+                // Safety: `self` is pinned.
+                let fut = unsafe { Pin::new_unchecked(fut) };
+                match fut.poll(cx) {
+                    Poll::Pending => false,
+                    Poll::Ready(output) => {
+                        *self = MaybeDone::Done(output);
+                        true
+                    }
+                }
+            }
+            MaybeDone::Done(_) => true,
+            MaybeDone::Taken => panic!("Future polled after completion"),
+        }
+    }
+
+    fn take(&mut self) -> F::Output {
+        match mem::replace(self, MaybeDone::Taken) {
+            MaybeDone::Done(output) => output,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Future that polls two futures concurrently
+/// and resolves to their outputs when both complete.
+///
+/// Created by [`join`].
+#[must_use = "Future does nothing unless polled"]
+pub struct Join<A: Future, B: Future> {
+    a: MaybeDone<A>,
+    b: MaybeDone<B>,
+}
+
+impl<A, B> Future for Join<A, B>
+where
+    A: Future,
+    B: Future,
+{
+    type Output = (A::Output, B::Output);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<(A::Output, B::Output)> {
+        // Safety: fields are never moved.
+        let me = unsafe { self.get_unchecked_mut() };
+
+        // Poll both to make progress on each.
+        let a = unsafe { me.a.poll(cx) };
+        let b = unsafe { me.b.poll(cx) };
+
+        if a && b {
+            Poll::Ready((me.a.take(), me.b.take()))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Runs two futures concurrently within one flow
+/// and resolves to their outputs when both complete.
+///
+/// # Example
+///
+/// ```
+/// # use edict::{flow::{join, FlowEntity}, world::World};
+/// # let mut world = World::new();
+/// # let a = world.spawn(()).id();
+/// world.spawn_flow_for(a, |a: FlowEntity| async move {
+///     let (x, y) = join(async { 1 }, async { 2 }).await;
+///     assert_eq!(x + y, 3);
+/// });
+/// ```
+pub fn join<A, B>(a: A, b: B) -> Join<A::IntoFuture, B::IntoFuture>
+where
+    A: IntoFuture,
+    B: IntoFuture,
+{
+    Join {
+        a: MaybeDone::Pending(a.into_future()),
+        b: MaybeDone::Pending(b.into_future()),
+    }
+}
+
+/// Future that polls many futures concurrently
+/// and resolves to their outputs when all complete.
+///
+/// Created by [`join_all`].
+#[must_use = "Future does nothing unless polled"]
+pub struct JoinAll<F: Future> {
+    // Boxed slice is never reallocated, so elements stay pinned.
+    futures: Pin<Box<[MaybeDone<F>]>>,
+}
+
+impl<F> Future for JoinAll<F>
+where
+    F: Future,
+{
+    type Output = Vec<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Vec<F::Output>> {
+        // Safety: elements are never moved.
+        let futures = unsafe { self.futures.as_mut().get_unchecked_mut() };
+
+        let mut done = true;
+        for fut in futures.iter_mut() {
+            done &= unsafe { fut.poll(cx) };
+        }
+
+        if done {
+            Poll::Ready(futures.iter_mut().map(MaybeDone::take).collect())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Runs all futures concurrently within one flow
+/// and resolves to their outputs in the same order when all complete.
+pub fn join_all<I>(futures: I) -> JoinAll<<I::Item as IntoFuture>::IntoFuture>
+where
+    I: IntoIterator,
+    I::Item: IntoFuture,
+{
+    let futures = futures
+        .into_iter()
+        .map(|fut| MaybeDone::Pending(fut.into_future()))
+        .collect();
+
+    JoinAll {
+        futures: Box::into_pin(futures),
+    }
+}
+
+/// Output of [`select`] with output of the future that completed first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Either<A, B> {
+    /// First future completed first.
+    Left(A),
+
+    /// Second future completed first.
+    Right(B),
+}
+
+/// Future that polls two futures concurrently
+/// and resolves to output of the one that completes first.
+///
+/// Created by [`select`].
+#[must_use = "Future does nothing unless polled"]
+pub struct Select<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> Future for Select<A, B>
+where
+    A: Future,
+    B: Future,
+{
+    type Output = Either<A::Output, B::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Either<A::Output, B::Output>> {
+        // Safety: fields are never moved.
+        let me = unsafe { self.get_unchecked_mut() };
+        let a = unsafe { Pin::new_unchecked(&mut me.a) };
+        let b = unsafe { Pin::new_unchecked(&mut me.b) };
+
+        if let Poll::Ready(output) = a.poll(cx) {
+            return Poll::Ready(Either::Left(output));
+        }
+        if let Poll::Ready(output) = b.poll(cx) {
+            return Poll::Ready(Either::Right(output));
+        }
+        Poll::Pending
+    }
+}
+
+/// Runs two futures concurrently within one flow
+/// and resolves to output of the one that completes first.
+/// The other future is dropped.
+///
+/// First future is polled first, so it wins if both are ready.
+pub fn select<A, B>(a: A, b: B) -> Select<A::IntoFuture, B::IntoFuture>
+where
+    A: IntoFuture,
+    B: IntoFuture,
+{
+    Select {
+        a: a.into_future(),
+        b: b.into_future(),
+    }
+}
+
+/// Future that polls many futures with the same output concurrently
+/// and resolves to output of the one that completes first.
+///
+/// Created by [`race`].
+#[must_use = "Future does nothing unless polled"]
+pub struct Race<F> {
+    // Boxed slice is never reallocated, so elements stay pinned.
+    futures: Pin<Box<[F]>>,
+}
+
+impl<F> Future for Race<F>
+where
+    F: Future,
+{
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        // Safety: elements are never moved.
+        let futures = unsafe { self.futures.as_mut().get_unchecked_mut() };
+
+        for fut in futures.iter_mut() {
+            let fut = unsafe { Pin::new_unchecked(fut) };
+            if let Poll::Ready(output) = fut.poll(cx) {
+                return Poll::Ready(output);
+            }
+        }
+        Poll::Pending
+    }
+}
+
+/// Runs all futures concurrently within one flow
+/// and resolves to output of the one that completes first.
+/// Other futures are dropped.
+///
+/// Futures are polled in order, so earlier futures win if several are ready.
+/// Never resolves if there are no futures.
+pub fn race<I>(futures: I) -> Race<<I::Item as IntoFuture>::IntoFuture>
+where
+    I: IntoIterator,
+    I::Item: IntoFuture,
+{
+    let futures = futures.into_iter().map(IntoFuture::into_future).collect();
+    Race {
+        futures: Box::into_pin(futures),
+    }
+}
@@ -11,11 +11,14 @@ use crate::{
     component::Component,
     entity::{Entity, EntityBound, EntityId, EntityLoc, EntityRef, EntitySet, Location},
     query::{DefaultQuery, IntoQuery, Query, QueryItem},
+    wakers::WakeToken,
     world::{World, WorldLocal},
     EntityError, NoSuchEntity,
 };
 
-use super::{get_flow_world, Flow, FlowWorld, WakeOnDrop};
+use super::{
+    get_flow_world, watch::watch_entity, Flow, FlowHandle, FlowPriority, FlowWorld, WakeOnDrop,
+};
 
 /// Entity reference usable in flows.
 ///
@@ -129,9 +132,11 @@ impl<F> FutureEntityFlow<F> {
 
 impl<F> Flow for FutureEntityFlow<F>
 where
-    F: Future<Output = ()> + Send,
+    F: Future + Send,
 {
-    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<F::Output>> {
         // Safety: `me` never moves.
         let (fut, id) = self.pin_project();
 
@@ -141,7 +146,7 @@ where
 
             if !world.is_alive(id) {
                 // Terminate flow if entity is removed.
-                return Poll::Ready(());
+                return Poll::Ready(None);
             };
         }
 
@@ -153,7 +158,7 @@ where
         let mut e = match world.entity(id) {
             Err(NoSuchEntity) => {
                 // Terminate flow if entity is removed.
-                return Poll::Ready(());
+                return Poll::Ready(None);
             }
             Ok(e) => e,
         };
@@ -166,14 +171,14 @@ where
                 let auto_wake = e.with(WakeOnDrop::new);
                 auto_wake.add_waker(cx.waker());
             }
-            Poll::Ready(()) => {
+            Poll::Ready(_) => {
                 // If waker is registered, remove it for clean up.
                 if let Some(auto_wake) = e.get_mut::<&mut WakeOnDrop>() {
                     auto_wake.remove_waker(cx.waker());
                 }
             }
         }
-        poll
+        poll.map(Some)
     }
 }
 
@@ -203,12 +208,19 @@ pub trait IntoEntityFlow: 'static {
 
     /// Converts self into a flow.
     fn into_entity_flow(self, e: FlowEntity) -> Option<Self::Flow>;
+
+    /// Returns priority of the flow.
+    ///
+    /// See [`with_priority`](super::with_priority).
+    fn priority(&self) -> FlowPriority {
+        FlowPriority::Normal
+    }
 }
 
 impl<F, Fut> IntoEntityFlow for F
 where
     F: FnOnce(FlowEntity) -> Fut + 'static,
-    Fut: Future<Output = ()> + Send + 'static,
+    Fut: Future + Send + 'static,
 {
     type Flow = FutureEntityFlow<Fut>;
 
@@ -329,6 +341,7 @@ impl FlowEntity {
             entity: self.id,
             f,
             query: Q::default_query(),
+            token: WakeToken::default(),
             world: self.world(),
         }
     }
@@ -369,6 +382,7 @@ impl FlowEntity {
             entity: self.id,
             f,
             query: query.into_query(),
+            token: WakeToken::default(),
             world: self.world(),
         }
     }
@@ -777,6 +791,9 @@ impl FlowEntity {
     }
 
     /// Despawns the referenced entity.
+    ///
+    /// Entity protected from despawning by a relation is left alive.
+    /// Use [`FlowWorld::try_despawn`](crate::flow::FlowWorld::try_despawn) to detect this.
     #[inline]
     pub fn despawn(self) {
         match self.try_despawn() {
@@ -786,6 +803,8 @@ impl FlowEntity {
     }
 
     /// Despawns the referenced entity.
+    ///
+    /// Entity protected from despawning by a relation is left alive.
     #[inline]
     pub fn try_despawn(self) -> Result<(), NoSuchEntity> {
         // Safety: world reference does not escape this scope.
@@ -817,14 +836,17 @@ impl FlowEntity {
     }
 
     /// Spawns a new flow for the entity.
-    pub fn spawn_flow<F>(self, f: F)
+    ///
+    /// Returns [`FlowHandle`] to await flow's output or abort it.
+    #[track_caller]
+    pub fn spawn_flow<F>(self, f: F) -> FlowHandle<<F::Flow as Flow>::Output>
     where
         F: IntoEntityFlow,
     {
         // Safety: world reference does not escape this scope.
         let world = unsafe { get_flow_world() };
 
-        world.spawn_flow_for(self.id, f);
+        world.spawn_flow_for(self.id, f)
     }
 }
 
@@ -903,6 +925,7 @@ pub struct PollEntityView<Q, F> {
     entity: EntityId,
     query: Q,
     f: F,
+    token: WakeToken,
     world: FlowWorld,
 }
 
@@ -921,14 +944,14 @@ where
         let world = unsafe { me.world.get() };
 
         match world.get_with(me.entity, me.query) {
-            Err(EntityError::NoSuchEntity) => Poll::Pending,
-            Err(EntityError::Mismatch) => {
-                // TODO: Should archetype change be detected to wake up the task?
-                cx.waker().wake_by_ref();
-                Poll::Pending
-            }
-            Ok(item) => (me.f)(item, cx),
+            Err(EntityError::NoSuchEntity) => return Poll::Pending,
+            Err(EntityError::Mismatch) => {}
+            Ok(item) => return (me.f)(item, cx),
         }
+
+        // Wake up the task when entity's components change.
+        watch_entity(world, me.entity, me.query, &me.token, cx);
+        Poll::Pending
     }
 }
 
@@ -0,0 +1,105 @@
+use core::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{
+    event::{EventCursor, Events},
+    wakers::WakeToken,
+};
+
+use super::{watch::watch_resource, FlowWorld};
+
+/// Subscription to events of type `E`.
+///
+/// Created with [`FlowWorld::subscribe`].
+/// Receives events sent after subscription was created.
+pub struct EventSubscription<E> {
+    cursor: EventCursor,
+    token: WakeToken,
+    world: FlowWorld,
+    marker: PhantomData<fn() -> E>,
+}
+
+impl<E> EventSubscription<E>
+where
+    E: Clone + 'static,
+{
+    /// Returns a future that resolves to the next event.
+    ///
+    /// Flow is woken when [`Events<E>`] resource changes.
+    pub fn recv(&mut self) -> RecvEvent<'_, E> {
+        RecvEvent { subscription: self }
+    }
+}
+
+/// Future that resolves to the next event of [`EventSubscription`].
+#[must_use = "Future does nothing unless polled"]
+pub struct RecvEvent<'a, E> {
+    subscription: &'a mut EventSubscription<E>,
+}
+
+impl<E> Future for RecvEvent<'_, E>
+where
+    E: Clone + 'static,
+{
+    type Output = E;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<E> {
+        let subscription = &mut *self.get_mut().subscription;
+
+        // Safety: world reference does not escape this scope.
+        let world = unsafe { subscription.world.get() };
+
+        if let Some(events) = world.get_resource::<Events<E>>() {
+            if let Some(event) = events.read_one(&mut subscription.cursor) {
+                return Poll::Ready(event.clone());
+            }
+        }
+
+        watch_resource::<Events<E>>(world, &subscription.token, cx);
+        Poll::Pending
+    }
+}
+
+impl FlowWorld {
+    /// Sends event to [`Events<E>`] resource.
+    /// Inserts the resource if it is not present.
+    ///
+    /// Changes version of the resource to wake subscribers.
+    pub fn send_event<E>(self, event: E)
+    where
+        E: 'static,
+    {
+        // Safety: world reference does not escape this scope.
+        let world = unsafe { self.get() };
+
+        world.with_default_resource::<Events<E>>();
+        world.expect_resource_mut::<Events<E>>().send(event);
+    }
+
+    /// Subscribes to events of type `E`.
+    ///
+    /// Subscription receives events sent after this call.
+    pub fn subscribe<E>(self) -> EventSubscription<E>
+    where
+        E: 'static,
+    {
+        // Safety: world reference does not escape this scope.
+        let world = unsafe { self.get() };
+
+        let cursor = match world.get_resource::<Events<E>>() {
+            None => EventCursor::default(),
+            Some(events) => events.cursor(),
+        };
+
+        EventSubscription {
+            cursor,
+            token: WakeToken::default(),
+            world: self,
+            marker: PhantomData,
+        }
+    }
+}
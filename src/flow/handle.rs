@@ -0,0 +1,275 @@
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, Waker},
+};
+
+use alloc::sync::Arc;
+use amity::spin::Spin;
+
+/// Error returned when awaiting [`FlowHandle`] of a flow that did not complete.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum JoinError {
+    /// Flow was aborted with [`FlowHandle::abort`].
+    Aborted,
+
+    /// Flow was cancelled before completion.
+    ///
+    /// This happens when entity of an entity flow is despawned
+    /// or when [`Flows`](super::Flows) executor is dropped.
+    Cancelled,
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JoinError::Aborted => f.write_str("Flow was aborted"),
+            JoinError::Cancelled => f.write_str("Flow was cancelled before completion"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JoinError {}
+
+struct FlowState<T> {
+    output: Option<T>,
+    finished: bool,
+    taken: bool,
+
+    /// Waker of the task awaiting the handle.
+    join_waker: Option<Waker>,
+}
+
+type Shared<T> = Arc<Spin<FlowState<T>>>;
+
+/// Abort state of the flow.
+///
+/// Separate from the output to allow aborting flows without knowing output type.
+struct AbortState {
+    aborted: AtomicBool,
+    finished: AtomicBool,
+
+    /// Waker of the flow task itself.
+    task_waker: Spin<Option<Waker>>,
+}
+
+impl AbortState {
+    fn abort(&self) {
+        if self.finished.load(Ordering::Acquire) {
+            return;
+        }
+        self.aborted.store(true, Ordering::Release);
+
+        let waker = self.task_waker.lock().take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Handle to abort a spawned flow.
+///
+/// Unlike [`FlowHandle`] it does not depend on the flow's output type.
+/// Returned from [`FlowHandle::abort_handle`].
+#[derive(Clone)]
+pub struct AbortHandle {
+    state: Arc<AbortState>,
+}
+
+impl fmt::Debug for AbortHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AbortHandle")
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
+
+impl AbortHandle {
+    /// Returns `true` if flow is finished,
+    /// either completed, aborted or cancelled.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.state.finished.load(Ordering::Acquire)
+    }
+
+    /// Aborts the flow.
+    ///
+    /// See [`FlowHandle::abort`].
+    #[inline]
+    pub fn abort(&self) {
+        self.state.abort();
+    }
+}
+
+/// Handle to a spawned flow.
+///
+/// Returned from `spawn_flow` methods.
+/// Can be awaited from another flow to get the flow's output,
+/// checked with [`FlowHandle::try_join`] from systems
+/// and used to abort the flow with [`FlowHandle::abort`].
+///
+/// Dropping the handle detaches the flow, it keeps running.
+pub struct FlowHandle<T> {
+    shared: Shared<T>,
+    abort: Arc<AbortState>,
+}
+
+impl<T> fmt::Debug for FlowHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlowHandle")
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
+
+impl<T> FlowHandle<T> {
+    /// Returns `true` if flow is finished,
+    /// either completed, aborted or cancelled.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.shared.lock().finished
+    }
+
+    /// Aborts the flow.
+    ///
+    /// Flow's future is dropped by the [`Flows`](super::Flows) executor
+    /// next time it runs, without being polled again.
+    /// Does nothing if flow is already finished.
+    #[inline]
+    pub fn abort(&self) {
+        self.abort.abort();
+    }
+
+    /// Returns handle to abort the flow
+    /// that does not depend on the flow's output type.
+    #[inline]
+    pub fn abort_handle(&self) -> AbortHandle {
+        AbortHandle {
+            state: self.abort.clone(),
+        }
+    }
+
+    /// Returns result of the flow if it is finished.
+    /// Returns `None` if flow is still running.
+    ///
+    /// Useful to check the flow from systems.
+    ///
+    /// # Panics
+    ///
+    /// If result was already taken by this method or by awaiting the handle.
+    pub fn try_join(&mut self) -> Option<Result<T, JoinError>> {
+        let mut state = self.shared.lock();
+        if !state.finished {
+            return None;
+        }
+        Some(take_result(&mut state, &self.abort))
+    }
+}
+
+impl<T> Future for FlowHandle<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<T, JoinError>> {
+        let mut state = self.shared.lock();
+        if state.finished {
+            return Poll::Ready(take_result(&mut state, &self.abort));
+        }
+
+        match &state.join_waker {
+            Some(waker) if waker.will_wake(cx.waker()) => {}
+            _ => state.join_waker = Some(cx.waker().clone()),
+        }
+        Poll::Pending
+    }
+}
+
+fn take_result<T>(state: &mut FlowState<T>, abort: &AbortState) -> Result<T, JoinError> {
+    assert!(!state.taken, "Flow result is already taken");
+    state.taken = true;
+
+    match state.output.take() {
+        Some(output) => Ok(output),
+        None if abort.aborted.load(Ordering::Acquire) => Err(JoinError::Aborted),
+        None => Err(JoinError::Cancelled),
+    }
+}
+
+/// Completion side of the [`FlowHandle`].
+///
+/// Finishes the flow as cancelled when dropped without output.
+pub(super) struct Completion<T> {
+    shared: Shared<T>,
+    abort: Arc<AbortState>,
+}
+
+impl<T> Completion<T> {
+    /// Returns new pair of completion and handle.
+    pub fn new() -> (Self, FlowHandle<T>) {
+        let shared = Arc::new(Spin::new(FlowState {
+            output: None,
+            finished: false,
+            taken: false,
+            join_waker: None,
+        }));
+
+        let abort = Arc::new(AbortState {
+            aborted: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+            task_waker: Spin::new(None),
+        });
+
+        let completion = Completion {
+            shared: shared.clone(),
+            abort: abort.clone(),
+        };
+        (completion, FlowHandle { shared, abort })
+    }
+
+    /// Sets waker used to wake the flow task on abort.
+    pub fn set_task_waker(&self, waker: &Waker) {
+        *self.abort.task_waker.lock() = Some(waker.clone());
+
+        // Flow could be aborted before task is created.
+        if self.is_aborted() {
+            waker.wake_by_ref();
+        }
+    }
+
+    /// Returns `true` if flow was aborted.
+    pub fn is_aborted(&self) -> bool {
+        self.abort.aborted.load(Ordering::Acquire)
+    }
+
+    /// Finishes the flow with the output.
+    pub fn complete(self, output: T) {
+        self.finish(Some(output));
+    }
+
+    fn finish(&self, output: Option<T>) {
+        let waker = {
+            let mut state = self.shared.lock();
+            if state.finished {
+                return;
+            }
+            state.finished = true;
+            state.output = output;
+            state.join_waker.take()
+        };
+
+        self.abort.finished.store(true, Ordering::Release);
+        self.abort.task_waker.lock().take();
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Drop for Completion<T> {
+    fn drop(&mut self) {
+        self.finish(None);
+    }
+}
@@ -0,0 +1,118 @@
+use core::{fmt, panic::Location};
+
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+use crate::entity::EntityId;
+
+use super::FlowPriority;
+
+/// Bookkeeping of a spawned flow used for introspection.
+pub(super) struct TaskInfo {
+    pub type_name: &'static str,
+    pub entity: Option<EntityId>,
+    pub location: &'static Location<'static>,
+    pub priority: FlowPriority,
+    pub polls: u64,
+
+    #[cfg(feature = "std")]
+    pub last_wake: Instant,
+}
+
+impl TaskInfo {
+    pub fn new(
+        type_name: &'static str,
+        entity: Option<EntityId>,
+        location: &'static Location<'static>,
+        priority: FlowPriority,
+    ) -> Self {
+        TaskInfo {
+            type_name,
+            entity,
+            location,
+            priority,
+            polls: 0,
+
+            #[cfg(feature = "std")]
+            last_wake: Instant::now(),
+        }
+    }
+
+    pub fn snapshot(&self, #[cfg(feature = "std")] now: Instant) -> FlowInfo {
+        FlowInfo {
+            type_name: self.type_name,
+            entity: self.entity,
+            location: self.location,
+            priority: self.priority,
+            polls: self.polls,
+
+            #[cfg(feature = "std")]
+            since_wake: now.saturating_duration_since(self.last_wake),
+        }
+    }
+}
+
+/// Information about a live flow.
+///
+/// Returned by [`Flows::inspect`](super::Flows::inspect).
+#[derive(Clone, Debug)]
+pub struct FlowInfo {
+    type_name: &'static str,
+    entity: Option<EntityId>,
+    location: &'static Location<'static>,
+    priority: FlowPriority,
+    polls: u64,
+
+    #[cfg(feature = "std")]
+    since_wake: Duration,
+}
+
+impl FlowInfo {
+    /// Returns type name of the spawned flow value.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns entity the flow is bound to.
+    /// Returns `None` for flows not spawned for an entity.
+    pub fn entity(&self) -> Option<EntityId> {
+        self.entity
+    }
+
+    /// Returns location of the code that spawned the flow.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Returns priority of the flow.
+    pub fn priority(&self) -> FlowPriority {
+        self.priority
+    }
+
+    /// Returns how many times the flow was polled.
+    pub fn polls(&self) -> u64 {
+        self.polls
+    }
+
+    /// Returns time passed since the flow was last woken.
+    /// Flows that were never woken count from spawn.
+    #[cfg(feature = "std")]
+    pub fn since_wake(&self) -> Duration {
+        self.since_wake
+    }
+}
+
+impl fmt::Display for FlowInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{:?}]", self.type_name, self.priority)?;
+        if let Some(entity) = self.entity {
+            write!(f, " for entity {}", entity)?;
+        }
+        write!(f, " spawned at {}, polls: {}", self.location, self.polls)?;
+
+        #[cfg(feature = "std")]
+        write!(f, ", last wake: {:?} ago", self.since_wake)?;
+
+        Ok(())
+    }
+}
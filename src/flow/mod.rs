@@ -24,7 +24,8 @@
 // However user-defined auto-traits are far from stable.
 
 use core::{
-    any::TypeId,
+    any::{type_name, TypeId},
+    panic::Location,
     pin::Pin,
     sync::atomic::{AtomicBool, Ordering},
     task::{Context, Poll, Waker},
@@ -32,11 +33,15 @@ use core::{
 
 use alloc::{
     boxed::Box,
+    string::String,
     sync::{Arc, Weak},
     task::Wake,
     vec::Vec,
 };
 
+#[cfg(feature = "std")]
+use std::time::Instant;
+
 use amity::{flip_queue::FlipQueue, ring_buffer::RingBuffer};
 use hashbrown::HashMap;
 use slab::Slab;
@@ -48,21 +53,55 @@ use crate::{
     world::{World, WorldLocal},
 };
 
+mod bound;
+mod budget;
+mod closure;
+mod combinators;
 mod entity;
+mod event;
 mod futures;
+mod handle;
+mod info;
+#[cfg(feature = "threaded-scheduler")]
+mod offload;
+mod priority;
+mod resource;
+mod resumable;
+mod scope;
+mod timer;
 mod tls;
+mod watch;
 mod world;
 
-pub use self::{entity::*, futures::*, world::*};
+pub use edict_proc::flow_fn;
+
+pub use self::{
+    budget::*, closure::*, combinators::*, entity::*, event::*, futures::*, handle::*,
+    info::FlowInfo, priority::*, resource::*, resumable::*, scope::*, timer::*, watch::*, world::*,
+};
+
+#[cfg(feature = "threaded-scheduler")]
+pub use self::offload::{OffloadExecutor, OffloadWorld, OnWorld};
+
+use self::{
+    budget::BudgetTracker,
+    handle::Completion,
+    info::TaskInfo,
+    resumable::{RestoreFn, SaveFn},
+};
 
 /// Task that access world when polled.
 pub trait Flow {
+    /// Output of the flow.
+    type Output;
+
     /// Polls the flow.
+    /// Resolves to `None` if the flow is cancelled before completion.
     ///
     /// # Safety
     ///
     /// Must be called only from flow execution context.
-    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()>;
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Output>>;
 }
 
 /// Trait to construct flow instances.
@@ -70,6 +109,19 @@ trait MakeFlow: 'static {
     /// Flow type that will be polled.
     type Flow: Flow;
 
+    fn priority(&self) -> FlowPriority;
+
+    /// Returns entity the flow is bound to.
+    fn entity(&self) -> Option<EntityId>;
+
+    /// Returns type name of the user-provided flow value.
+    fn type_name(&self) -> &'static str;
+
+    /// Returns function to save flows of this type.
+    fn saver() -> Option<SaveFn<Self::Flow>> {
+        None
+    }
+
     fn make_flow(self) -> Option<Self::Flow>;
 }
 
@@ -92,13 +144,13 @@ trait AnyMakeFlows {
     /// Returns type of the MakeFlow.
     fn flow_id(&self) -> TypeId;
 
-    /// Drains the array into the queue.
-    fn drain(&mut self, flows: &mut HashMap<TypeId, AnyQueue>);
+    /// Drains the array into the queues.
+    fn drain(&mut self, queues: &mut [PriorityQueues; FlowPriority::COUNT]);
 }
 
 impl<'a> dyn AnyMakeFlows + 'a {
     #[inline]
-    unsafe fn downcast_mut<F: 'static>(&mut self) -> &mut TypedMakeFlows<F> {
+    unsafe fn downcast_mut<F: MakeFlow>(&mut self) -> &mut TypedMakeFlows<F> {
         debug_assert_eq!(self.flow_id(), type_id::<F>());
 
         unsafe { &mut *(self as *mut Self as *mut TypedMakeFlows<F>) }
@@ -106,10 +158,11 @@ impl<'a> dyn AnyMakeFlows + 'a {
 }
 
 type FlowMake<F> = <F as MakeFlow>::Flow;
+type FlowOutput<F> = <FlowMake<F> as Flow>::Output;
 
 /// Typed array of newly inserted flows of a single type.
-struct TypedMakeFlows<F> {
-    array: Vec<F>,
+struct TypedMakeFlows<F: MakeFlow> {
+    array: Vec<(F, Completion<FlowOutput<F>>, TaskInfo)>,
 }
 
 impl<F> AnyMakeFlows for TypedMakeFlows<F>
@@ -120,38 +173,31 @@ where
         type_id::<F>()
     }
 
-    fn drain(&mut self, flows: &mut HashMap<TypeId, AnyQueue>) {
-        // Short-circuit if there are no new flows.
-        if self.array.is_empty() {
-            return;
-        }
-
-        let flow_id = type_id::<FlowMake<F>>();
-
-        // Find queue for this type of flows or create new one.
-        let queue = flows
-            .entry(flow_id)
-            .or_insert_with(AnyQueue::new::<FlowMake<F>>);
+    fn drain(&mut self, queues: &mut [PriorityQueues; FlowPriority::COUNT]) {
+        for (make_flow, completion, info) in self.array.drain(..) {
+            // Find queue for this type of flows and priority or create new one.
+            let queue = queues[info.priority.index()].queue::<F>();
 
-        // Safety: TypedFlows<F> is at index `type_id::<F>()` in `flows.map`.
-        let typed_flows = unsafe { queue.flows.downcast_mut::<FlowMake<F>>() };
+            // Safety: Queue for `FlowMake<F>` always holds `TypedFlows<FlowMake<F>>`.
+            let typed_flows = unsafe { queue.flows.downcast_mut::<FlowMake<F>>() };
 
-        // Reserve space to ensure oom can't happen in the loop below.
-        typed_flows.array.reserve(self.array.len());
-
-        for make_flow in self.array.drain(..) {
             if let Some(flow) = make_flow.make_flow() {
                 let task_id = typed_flows.array.vacant_key();
 
                 let needs_wake = Arc::new(AtomicBool::new(false));
+                let waker = Waker::from(Arc::new(FlowWaker {
+                    task_id,
+                    flip: Arc::downgrade(&queue.flip),
+                    needs_wake: needs_wake.clone(),
+                }));
+                completion.set_task_waker(&waker);
+
                 let task = FlowTask {
                     flow: Box::pin(flow),
-                    needs_wake: needs_wake.clone(),
-                    waker: Waker::from(Arc::new(FlowWaker {
-                        task_id,
-                        flip: Arc::downgrade(&queue.flip),
-                        needs_wake,
-                    })),
+                    needs_wake,
+                    waker,
+                    completion,
+                    info,
                 };
 
                 typed_flows.array.insert(task);
@@ -184,12 +230,22 @@ impl NewFlows {
         unsafe { new_flows.downcast_mut::<F>() }
     }
 
-    fn add<F>(&mut self, flow: F)
+    #[track_caller]
+    fn add<F>(&mut self, flow: F) -> FlowHandle<FlowOutput<F>>
     where
         F: MakeFlow,
     {
+        let info = TaskInfo::new(
+            flow.type_name(),
+            flow.entity(),
+            Location::caller(),
+            flow.priority(),
+        );
+
+        let (completion, handle) = Completion::new();
         let typed_new_flows = self.typed_new_flows();
-        typed_new_flows.array.push(flow);
+        typed_new_flows.array.push((flow, completion, info));
+        handle
     }
 }
 
@@ -198,12 +254,24 @@ trait AnyFlows {
     #[cfg(debug_assertions)]
     fn flow_id(&self) -> TypeId;
 
-    unsafe fn execute(&mut self, front: &[usize], back: &[usize]);
+    /// Polls ready flows until budget is exhausted.
+    /// Flows that were not polled are left in `ready`.
+    unsafe fn execute(&mut self, ready: &mut RingBuffer<usize>, budget: &mut BudgetTracker);
+
+    /// Records wake time of woken flows.
+    #[cfg(feature = "std")]
+    fn mark_woken(&mut self, ids: &[usize], now: Instant);
+
+    /// Collects information about live flows.
+    fn inspect(&self, #[cfg(feature = "std")] now: Instant, out: &mut Vec<FlowInfo>);
+
+    /// Saves state of live resumable flows.
+    fn save(&self, out: &mut Vec<SavedFlow>);
 }
 
 impl dyn AnyFlows {
     #[inline]
-    unsafe fn downcast_mut<F: 'static>(&mut self) -> &mut TypedFlows<F> {
+    unsafe fn downcast_mut<F: Flow + 'static>(&mut self) -> &mut TypedFlows<F> {
         #[cfg(debug_assertions)]
         assert_eq!(self.flow_id(), type_id::<F>());
 
@@ -236,15 +304,20 @@ impl Wake for FlowWaker {
     }
 }
 
-struct FlowTask<F> {
+struct FlowTask<F: Flow> {
     flow: Pin<Box<F>>,
     needs_wake: Arc<AtomicBool>,
     waker: Waker,
+    completion: Completion<F::Output>,
+    info: TaskInfo,
 }
 
 /// Container of spawned flows of specific type.
-struct TypedFlows<F> {
+struct TypedFlows<F: Flow> {
     array: Slab<FlowTask<F>>,
+
+    /// Saves flows of resumable type.
+    save: Option<SaveFn<F>>,
 }
 
 impl<F> TypedFlows<F>
@@ -252,12 +325,22 @@ where
     F: Flow + 'static,
 {
     #[inline]
-    unsafe fn execute(&mut self, ids: &[usize]) {
-        for &id in ids {
+    unsafe fn execute(&mut self, ready: &mut RingBuffer<usize>, budget: &mut BudgetTracker) {
+        while !budget.exhausted() {
+            let Some(id) = ready.pop() else {
+                break;
+            };
+
             let Some(task) = self.array.get_mut(id) else {
                 continue;
             };
 
+            if task.completion.is_aborted() {
+                // Drop aborted flow without polling.
+                self.array.remove(id);
+                continue;
+            }
+
             let mut cx = Context::from_waker(&task.waker);
 
             // Safety: This is the only code that can access `task.flow`.
@@ -268,13 +351,18 @@ where
             // Set this flag directly before polling the flow,
             // making any wake calls to re-enqueue the task.
             task.needs_wake.store(true, Ordering::Release);
+            task.info.polls += 1;
 
             // This is the only safe place to poll the flow.
             let poll = unsafe { F::poll(pinned, &mut cx) };
+            budget.spend();
 
-            if let Poll::Ready(()) = poll {
+            if let Poll::Ready(output) = poll {
                 // Task is finished, remove it from the array.
-                self.array.remove(id);
+                let task = self.array.remove(id);
+                if let Some(output) = output {
+                    task.completion.complete(output);
+                }
             }
         }
     }
@@ -289,12 +377,39 @@ where
         type_id::<F>()
     }
 
-    unsafe fn execute(&mut self, front: &[usize], back: &[usize]) {
+    unsafe fn execute(&mut self, ready: &mut RingBuffer<usize>, budget: &mut BudgetTracker) {
         unsafe {
-            self.execute(front);
+            self.execute(ready, budget);
         }
-        unsafe {
-            self.execute(back);
+    }
+
+    #[cfg(feature = "std")]
+    fn mark_woken(&mut self, ids: &[usize], now: Instant) {
+        for &id in ids {
+            if let Some(task) = self.array.get_mut(id) {
+                task.info.last_wake = now;
+            }
+        }
+    }
+
+    fn inspect(&self, #[cfg(feature = "std")] now: Instant, out: &mut Vec<FlowInfo>) {
+        out.extend(self.array.iter().map(|(_, task)| {
+            task.info.snapshot(
+                #[cfg(feature = "std")]
+                now,
+            )
+        }));
+    }
+
+    fn save(&self, out: &mut Vec<SavedFlow>) {
+        let Some(save) = self.save else {
+            return;
+        };
+
+        for (_, task) in self.array.iter() {
+            if !task.completion.is_aborted() {
+                out.push(save(&task.flow));
+            }
         }
     }
 }
@@ -307,14 +422,100 @@ struct AnyQueue {
 }
 
 impl AnyQueue {
-    fn new<F>() -> Self
+    fn new<F>(save: Option<SaveFn<F>>) -> Self
     where
         F: Flow + 'static,
     {
         AnyQueue {
             flip: Arc::new(FlipQueue::new()),
             ready: RingBuffer::new(),
-            flows: Box::new(TypedFlows::<F> { array: Slab::new() }),
+            flows: Box::new(TypedFlows::<F> {
+                array: Slab::new(),
+                save,
+            }),
+        }
+    }
+
+    /// Moves woken flows to the ready buffer.
+    /// Keeps flows carried over from previous execution in front.
+    fn collect_woken(&mut self) {
+        if self.ready.is_empty() {
+            self.flip.swap_buffer(&mut self.ready);
+
+            #[cfg(feature = "std")]
+            mark_woken(&mut *self.flows, &self.ready);
+        } else {
+            let mut woken = RingBuffer::new();
+            self.flip.swap_buffer(&mut woken);
+
+            #[cfg(feature = "std")]
+            mark_woken(&mut *self.flows, &woken);
+
+            for id in woken.drain() {
+                self.ready.push(id);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn mark_woken(flows: &mut dyn AnyFlows, woken: &RingBuffer<usize>) {
+    if woken.is_empty() {
+        return;
+    }
+
+    let now = Instant::now();
+    let (front, back) = woken.as_slices();
+    flows.mark_woken(front, now);
+    flows.mark_woken(back, now);
+}
+
+/// Queues of flows of a single priority class.
+#[derive(Default)]
+struct PriorityQueues {
+    index: HashMap<TypeId, usize>,
+    queues: Vec<AnyQueue>,
+
+    /// Index of the queue to execute first.
+    /// Rotates between executions to share budget between flow types.
+    next: usize,
+}
+
+impl PriorityQueues {
+    fn queue<F>(&mut self) -> &mut AnyQueue
+    where
+        F: MakeFlow,
+    {
+        let idx = *self
+            .index
+            .entry(type_id::<FlowMake<F>>())
+            .or_insert_with(|| {
+                self.queues.push(AnyQueue::new::<FlowMake<F>>(F::saver()));
+                self.queues.len() - 1
+            });
+        &mut self.queues[idx]
+    }
+
+    /// Executes ready flows until budget is exhausted.
+    fn execute(&mut self, budget: &mut BudgetTracker) {
+        let count = self.queues.len();
+        for i in 0..count {
+            let idx = (self.next + i) % count;
+            let queue = &mut self.queues[idx];
+
+            unsafe {
+                queue.flows.execute(&mut queue.ready, budget);
+            }
+
+            if budget.exhausted() {
+                // Continue from this queue next time.
+                self.next = idx;
+                return;
+            }
+        }
+
+        if count > 0 {
+            self.next = (self.next + 1) % count;
         }
     }
 }
@@ -323,7 +524,9 @@ impl AnyQueue {
 /// collects spawned flows and executes them.
 pub struct Flows {
     new_flows: NewFlows,
-    map: HashMap<TypeId, AnyQueue>,
+    queues: [PriorityQueues; FlowPriority::COUNT],
+    budget: FlowBudget,
+    resumable: HashMap<&'static str, (TypeId, RestoreFn)>,
 }
 
 impl Default for Flows {
@@ -340,53 +543,129 @@ impl Flows {
     pub fn new() -> Self {
         Flows {
             new_flows: NewFlows::new(),
-            map: HashMap::new(),
+            queues: Default::default(),
+            budget: FlowBudget::unlimited(),
+            resumable: HashMap::new(),
         }
     }
 
-    fn collect_new_flows<'a>(&mut self, world: &'a mut World) -> Option<tls::WorldGuard<'a>> {
+    /// Returns budget used by [`Flows::execute`].
+    pub fn budget(&self) -> FlowBudget {
+        self.budget
+    }
+
+    /// Sets budget used by [`Flows::execute`].
+    pub fn set_budget(&mut self, budget: FlowBudget) {
+        self.budget = budget;
+    }
+
+    fn collect_new_flows<'a>(&mut self, world: &'a mut World) -> tls::WorldGuard<'a> {
         let world = world.local();
 
         core::mem::swap(&mut self.new_flows, world.new_flows.get_mut());
 
         let guard = tls::WorldGuard::new(world);
 
-        // First swap all queues with ready buffer.
-        for typed in self.map.values_mut() {
-            debug_assert!(typed.ready.is_empty());
-            typed.flip.swap_buffer(&mut typed.ready);
+        // First move woken flows into ready buffers.
+        for queues in &mut self.queues {
+            for queue in &mut queues.queues {
+                queue.collect_woken();
+            }
         }
 
         // Then drain all new flows into queues.
         // New flow ids are added to ready buffer.
         for (_, typed) in &mut self.new_flows.map {
-            typed.drain(&mut self.map);
+            typed.drain(&mut self.queues);
         }
 
-        Some(guard)
+        guard
     }
 
-    /// Executes all ready flows in the world.
+    /// Executes ready flows in the world within the budget
+    /// set with [`Flows::set_budget`].
+    /// Without budget all ready flows are executed.
     ///
     /// Flows spawned in the world are drained into this instance,
     /// so this function should be called with the same world instance.
     pub fn execute(&mut self, world: &mut World) {
+        self.execute_with_budget(world, self.budget);
+    }
+
+    /// Executes ready flows in the world within specified budget.
+    ///
+    /// Flows with higher [`FlowPriority`] are polled first.
+    /// Ready flows that were not polled are carried over to the next call.
+    pub fn execute_with_budget(&mut self, world: &mut World, budget: FlowBudget) {
         world.maintenance();
 
-        let Some(_guard) = self.collect_new_flows(world) else {
-            return;
-        };
+        let _guard = self.collect_new_flows(world);
 
-        // Execute all ready flows.
-        for typed in self.map.values_mut() {
-            let (front, back) = typed.ready.as_slices();
-            unsafe {
-                typed.flows.execute(front, back);
+        // Execute ready flows, higher priorities first.
+        let mut budget = budget.start();
+        for queues in &mut self.queues {
+            queues.execute(&mut budget);
+            if budget.exhausted() {
+                break;
             }
+        }
+    }
 
-            // Clear ready buffer.
-            typed.ready.clear();
+    /// Returns information about all live flows in this executor.
+    ///
+    /// Flows spawned after last [`Flows::execute`] call are not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use core::time::Duration;
+    /// # use edict::{flow::{FlowTime, FlowWorld, Flows}, world::World};
+    /// let mut world = World::new();
+    /// let mut flows = Flows::new();
+    /// world.insert_resource(FlowTime::with_step(Duration::from_millis(16)));
+    ///
+    /// world.spawn_flow(|world: FlowWorld| async move {
+    ///     world.sleep_ticks(1).await;
+    /// });
+    /// flows.execute(&mut world);
+    ///
+    /// let info = flows.inspect();
+    /// assert_eq!(info.len(), 1);
+    /// assert_eq!(info[0].polls(), 1);
+    /// assert_eq!(info[0].entity(), None);
+    /// ```
+    pub fn inspect(&self) -> Vec<FlowInfo> {
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+
+        let mut out = Vec::new();
+        for queues in &self.queues {
+            for queue in &queues.queues {
+                queue.flows.inspect(
+                    #[cfg(feature = "std")]
+                    now,
+                    &mut out,
+                );
+            }
         }
+        out
+    }
+
+    /// Returns human-readable dump of all live flows,
+    /// one flow per line.
+    ///
+    /// Suitable for debug console commands.
+    pub fn dump(&self) -> String {
+        use core::fmt::Write;
+
+        let flows = self.inspect();
+
+        let mut dump = String::new();
+        let _ = writeln!(dump, "{} live flows", flows.len());
+        for flow in &flows {
+            let _ = writeln!(dump, "  {}", flow);
+        }
+        dump
     }
 
     /// Enter flow context and execute the closure with the [`FlowWorld`] instance.
@@ -421,6 +700,18 @@ where
 {
     type Flow = F::Flow;
 
+    fn priority(&self) -> FlowPriority {
+        self.f.priority()
+    }
+
+    fn entity(&self) -> Option<EntityId> {
+        Some(self.entity)
+    }
+
+    fn type_name(&self) -> &'static str {
+        type_name::<F>()
+    }
+
     fn make_flow(self) -> Option<F::Flow> {
         let e = FlowEntity::new(self.entity);
 
@@ -442,91 +733,139 @@ where
 {
     type Flow = F::Flow;
 
+    fn priority(&self) -> FlowPriority {
+        self.f.priority()
+    }
+
+    fn entity(&self) -> Option<EntityId> {
+        None
+    }
+
+    fn type_name(&self) -> &'static str {
+        type_name::<F>()
+    }
+
     fn make_flow(self) -> Option<F::Flow> {
         self.f.into_flow(FlowWorld::new())
     }
 }
 
+type IntoFlowOutput<F> = <<F as IntoFlow>::Flow as Flow>::Output;
+type IntoEntityFlowOutput<F> = <<F as IntoEntityFlow>::Flow as Flow>::Output;
+
 impl World {
     /// Spawns a flow in the world.
     /// It will be polled during [`Flows::execute`] until completion.
-    pub fn spawn_flow<F>(&mut self, flow: F)
+    ///
+    /// Returns [`FlowHandle`] to await flow's output or abort it.
+    #[track_caller]
+    pub fn spawn_flow<F>(&mut self, flow: F) -> FlowHandle<IntoFlowOutput<F>>
     where
         F: IntoFlow,
     {
-        self.new_flows.get_mut().add(WorldIntoFlow { f: flow });
+        self.new_flows.get_mut().add(WorldIntoFlow { f: flow })
     }
 
     /// Spawns a flow for an entity in the world.
     /// It will be polled during [`Flows::execute`] until completion
     /// or until the entity is despawned.
-    pub fn spawn_flow_for<F>(&mut self, entity: EntityId, flow: F)
+    ///
+    /// Returns [`FlowHandle`] to await flow's output or abort it.
+    #[track_caller]
+    pub fn spawn_flow_for<F>(
+        &mut self,
+        entity: EntityId,
+        flow: F,
+    ) -> FlowHandle<IntoEntityFlowOutput<F>>
     where
         F: IntoEntityFlow,
     {
         self.new_flows
             .get_mut()
-            .add(EntityIntoFlow { entity, f: flow });
+            .add(EntityIntoFlow { entity, f: flow })
     }
 }
 
 impl WorldLocal {
     /// Spawn a flow in the world.
     /// It will be polled during [`Flows::execute`] until completion.
-    pub fn spawn_flow<F>(&self, flow: F)
+    ///
+    /// Returns [`FlowHandle`] to await flow's output or abort it.
+    #[track_caller]
+    pub fn spawn_flow<F>(&self, flow: F) -> FlowHandle<IntoFlowOutput<F>>
     where
         F: IntoFlow,
     {
         // Safety: accessed only from "main" thread.
-        unsafe { &mut *self.new_flows.get() }.add(WorldIntoFlow { f: flow });
+        unsafe { &mut *self.new_flows.get() }.add(WorldIntoFlow { f: flow })
     }
 
     /// Spawns a flow for an entity in the world.
     /// It will be polled during [`Flows::execute`] until completion
     /// or until the entity is despawned.
-    pub fn spawn_flow_for<F>(&self, entity: EntityId, flow: F)
+    ///
+    /// Returns [`FlowHandle`] to await flow's output or abort it.
+    #[track_caller]
+    pub fn spawn_flow_for<F>(
+        &self,
+        entity: EntityId,
+        flow: F,
+    ) -> FlowHandle<IntoEntityFlowOutput<F>>
     where
         F: IntoEntityFlow,
     {
         // Safety: accessed only from "main" thread.
-        unsafe { &mut *self.new_flows.get() }.add(EntityIntoFlow { entity, f: flow });
+        unsafe { &mut *self.new_flows.get() }.add(EntityIntoFlow { entity, f: flow })
     }
 }
 
 impl FlowWorld {
     /// Spawn a flow in the world.
     /// It will be polled during [`Flows::execute`] until completion.
-    pub fn spawn_flow<F>(self, flow: F)
+    ///
+    /// Returns [`FlowHandle`] to await flow's output or abort it.
+    #[track_caller]
+    pub fn spawn_flow<F>(self, flow: F) -> FlowHandle<IntoFlowOutput<F>>
     where
         F: IntoFlow,
     {
         // Safety: world reference does not escape this scope.
         let world = unsafe { self.get() };
 
-        world.spawn_flow(flow);
+        world.spawn_flow(flow)
     }
 
     /// Spawns a flow for an entity in the world.
     /// It will be polled during [`Flows::execute`] until completion
     /// or until the entity is despawned.
-    pub fn spawn_flow_for<F>(&self, entity: EntityId, flow: F)
+    ///
+    /// Returns [`FlowHandle`] to await flow's output or abort it.
+    #[track_caller]
+    pub fn spawn_flow_for<F>(
+        &self,
+        entity: EntityId,
+        flow: F,
+    ) -> FlowHandle<IntoEntityFlowOutput<F>>
     where
         F: IntoEntityFlow,
     {
         // Safety: world reference does not escape this scope.
         let world = unsafe { self.get() };
 
-        world.spawn_flow_for(entity, flow);
+        world.spawn_flow_for(entity, flow)
     }
 }
 
 impl EntityRef<'_> {
     /// Spawns a new flow for the entity.
-    pub fn spawn_flow<F>(&mut self, f: F)
+    ///
+    /// Returns [`FlowHandle`] to await flow's output or abort it.
+    #[track_caller]
+    pub fn spawn_flow<F>(&mut self, f: F) -> FlowHandle<IntoEntityFlowOutput<F>>
     where
         F: crate::flow::IntoEntityFlow,
     {
         let id = self.id();
-        self.world().spawn_flow_for(id, f);
+        self.world().spawn_flow_for(id, f)
     }
 }
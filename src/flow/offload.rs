@@ -0,0 +1,401 @@
+use core::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, Waker},
+};
+
+use alloc::{
+    boxed::Box,
+    sync::{Arc, Weak},
+    task::Wake,
+};
+
+use amity::{flip_queue::FlipQueue, ring_buffer::RingBuffer, spin::Spin};
+
+use crate::world::World;
+
+use super::{handle::Completion, tls, FlowHandle, FlowWorld, Flows};
+
+/// Closure that runs on the world's thread on behalf of offloaded future.
+type Hop = Box<dyn FnOnce(FlowWorld) + Send>;
+
+/// Queues shared between the executor and offloaded futures.
+struct Shared {
+    /// Offloaded tasks to poll on next execution.
+    ready: FlipQueue<Arc<OffloadTask>>,
+
+    /// Closures waiting to be run with world access.
+    hops: FlipQueue<Hop>,
+
+    /// Waker of the thread that executes flows.
+    waker: Spin<Option<Waker>>,
+}
+
+impl Shared {
+    /// Wakes the thread that executes flows
+    /// when offloaded task is ready or requests world access.
+    fn wake_world(&self) {
+        if let Some(waker) = &*self.waker.lock() {
+            waker.wake_by_ref();
+        }
+    }
+
+    /// Runs all requested hops.
+    ///
+    /// Must be called with world bound to the flow context.
+    fn run_hops(&self, buffer: &mut RingBuffer<Hop>) {
+        self.hops.swap_buffer(buffer);
+        for hop in buffer.drain() {
+            hop(FlowWorld::new());
+        }
+    }
+}
+
+/// Resource that holds offloaded futures of the world.
+pub(super) struct Offloaded {
+    shared: Arc<Shared>,
+}
+
+impl Default for Offloaded {
+    fn default() -> Self {
+        Offloaded {
+            shared: Arc::new(Shared {
+                ready: FlipQueue::new(),
+                hops: FlipQueue::new(),
+                waker: Spin::new(None),
+            }),
+        }
+    }
+}
+
+/// Offloaded future polled on worker threads.
+struct OffloadTask {
+    future: Spin<Option<Pin<Box<dyn Future<Output = ()> + Send>>>>,
+    needs_wake: AtomicBool,
+
+    /// Set when task is woken while being polled on another thread.
+    repoll: AtomicBool,
+
+    shared: Weak<Shared>,
+}
+
+impl Wake for OffloadTask {
+    #[inline]
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    #[inline]
+    fn wake_by_ref(self: &Arc<Self>) {
+        let needs_wake = self.needs_wake.fetch_and(false, Ordering::Acquire);
+        if !needs_wake {
+            return;
+        }
+        let Some(shared) = self.shared.upgrade() else {
+            return;
+        };
+        shared.ready.push_sync(self.clone());
+        shared.wake_world();
+    }
+}
+
+impl OffloadTask {
+    /// Polls the future.
+    ///
+    /// If the future is being polled on another thread,
+    /// that thread polls it again instead of blocking this one.
+    fn poll(self: Arc<Self>) {
+        self.repoll.store(true, Ordering::SeqCst);
+
+        loop {
+            let Some(mut future) = self.future.try_lock() else {
+                // Thread that holds the lock checks `repoll` after releasing it.
+                return;
+            };
+            self.repoll.store(false, Ordering::SeqCst);
+
+            let Some(pinned) = future.as_mut() else {
+                return;
+            };
+
+            let waker = Waker::from(self.clone());
+            let mut cx = Context::from_waker(&waker);
+
+            // Set this flag directly before polling the future,
+            // making any wake calls to re-enqueue the task.
+            self.needs_wake.store(true, Ordering::Release);
+
+            if pinned.as_mut().poll(&mut cx).is_ready() {
+                *future = None;
+                return;
+            }
+
+            drop(future);
+
+            if !self.repoll.load(Ordering::SeqCst) {
+                return;
+            }
+        }
+    }
+}
+
+/// Executor that polls offloaded futures.
+///
+/// Unlike [`ScopedExecutor`](crate::scheduler::ScopedExecutor)
+/// tasks may run after the call that spawned them returns.
+///
+/// Implemented for closures that accept boxed tasks,
+/// e.g. `|task| rayon::spawn(task)`.
+pub trait OffloadExecutor {
+    /// Spawns a task.
+    fn spawn(&self, task: Box<dyn FnOnce() + Send>);
+}
+
+impl<F> OffloadExecutor for F
+where
+    F: Fn(Box<dyn FnOnce() + Send>),
+{
+    #[inline]
+    fn spawn(&self, task: Box<dyn FnOnce() + Send>) {
+        self(task)
+    }
+}
+
+/// Wraps offloaded future to deliver its output to the [`FlowHandle`].
+struct OffloadFuture<Fut: Future> {
+    future: Pin<Box<Fut>>,
+    completion: Option<Completion<Fut::Output>>,
+}
+
+impl<Fut> Future for OffloadFuture<Fut>
+where
+    Fut: Future,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let me = self.get_mut();
+
+        let Some(completion) = &me.completion else {
+            return Poll::Ready(());
+        };
+
+        if completion.is_aborted() {
+            // Drop aborted future without polling.
+            me.completion = None;
+            return Poll::Ready(());
+        }
+
+        match me.future.as_mut().poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(output) => {
+                if let Some(completion) = me.completion.take() {
+                    completion.complete(output);
+                }
+                Poll::Ready(())
+            }
+        }
+    }
+}
+
+/// Handle to the world available to offloaded futures.
+///
+/// Offloaded futures run on worker threads and can't access the world directly.
+/// Use [`OffloadWorld::on_world`] to run code with world access.
+///
+/// Created by [`FlowWorld::offload`].
+#[derive(Clone)]
+pub struct OffloadWorld {
+    shared: Weak<Shared>,
+}
+
+impl OffloadWorld {
+    /// Runs the closure with world access on the thread that executes flows.
+    /// Returns a future that resolves to closure result.
+    ///
+    /// The closure runs during next [`Flows::execute_with`] call.
+    /// If the executor's world is dropped before that, the future never resolves.
+    pub fn on_world<F, R>(&self, f: F) -> OnWorld<R>
+    where
+        F: FnOnce(FlowWorld) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (completion, handle) = Completion::new();
+        if let Some(shared) = self.shared.upgrade() {
+            shared
+                .hops
+                .push_sync(Box::new(move |world| completion.complete(f(world))));
+            shared.wake_world();
+        }
+        OnWorld {
+            handle: Some(handle),
+        }
+    }
+}
+
+/// Future returned by [`OffloadWorld::on_world`].
+#[must_use = "Future does nothing unless polled"]
+pub struct OnWorld<R> {
+    handle: Option<FlowHandle<R>>,
+}
+
+impl<R> Future for OnWorld<R> {
+    type Output = R;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<R> {
+        let me = self.get_mut();
+        let Some(handle) = &mut me.handle else {
+            return Poll::Pending;
+        };
+
+        match Pin::new(handle).poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Ok(output)) => {
+                me.handle = None;
+                Poll::Ready(output)
+            }
+            Poll::Ready(Err(_)) => {
+                // Closure was dropped with the world.
+                me.handle = None;
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl FlowWorld {
+    /// Offloads a future to worker threads.
+    ///
+    /// The closure is called immediately with [`OffloadWorld`] handle
+    /// and returned future is polled on threads of the executor
+    /// passed to [`Flows::execute_with`].
+    /// Useful for CPU-heavy work that should not stall other flows.
+    ///
+    /// The future can't access the world directly.
+    /// It may hop back to the world with [`OffloadWorld::on_world`].
+    ///
+    /// Returns [`FlowHandle`] to await future's output or abort it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{flow::{FlowWorld, Flows}, world::World};
+    /// let mut world = World::new();
+    /// let mut flows = Flows::new();
+    ///
+    /// let mut handle = world.spawn_flow(|world: FlowWorld| async move {
+    ///     let sum = world
+    ///         .offload(|world| async move {
+    ///             let sum = (1..=100u32).sum::<u32>();
+    ///             world.on_world(move |world| world.insert_resource(sum)).await;
+    ///             sum
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     assert_eq!(world.copy_resource::<u32>(), sum);
+    ///     sum
+    /// });
+    ///
+    /// while !handle.is_finished() {
+    ///     flows.execute_threaded(&mut world);
+    ///     std::thread::yield_now();
+    /// }
+    /// assert_eq!(handle.try_join(), Some(Ok(5050)));
+    /// ```
+    pub fn offload<F, Fut>(self, f: F) -> FlowHandle<Fut::Output>
+    where
+        F: FnOnce(OffloadWorld) -> Fut,
+        Fut: Future + Send + 'static,
+        Fut::Output: Send + 'static,
+    {
+        let shared = {
+            // Safety: world reference does not escape this scope.
+            let world = unsafe { self.get() };
+            world.with_default_resource::<Offloaded>().shared.clone()
+        };
+
+        let future = f(OffloadWorld {
+            shared: Arc::downgrade(&shared),
+        });
+
+        let task = Arc::new(OffloadTask {
+            future: Spin::new(None),
+            needs_wake: AtomicBool::new(false),
+            repoll: AtomicBool::new(false),
+            shared: Arc::downgrade(&shared),
+        });
+
+        let (completion, handle) = Completion::new();
+        completion.set_task_waker(&Waker::from(task.clone()));
+
+        *task.future.lock() = Some(Box::pin(OffloadFuture {
+            future: Box::pin(future),
+            completion: Some(completion),
+        }));
+
+        shared.ready.push_sync(task);
+        handle
+    }
+}
+
+impl Flows {
+    /// Executes ready flows like [`Flows::execute`]
+    /// and spawns polls of woken offloaded futures on provided executor.
+    ///
+    /// This method does not wait for offloaded futures.
+    /// Polls spawned by the call may run after it returns.
+    /// Closures passed to [`OffloadWorld::on_world`] are run on this thread
+    /// during the next call after they were requested,
+    /// and offloaded futures woken after the polls are spawned
+    /// are polled during the next call too.
+    ///
+    /// Use [`Flows::set_offload_waker`] to learn when the next call has work to do.
+    pub fn execute_with(&mut self, world: &mut World, executor: &impl OffloadExecutor) {
+        let shared = world.with_default_resource::<Offloaded>().shared.clone();
+
+        let mut ready = RingBuffer::new();
+        shared.ready.swap_buffer(&mut ready);
+
+        for task in ready.drain() {
+            executor.spawn(Box::new(move || task.poll()));
+        }
+
+        self.execute(world);
+
+        let guard = tls::WorldGuard::new(world.local());
+        shared.run_hops(&mut RingBuffer::new());
+        drop(guard);
+    }
+
+    /// Sets waker that is woken when offloaded future of the world
+    /// becomes ready to be polled or requests world access.
+    ///
+    /// Event loops may use it to call [`Flows::execute_with`]
+    /// when there is work to do instead of polling.
+    pub fn set_offload_waker(&mut self, world: &mut World, waker: Waker) {
+        let shared = &world.with_default_resource::<Offloaded>().shared;
+        *shared.waker.lock() = Some(waker);
+    }
+
+    /// Executes flows and polls offloaded futures on new std threads.
+    ///
+    /// Spawns a thread for each poll.
+    /// Prefer [`Flows::execute_with`] with a thread pool for frequent polls.
+    ///
+    /// See [`Flows::execute_with`].
+    pub fn execute_threaded(&mut self, world: &mut World) {
+        self.execute_with(world, &|task: Box<dyn FnOnce() + Send>| {
+            std::thread::spawn(task);
+        });
+    }
+
+    /// Executes flows and polls offloaded futures using rayon's global thread pool.
+    ///
+    /// See [`Flows::execute_with`].
+    #[cfg(feature = "rayon-scheduler")]
+    pub fn execute_rayon(&mut self, world: &mut World) {
+        self.execute_with(world, &|task: Box<dyn FnOnce() + Send>| rayon::spawn(task));
+    }
+}
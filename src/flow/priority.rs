@@ -0,0 +1,101 @@
+use super::{FlowEntity, FlowWorld, IntoEntityFlow, IntoFlow};
+
+/// Priority class of a flow.
+///
+/// [`Flows::execute`](super::Flows::execute) polls ready flows
+/// with higher priority first.
+/// When [`FlowBudget`](super::FlowBudget) is exhausted,
+/// lower priority flows are carried over to the next call.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FlowPriority {
+    /// Polled before other flows.
+    High,
+
+    /// Default priority.
+    #[default]
+    Normal,
+
+    /// Polled after other flows.
+    Low,
+}
+
+impl FlowPriority {
+    /// Number of priority classes.
+    pub(super) const COUNT: usize = 3;
+
+    /// Returns index of the priority class, higher priorities first.
+    #[inline]
+    pub(crate) fn index(self) -> usize {
+        match self {
+            FlowPriority::High => 0,
+            FlowPriority::Normal => 1,
+            FlowPriority::Low => 2,
+        }
+    }
+
+    /// Returns priority class with the index.
+    #[cfg(any(feature = "serde", feature = "nanoserde", feature = "alkahest"))]
+    #[inline]
+    pub(crate) fn from_index(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(FlowPriority::High),
+            1 => Some(FlowPriority::Normal),
+            2 => Some(FlowPriority::Low),
+            _ => None,
+        }
+    }
+}
+
+/// Flow with priority.
+///
+/// Created with [`with_priority`].
+pub struct Prioritized<F> {
+    priority: FlowPriority,
+    f: F,
+}
+
+/// Wraps flow to be spawned with specified priority.
+///
+/// # Example
+///
+/// ```
+/// # use edict::{flow::{with_priority, FlowPriority, FlowWorld}, world::World};
+/// let mut world = World::new();
+///
+/// world.spawn_flow(with_priority(FlowPriority::High, |world: FlowWorld| async move {
+///     world.spawn(());
+/// }));
+/// ```
+pub fn with_priority<F>(priority: FlowPriority, f: F) -> Prioritized<F> {
+    Prioritized { priority, f }
+}
+
+impl<F> IntoFlow for Prioritized<F>
+where
+    F: IntoFlow,
+{
+    type Flow = F::Flow;
+
+    fn into_flow(self, world: FlowWorld) -> Option<F::Flow> {
+        self.f.into_flow(world)
+    }
+
+    fn priority(&self) -> FlowPriority {
+        self.priority
+    }
+}
+
+impl<F> IntoEntityFlow for Prioritized<F>
+where
+    F: IntoEntityFlow,
+{
+    type Flow = F::Flow;
+
+    fn into_entity_flow(self, e: FlowEntity) -> Option<F::Flow> {
+        self.f.into_entity_flow(e)
+    }
+
+    fn priority(&self) -> FlowPriority {
+        self.priority
+    }
+}
@@ -0,0 +1,111 @@
+use core::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::wakers::WakeToken;
+
+use super::{watch::watch_resource, FlowWorld};
+
+/// Future that resolves when resource `T` is present in the world.
+///
+/// Created by [`FlowWorld::wait_resource`].
+#[must_use = "Future does nothing unless polled"]
+pub struct WaitResource<T> {
+    token: WakeToken,
+    world: FlowWorld,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Future for WaitResource<T>
+where
+    T: 'static,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let me = self.get_mut();
+
+        // Safety: world reference does not escape this scope.
+        let world = unsafe { me.world.get() };
+
+        if world.resource_version::<T>().is_some() {
+            return Poll::Ready(());
+        }
+
+        watch_resource::<T>(world, &me.token, cx);
+        Poll::Pending
+    }
+}
+
+/// Future that resolves when resource `T` changes.
+///
+/// Created by [`FlowWorld::wait_resource_changed`].
+#[must_use = "Future does nothing unless polled"]
+pub struct WaitResourceChanged<T> {
+    /// Version of the resource when future was created.
+    version: Option<u64>,
+    token: WakeToken,
+    world: FlowWorld,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Future for WaitResourceChanged<T>
+where
+    T: 'static,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let me = self.get_mut();
+
+        // Safety: world reference does not escape this scope.
+        let world = unsafe { me.world.get() };
+
+        if world.resource_version::<T>() != me.version {
+            return Poll::Ready(());
+        }
+
+        watch_resource::<T>(world, &me.token, cx);
+        Poll::Pending
+    }
+}
+
+impl FlowWorld {
+    /// Returns a future that resolves when resource `T` is present in the world.
+    ///
+    /// Resolves immediately if resource is already present.
+    /// Otherwise flow is woken when resource is inserted.
+    pub fn wait_resource<T>(self) -> WaitResource<T>
+    where
+        T: 'static,
+    {
+        WaitResource {
+            token: WakeToken::default(),
+            world: self,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a future that resolves when resource `T` changes
+    /// after this method is called.
+    ///
+    /// Resource is considered changed when it is inserted, removed
+    /// or borrowed mutably, see [`World::resource_version`](crate::world::World::resource_version).
+    pub fn wait_resource_changed<T>(self) -> WaitResourceChanged<T>
+    where
+        T: 'static,
+    {
+        // Safety: world reference does not escape this scope.
+        let world = unsafe { self.get() };
+
+        WaitResourceChanged {
+            version: world.resource_version::<T>(),
+            token: WakeToken::default(),
+            world: self,
+            marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,361 @@
+use core::{
+    any::type_name,
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    entity::EntityId,
+    type_id,
+    world::{World, WorldLocal},
+};
+
+use super::{Flow, FlowHandle, FlowPriority, FlowWorld, Flows, MakeFlow};
+
+/// Flow written as an explicit state machine
+/// that can be saved and restored with the world.
+///
+/// Unlike futures, state of such flows is visible.
+/// [`Flows::save`] encodes state of all running resumable flows
+/// and [`Flows::restore`] spawns them again from saved states,
+/// so they resume at the saved step.
+///
+/// Flow types must be registered with [`Flows::register_resumable`]
+/// in the executor that restores them.
+///
+/// # Example
+///
+/// ```
+/// # use core::task::{Context, Poll};
+/// # use edict::{flow::{FlowWorld, Flows, ResumableFlow}, world::World};
+/// struct Countdown(u8);
+///
+/// impl ResumableFlow for Countdown {
+///     const NAME: &'static str = "countdown";
+///
+///     fn step(&mut self, world: FlowWorld, cx: &mut Context<'_>) -> Poll<()> {
+///         if self.0 == 0 {
+///             world.insert_resource("liftoff");
+///             return Poll::Ready(());
+///         }
+///         self.0 -= 1;
+///
+///         // Continue on next execution.
+///         cx.waker().wake_by_ref();
+///         Poll::Pending
+///     }
+///
+///     fn save(&self, out: &mut Vec<u8>) {
+///         out.push(self.0);
+///     }
+///
+///     fn restore(data: &[u8]) -> Option<Self> {
+///         match *data {
+///             [n] => Some(Countdown(n)),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// let mut flows = Flows::new();
+///
+/// world.spawn_resumable(Countdown(3));
+/// flows.execute(&mut world);
+///
+/// let saved = flows.save(&mut world);
+///
+/// let mut world2 = World::new();
+/// let mut flows2 = Flows::new();
+/// flows2.register_resumable::<Countdown>();
+/// flows2.restore(&mut world2, saved).unwrap();
+///
+/// for _ in 0..3 {
+///     flows2.execute(&mut world2);
+/// }
+/// assert_eq!(*world2.expect_resource::<&str>(), "liftoff");
+/// ```
+pub trait ResumableFlow: Send + 'static {
+    /// Unique name of the flow type.
+    /// Used to find the type when flows are restored.
+    const NAME: &'static str;
+
+    /// Advances the flow.
+    ///
+    /// Returns [`Poll::Ready`] when flow is complete.
+    /// Otherwise flow must arrange to be woken using `cx`,
+    /// like [`Future::poll`](core::future::Future::poll) does.
+    fn step(&mut self, world: FlowWorld, cx: &mut Context<'_>) -> Poll<()>;
+
+    /// Encodes current state of the flow.
+    fn save(&self, out: &mut Vec<u8>);
+
+    /// Decodes state encoded with [`ResumableFlow::save`].
+    /// Returns `None` if data is invalid.
+    fn restore(data: &[u8]) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Returns priority of the flow.
+    fn priority(&self) -> FlowPriority {
+        FlowPriority::Normal
+    }
+}
+
+/// Saved state of a resumable flow.
+///
+/// Returned by [`Flows::save`] and consumed by [`Flows::restore`].
+/// Can be serialized alongside the world dump
+/// with any serialization crate supported by [`dump`](crate::dump) module.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SavedFlow {
+    name: String,
+    priority: FlowPriority,
+    data: Vec<u8>,
+}
+
+impl SavedFlow {
+    /// Creates saved flow from its parts.
+    pub fn new(name: String, priority: FlowPriority, data: Vec<u8>) -> Self {
+        SavedFlow {
+            name,
+            priority,
+            data,
+        }
+    }
+
+    /// Returns name of the flow type.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns priority of the flow.
+    pub fn priority(&self) -> FlowPriority {
+        self.priority
+    }
+
+    /// Returns encoded state of the flow.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Error returned by [`Flows::restore`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RestoreError {
+    /// Flow type with saved name is not registered.
+    UnknownFlow,
+
+    /// Flow type failed to decode saved state.
+    InvalidState,
+}
+
+impl fmt::Display for RestoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RestoreError::UnknownFlow => f.write_str("Resumable flow type is not registered"),
+            RestoreError::InvalidState => f.write_str("Invalid resumable flow state"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RestoreError {}
+
+/// Function that restores saved flow of specific type.
+pub(super) type RestoreFn = fn(&mut World, &SavedFlow) -> Result<(), RestoreError>;
+
+/// Function that saves flow of specific type.
+pub(super) type SaveFn<F> = fn(&F) -> SavedFlow;
+
+fn restore_flow<F>(world: &mut World, saved: &SavedFlow) -> Result<(), RestoreError>
+where
+    F: ResumableFlow,
+{
+    let flow = F::restore(&saved.data).ok_or(RestoreError::InvalidState)?;
+    world.new_flows.get_mut().add(ResumableIntoFlow {
+        flow,
+        priority: saved.priority,
+    });
+    Ok(())
+}
+
+/// Resumable flow polled by the executor.
+pub(super) struct ResumableTask<F> {
+    flow: F,
+    priority: FlowPriority,
+}
+
+impl<F> Flow for ResumableTask<F>
+where
+    F: ResumableFlow,
+{
+    type Output = ();
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<()>> {
+        // Safety: `flow` is not structurally pinned.
+        let me = unsafe { self.get_unchecked_mut() };
+        me.flow.step(FlowWorld::new(), cx).map(Some)
+    }
+}
+
+fn save_flow<F>(task: &ResumableTask<F>) -> SavedFlow
+where
+    F: ResumableFlow,
+{
+    let mut data = Vec::new();
+    task.flow.save(&mut data);
+    SavedFlow::new(F::NAME.into(), task.priority, data)
+}
+
+pub(super) struct ResumableIntoFlow<F> {
+    flow: F,
+    priority: FlowPriority,
+}
+
+impl<F> MakeFlow for ResumableIntoFlow<F>
+where
+    F: ResumableFlow,
+{
+    type Flow = ResumableTask<F>;
+
+    fn priority(&self) -> FlowPriority {
+        self.priority
+    }
+
+    fn entity(&self) -> Option<EntityId> {
+        None
+    }
+
+    fn type_name(&self) -> &'static str {
+        type_name::<F>()
+    }
+
+    fn saver() -> Option<SaveFn<ResumableTask<F>>> {
+        Some(save_flow::<F>)
+    }
+
+    fn make_flow(self) -> Option<ResumableTask<F>> {
+        Some(ResumableTask {
+            flow: self.flow,
+            priority: self.priority,
+        })
+    }
+}
+
+impl World {
+    /// Spawns a resumable flow in the world.
+    /// It will be polled during [`Flows::execute`] until completion.
+    ///
+    /// Returns [`FlowHandle`] to await flow's completion or abort it.
+    #[track_caller]
+    pub fn spawn_resumable<F>(&mut self, flow: F) -> FlowHandle<()>
+    where
+        F: ResumableFlow,
+    {
+        let priority = flow.priority();
+        self.new_flows
+            .get_mut()
+            .add(ResumableIntoFlow { flow, priority })
+    }
+}
+
+impl WorldLocal {
+    /// Spawns a resumable flow in the world.
+    /// It will be polled during [`Flows::execute`] until completion.
+    ///
+    /// Returns [`FlowHandle`] to await flow's completion or abort it.
+    #[track_caller]
+    pub fn spawn_resumable<F>(&self, flow: F) -> FlowHandle<()>
+    where
+        F: ResumableFlow,
+    {
+        let priority = flow.priority();
+
+        // Safety: accessed only from "main" thread.
+        unsafe { &mut *self.new_flows.get() }.add(ResumableIntoFlow { flow, priority })
+    }
+}
+
+impl FlowWorld {
+    /// Spawns a resumable flow in the world.
+    /// It will be polled during [`Flows::execute`] until completion.
+    ///
+    /// Returns [`FlowHandle`] to await flow's completion or abort it.
+    #[track_caller]
+    pub fn spawn_resumable<F>(self, flow: F) -> FlowHandle<()>
+    where
+        F: ResumableFlow,
+    {
+        // Safety: world reference does not escape this scope.
+        let world = unsafe { self.get() };
+
+        world.spawn_resumable(flow)
+    }
+}
+
+impl Flows {
+    /// Registers resumable flow type to be restored by [`Flows::restore`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if different flow type with the same name is already registered.
+    pub fn register_resumable<F>(&mut self)
+    where
+        F: ResumableFlow,
+    {
+        let old = self
+            .resumable
+            .insert(F::NAME, (type_id::<F>(), restore_flow::<F>));
+
+        if let Some((id, _)) = old {
+            assert_eq!(
+                id,
+                type_id::<F>(),
+                "Resumable flow name {:?} is already registered for another type",
+                F::NAME,
+            );
+        }
+    }
+
+    /// Saves state of all running resumable flows.
+    ///
+    /// Flows spawned in the world since last [`Flows::execute`]
+    /// are collected first, so they are saved too.
+    /// Flows are not stopped and continue running.
+    pub fn save(&mut self, world: &mut World) -> Vec<SavedFlow> {
+        drop(self.collect_new_flows(world));
+
+        let mut out = Vec::new();
+        for queues in &self.queues {
+            for queue in &queues.queues {
+                queue.flows.save(&mut out);
+            }
+        }
+        out
+    }
+
+    /// Restores saved resumable flows into the world.
+    ///
+    /// Restored flows start running on next [`Flows::execute`]
+    /// from the saved step.
+    ///
+    /// Stops at the first flow that fails to restore.
+    /// Flows restored before it are kept.
+    pub fn restore<I>(&mut self, world: &mut World, saved: I) -> Result<(), RestoreError>
+    where
+        I: IntoIterator<Item = SavedFlow>,
+    {
+        for saved in saved {
+            let (_, restore) = self
+                .resumable
+                .get(saved.name())
+                .ok_or(RestoreError::UnknownFlow)?;
+            restore(world, &saved)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use alloc::vec::Vec;
+
+use crate::entity::EntityId;
+
+use super::{AbortHandle, Flow, FlowHandle, FlowWorld, IntoEntityFlow, IntoFlow};
+
+/// Scope for child flows.
+///
+/// Child flows are spawned into the [`Flows`](super::Flows) executor
+/// like any other flow and run concurrently with the parent flow.
+/// When scope is dropped, e.g. when parent flow finishes or is aborted,
+/// all child flows that are still running are aborted.
+///
+/// Created with [`FlowWorld::scope`].
+///
+/// # Example
+///
+/// ```
+/// # use edict::{flow::{FlowWorld, Flows}, world::World};
+/// let mut world = World::new();
+///
+/// world.spawn_flow(|world: FlowWorld| async move {
+///     let mut scope = world.scope();
+///     let child = scope.spawn(|_: FlowWorld| async move { 42 });
+///     assert_eq!(child.await, Ok(42));
+/// });
+///
+/// let mut flows = Flows::new();
+/// flows.execute(&mut world);
+/// flows.execute(&mut world);
+/// ```
+#[derive(Debug)]
+#[must_use = "Child flows are aborted when scope is dropped"]
+pub struct FlowScope {
+    world: FlowWorld,
+    children: Vec<AbortHandle>,
+}
+
+impl Drop for FlowScope {
+    fn drop(&mut self) {
+        for child in self.children.drain(..) {
+            child.abort();
+        }
+    }
+}
+
+impl FlowScope {
+    /// Spawns a child flow.
+    ///
+    /// Returns [`FlowHandle`] to await flow's output or abort it.
+    #[track_caller]
+    pub fn spawn<F>(&mut self, flow: F) -> FlowHandle<<F::Flow as Flow>::Output>
+    where
+        F: IntoFlow,
+    {
+        let handle = self.world.spawn_flow(flow);
+        self.add(handle.abort_handle());
+        handle
+    }
+
+    /// Spawns a child flow for an entity.
+    ///
+    /// Returns [`FlowHandle`] to await flow's output or abort it.
+    #[track_caller]
+    pub fn spawn_for<F>(
+        &mut self,
+        entity: EntityId,
+        flow: F,
+    ) -> FlowHandle<<F::Flow as Flow>::Output>
+    where
+        F: IntoEntityFlow,
+    {
+        let handle = self.world.spawn_flow_for(entity, flow);
+        self.add(handle.abort_handle());
+        handle
+    }
+
+    /// Returns number of child flows that are still running.
+    pub fn running(&self) -> usize {
+        self.children
+            .iter()
+            .filter(|child| !child.is_finished())
+            .count()
+    }
+
+    /// Aborts all child flows that are still running.
+    pub fn abort_all(&mut self) {
+        for child in self.children.drain(..) {
+            child.abort();
+        }
+    }
+
+    fn add(&mut self, child: AbortHandle) {
+        // Forget finished children to keep long-lived scopes small.
+        self.children.retain(|child| !child.is_finished());
+        self.children.push(child);
+    }
+}
+
+impl FlowWorld {
+    /// Returns new scope for child flows.
+    ///
+    /// Child flows are aborted when scope is dropped.
+    pub fn scope(self) -> FlowScope {
+        FlowScope {
+            world: self,
+            children: Vec::new(),
+        }
+    }
+}
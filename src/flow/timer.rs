@@ -0,0 +1,436 @@
+use core::{
+    cmp::Ordering,
+    fmt,
+    future::{Future, IntoFuture},
+    pin::Pin,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use alloc::{collections::BinaryHeap, sync::Arc};
+
+use amity::spin::Spin;
+
+use crate::resources::ResMut;
+
+use super::{get_flow_world, FlowEntity, FlowWorld};
+
+/// Waker of the sleeping flow shared between [`Sleep`] and its timer.
+type TimerSlot = Arc<Spin<Option<Waker>>>;
+
+/// Minimal number of timers to prune cancelled ones.
+const MIN_PRUNE: usize = 64;
+
+/// Timer registered by a sleeping flow.
+struct Timer<T> {
+    deadline: T,
+
+    /// Registration order to wake timers with equal deadlines in order.
+    seq: u64,
+    slot: TimerSlot,
+}
+
+impl<T> Timer<T> {
+    /// Timer is cancelled when its [`Sleep`] is dropped.
+    fn is_cancelled(&self) -> bool {
+        Arc::strong_count(&self.slot) == 1
+    }
+
+    fn wake(self) {
+        if self.is_cancelled() {
+            return;
+        }
+        if let Some(waker) = self.slot.lock().take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T: Ord> PartialEq for Timer<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Ord> Eq for Timer<T> {}
+
+impl<T: Ord> PartialOrd for Timer<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> Ord for Timer<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed to make `BinaryHeap` a min-heap.
+        (&other.deadline, other.seq).cmp(&(&self.deadline, self.seq))
+    }
+}
+
+/// Timers registered by sleeping flows.
+struct Timers {
+    next_seq: u64,
+    time: BinaryHeap<Timer<Duration>>,
+    tick: BinaryHeap<Timer<u64>>,
+
+    /// Number of timers at which cancelled timers are pruned.
+    prune_at: usize,
+}
+
+impl Timers {
+    fn len(&self) -> usize {
+        self.time.len() + self.tick.len()
+    }
+
+    fn register(&mut self, deadline: Deadline, slot: TimerSlot) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+
+        match deadline {
+            Deadline::Time(deadline) => self.time.push(Timer {
+                deadline,
+                seq,
+                slot,
+            }),
+            Deadline::Tick(deadline) => self.tick.push(Timer {
+                deadline,
+                seq,
+                slot,
+            }),
+        }
+
+        if self.len() >= self.prune_at {
+            self.time.retain(|t| !t.is_cancelled());
+            self.tick.retain(|t| !t.is_cancelled());
+            self.prune_at = MIN_PRUNE.max(self.len() * 2);
+        }
+    }
+}
+
+/// Resource with world time used by flow timers.
+///
+/// Time is advanced with [`FlowTime::advance`] or [`FlowTime::tick`],
+/// usually by the [`flow_time_system`] once per frame.
+/// Each advance is one tick.
+/// Flows sleeping with [`FlowWorld::sleep`] and [`FlowWorld::sleep_ticks`]
+/// are woken when their deadline is reached and resume
+/// on the next [`Flows::execute`](super::Flows::execute).
+///
+/// The resource must be inserted into the world before flows use timers.
+/// Timers panic if it is missing.
+///
+/// Registering timers does not borrow the resource mutably,
+/// so [resource version](crate::world::World::resource_version)
+/// changes only when time is advanced.
+pub struct FlowTime {
+    elapsed: Duration,
+    ticks: u64,
+    step: Duration,
+    timers: Spin<Timers>,
+}
+
+impl fmt::Debug for FlowTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlowTime")
+            .field("elapsed", &self.elapsed)
+            .field("ticks", &self.ticks)
+            .field("step", &self.step)
+            .field("timers", &self.timers())
+            .finish()
+    }
+}
+
+impl FlowTime {
+    /// Returns new time resource with fixed step
+    /// applied by [`FlowTime::tick`].
+    ///
+    /// With zero step only [`FlowWorld::sleep_ticks`] and [`FlowTime::advance`]
+    /// are useful.
+    pub const fn with_step(step: Duration) -> Self {
+        FlowTime {
+            elapsed: Duration::ZERO,
+            ticks: 0,
+            step,
+            timers: Spin::new(Timers {
+                next_seq: 0,
+                time: BinaryHeap::new(),
+                tick: BinaryHeap::new(),
+                prune_at: MIN_PRUNE,
+            }),
+        }
+    }
+
+    /// Returns time elapsed since the resource was created.
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns number of ticks since the resource was created.
+    #[inline]
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Returns step applied by [`FlowTime::tick`].
+    #[inline]
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Sets step applied by [`FlowTime::tick`].
+    ///
+    /// Games with variable frame rate may set it to frame delta time
+    /// before [`flow_time_system`] runs.
+    #[inline]
+    pub fn set_step(&mut self, step: Duration) {
+        self.step = step;
+    }
+
+    /// Returns number of registered timers.
+    /// Timers of dropped [`Sleep`] futures are not counted.
+    pub fn timers(&self) -> usize {
+        let timers = self.timers.lock();
+        let time = timers.time.iter().filter(|t| !t.is_cancelled());
+        let tick = timers.tick.iter().filter(|t| !t.is_cancelled());
+        time.count() + tick.count()
+    }
+
+    /// Advances time by the step.
+    #[inline]
+    pub fn tick(&mut self) {
+        self.advance(self.step);
+    }
+
+    /// Advances time by `delta` and counts one tick.
+    /// Wakes flows with reached deadlines.
+    pub fn advance(&mut self, delta: Duration) {
+        self.elapsed += delta;
+        self.ticks += 1;
+
+        let timers = self.timers.get_mut();
+
+        while let Some(timer) = timers.time.peek() {
+            if timer.deadline > self.elapsed {
+                break;
+            }
+            timers.time.pop().unwrap().wake();
+        }
+
+        while let Some(timer) = timers.tick.peek() {
+            if timer.deadline > self.ticks {
+                break;
+            }
+            timers.tick.pop().unwrap().wake();
+        }
+    }
+
+    fn register(&self, deadline: Deadline, slot: TimerSlot) {
+        self.timers.lock().register(deadline, slot);
+    }
+
+    fn reached(&self, deadline: Deadline) -> bool {
+        match deadline {
+            Deadline::Time(deadline) => self.elapsed >= deadline,
+            Deadline::Tick(deadline) => self.ticks >= deadline,
+        }
+    }
+
+    fn deadline_after(&self, duration: Duration) -> Deadline {
+        Deadline::Time(self.elapsed.saturating_add(duration))
+    }
+
+    fn deadline_after_ticks(&self, ticks: u64) -> Deadline {
+        Deadline::Tick(self.ticks.saturating_add(ticks))
+    }
+}
+
+/// System that advances [`FlowTime`] by its step.
+///
+/// Should run once per frame before flows are executed.
+///
+/// # Panics
+///
+/// Panics if [`FlowTime`] resource is not inserted into the world.
+pub fn flow_time_system(mut time: ResMut<FlowTime>) {
+    time.tick();
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Deadline {
+    Time(Duration),
+    Tick(u64),
+}
+
+/// Future that resolves when [`FlowTime`] reaches the deadline.
+///
+/// Created by [`FlowWorld::sleep`] and [`FlowWorld::sleep_ticks`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Sleep {
+    deadline: Deadline,
+
+    /// Slot of the registered timer.
+    /// Dropping it cancels the timer.
+    slot: Option<TimerSlot>,
+}
+
+impl Sleep {
+    fn new(deadline: Deadline) -> Self {
+        Sleep {
+            deadline,
+            slot: None,
+        }
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let me = self.get_mut();
+
+        // Safety: world reference does not escape this scope.
+        let world = unsafe { get_flow_world() };
+        let time = world.expect_resource::<FlowTime>();
+
+        if time.reached(me.deadline) {
+            me.slot = None;
+            return Poll::Ready(());
+        }
+
+        match &me.slot {
+            Some(slot) => {
+                let mut waker = slot.lock();
+                match &*waker {
+                    Some(waker) if waker.will_wake(cx.waker()) => {}
+                    _ => *waker = Some(cx.waker().clone()),
+                }
+            }
+            None => {
+                let slot = Arc::new(Spin::new(Some(cx.waker().clone())));
+                time.register(me.deadline, slot.clone());
+                me.slot = Some(slot);
+            }
+        }
+        Poll::Pending
+    }
+}
+
+/// Error returned by [`Timeout`] future when deadline is reached
+/// before the inner future completes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Elapsed;
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Deadline has elapsed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Elapsed {}
+
+/// Future that resolves to inner future output
+/// or to [`Elapsed`] error if deadline is reached first.
+///
+/// Created by [`FlowWorld::timeout`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Timeout<F> {
+    fut: F,
+    sleep: Sleep,
+}
+
+impl<F> Future for Timeout<F>
+where
+    F: Future,
+{
+    type Output = Result<F::Output, Elapsed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<F::Output, Elapsed>> {
+        // Safety: `fut` is never moved.
+        let me = unsafe { self.get_unchecked_mut() };
+        let fut = unsafe { Pin::new_unchecked(&mut me.fut) };
+
+        if let Poll::Ready(output) = fut.poll(cx) {
+            return Poll::Ready(Ok(output));
+        }
+
+        match Pin::new(&mut me.sleep).poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(Elapsed)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl FlowWorld {
+    /// Returns a future that resolves after `duration` of world time.
+    ///
+    /// World time is [`FlowTime`] resource.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`FlowTime`] resource is not inserted into the world.
+    pub fn sleep(self, duration: Duration) -> Sleep {
+        // Safety: world reference does not escape this scope.
+        let world = unsafe { self.get() };
+        let time = world.expect_resource::<FlowTime>();
+
+        Sleep::new(time.deadline_after(duration))
+    }
+
+    /// Returns a future that resolves after `ticks` ticks of world time.
+    ///
+    /// World time is [`FlowTime`] resource.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`FlowTime`] resource is not inserted into the world.
+    pub fn sleep_ticks(self, ticks: u64) -> Sleep {
+        // Safety: world reference does not escape this scope.
+        let world = unsafe { self.get() };
+        let time = world.expect_resource::<FlowTime>();
+
+        Sleep::new(time.deadline_after_ticks(ticks))
+    }
+
+    /// Returns a future that resolves to the output of `fut`
+    /// or fails with [`Elapsed`] if `fut` does not complete
+    /// within `duration` of world time.
+    pub fn timeout<F>(self, duration: Duration, fut: F) -> Timeout<F::IntoFuture>
+    where
+        F: IntoFuture,
+    {
+        Timeout {
+            fut: fut.into_future(),
+            sleep: self.sleep(duration),
+        }
+    }
+}
+
+impl FlowEntity {
+    /// Returns a future that resolves after `duration` of world time.
+    ///
+    /// See [`FlowWorld::sleep`].
+    pub fn sleep(self, duration: Duration) -> Sleep {
+        self.world().sleep(duration)
+    }
+
+    /// Returns a future that resolves after `ticks` ticks of world time.
+    ///
+    /// See [`FlowWorld::sleep_ticks`].
+    pub fn sleep_ticks(self, ticks: u64) -> Sleep {
+        self.world().sleep_ticks(ticks)
+    }
+
+    /// Returns a future that resolves to the output of `fut`
+    /// or fails with [`Elapsed`] if `fut` does not complete
+    /// within `duration` of world time.
+    ///
+    /// See [`FlowWorld::timeout`].
+    pub fn timeout<F>(self, duration: Duration, fut: F) -> Timeout<F::IntoFuture>
+    where
+        F: IntoFuture,
+    {
+        self.world().timeout(duration, fut)
+    }
+}
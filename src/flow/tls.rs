@@ -66,11 +66,22 @@ impl Drop for WorldGuard<'_> {
 /// The caller is responsible to ensure that the reference
 /// is not used after current `Guard` is dropped.
 pub(super) unsafe fn get_world_mut<'a>() -> &'a mut WorldLocal {
+    unsafe { try_get_world_mut() }.unwrap()
+}
+
+/// Returns the current world reference if `WorldGuard` exists.
+///
+/// # Safety
+///
+/// Returns reference with unbound lifetime.
+/// The caller is responsible to ensure that the reference
+/// is not used after current `Guard` is dropped.
+pub(super) unsafe fn try_get_world_mut<'a>() -> Option<&'a mut WorldLocal> {
     #[cfg(feature = "std")]
     let world = WORLD_TLS.with(|cell| cell.get());
 
     #[cfg(not(feature = "std"))]
     let world = unsafe { edict_get_flow_world_tls() }.map(NonNull::cast);
 
-    unsafe { world.unwrap().as_mut() }
+    world.map(|mut world| unsafe { world.as_mut() })
 }
@@ -0,0 +1,246 @@
+use core::{
+    any::TypeId,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use alloc::vec::Vec;
+
+use crate::{
+    archetype::Archetype,
+    entity::EntityId,
+    query::{DefaultQuery, ImmutableQuery, IntoQuery, Query, QueryItem},
+    view::View,
+    wakers::WakeToken,
+    world::World,
+    EntityError, NoSuchEntity,
+};
+
+use super::{FlowEntity, FlowWorld};
+
+/// Returns components of the archetype accessed by the query.
+fn accessed<Q: Query>(query: &Q, archetype: &Archetype) -> Vec<TypeId> {
+    archetype
+        .infos()
+        .filter(|info| !matches!(query.component_access(info), Ok(None)))
+        .map(|info| info.id())
+        .collect()
+}
+
+/// Registers waker to wake the task when entity changes
+/// in a way that may affect the query.
+///
+/// Task is woken when entities are added to or removed from
+/// the entity's archetype, including when entity itself is despawned
+/// or moved to another archetype, or when components of the archetype
+/// accessed by the query are borrowed mutably.
+pub(super) fn watch_entity<Q: Query>(
+    world: &mut World,
+    entity: EntityId,
+    query: Q,
+    token: &WakeToken,
+    cx: &mut Context,
+) {
+    let Some(loc) = world.entities().get_location(entity) else {
+        // Nothing to watch.
+        return;
+    };
+
+    // Reserved entities are placed into the empty archetype.
+    let arch = if loc.arch == u32::MAX { 0 } else { loc.arch };
+
+    let archetype = &mut world.archetypes_mut()[arch as usize];
+    let types = accessed(&query, archetype);
+    archetype.register_waker(types.into_iter(), token, cx.waker());
+}
+
+/// Registers waker to wake the task when components
+/// accessed by the query change anywhere in the world.
+///
+/// Task is woken when entities are added to or removed from
+/// archetypes that match the query, when accessed components
+/// are borrowed mutably or when new archetype is created.
+fn watch_world<Q: Query>(world: &mut World, query: Q, token: &WakeToken, cx: &mut Context) {
+    for archetype in world.archetypes_mut() {
+        if query.visit_archetype(archetype) {
+            let types = accessed(&query, archetype);
+            archetype.register_waker(types.into_iter(), token, cx.waker());
+        }
+    }
+
+    world.register_new_archetype_waker(token, cx.waker());
+}
+
+/// Registers waker to wake the task when resource `T` is inserted,
+/// borrowed mutably or removed.
+pub(super) fn watch_resource<T: 'static>(world: &mut World, token: &WakeToken, cx: &mut Context) {
+    world.register_resource_waker::<T>(token, cx.waker());
+}
+
+/// Future that resolves when entity matches the query
+/// and predicate returns `true` for the query item.
+/// Resolves with `Err(NoSuchEntity)` if entity is despawned.
+///
+/// Created by [`FlowEntity::wait_until`].
+#[must_use = "Future does nothing unless polled"]
+pub struct WaitEntity<Q, F> {
+    entity: EntityId,
+    query: Q,
+    f: F,
+    token: WakeToken,
+    world: FlowWorld,
+}
+
+impl<Q, F> Future for WaitEntity<Q, F>
+where
+    Q: ImmutableQuery,
+    for<'a> F: FnMut(QueryItem<'a, Q>) -> bool,
+{
+    type Output = Result<(), NoSuchEntity>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), NoSuchEntity>> {
+        // Safety: `me` never moves.
+        let me = unsafe { self.get_unchecked_mut() };
+
+        // Safety: world reference does not escape this scope.
+        let world = unsafe { me.world.get() };
+
+        match world.get_with(me.entity, me.query) {
+            Err(EntityError::NoSuchEntity) => return Poll::Ready(Err(NoSuchEntity)),
+            Err(EntityError::Mismatch) => {}
+            Ok(item) => {
+                if (me.f)(item) {
+                    return Poll::Ready(Ok(()));
+                }
+            }
+        }
+
+        watch_entity(world, me.entity, me.query, &me.token, cx);
+        Poll::Pending
+    }
+}
+
+/// Future that resolves when predicate returns `true` for the world view.
+///
+/// Created by [`FlowWorld::wait_until`].
+#[must_use = "Future does nothing unless polled"]
+pub struct WaitWorld<Q, F> {
+    query: Q,
+    f: F,
+    token: WakeToken,
+    world: FlowWorld,
+}
+
+impl<Q, F> Future for WaitWorld<Q, F>
+where
+    Q: ImmutableQuery,
+    for<'a> F: FnMut(View<'a, Q>) -> bool,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // Safety: `me` never moves.
+        let me = unsafe { self.get_unchecked_mut() };
+
+        // Safety: world reference does not escape this scope.
+        let world = unsafe { me.world.get() };
+
+        let view = world.view_filter_with_mut(me.query, ());
+        if (me.f)(view.into()) {
+            return Poll::Ready(());
+        }
+
+        watch_world(world, me.query, &me.token, cx);
+        Poll::Pending
+    }
+}
+
+impl FlowWorld {
+    /// Returns a future that resolves when predicate returns `true`
+    /// for the view constructed with specified query type.
+    ///
+    /// Flow is woken to check predicate again only when components
+    /// accessed by the query may have been inserted, modified or removed.
+    /// Wakes are tracked per archetype and component,
+    /// so predicate may be checked again without relevant changes.
+    pub fn wait_until<Q, F>(self, f: F) -> WaitWorld<Q::Query, F>
+    where
+        Q: DefaultQuery,
+        Q::Query: ImmutableQuery,
+        F: FnMut(View<Q>) -> bool,
+    {
+        WaitWorld {
+            query: Q::default_query(),
+            f,
+            token: WakeToken::default(),
+            world: self,
+        }
+    }
+
+    /// Returns a future that resolves when predicate returns `true`
+    /// for the view constructed with specified query.
+    ///
+    /// Flow is woken to check predicate again only when components
+    /// accessed by the query are inserted, modified or removed.
+    pub fn wait_until_with<Q, F>(self, query: Q, f: F) -> WaitWorld<Q::Query, F>
+    where
+        Q: IntoQuery,
+        Q::Query: ImmutableQuery,
+        F: FnMut(View<Q::Query>) -> bool,
+    {
+        WaitWorld {
+            query: query.into_query(),
+            f,
+            token: WakeToken::default(),
+            world: self,
+        }
+    }
+}
+
+impl FlowEntity {
+    /// Returns a future that resolves when entity matches the query
+    /// and predicate returns `true` for the query item.
+    ///
+    /// Flow is woken to check predicate again only when components
+    /// of the entity accessed by the query may have been modified
+    /// or when components are inserted or removed.
+    /// Wakes are tracked per archetype and component,
+    /// so changes of other entities in the same archetype
+    /// cause predicate to be checked again.
+    ///
+    /// Resolves with `Err(NoSuchEntity)` if the entity is despawned.
+    pub fn wait_until<Q, F>(self, f: F) -> WaitEntity<Q::Query, F>
+    where
+        Q: DefaultQuery,
+        Q::Query: ImmutableQuery,
+        F: FnMut(QueryItem<Q>) -> bool,
+    {
+        WaitEntity {
+            entity: self.id(),
+            query: Q::default_query(),
+            f,
+            token: WakeToken::default(),
+            world: self.world(),
+        }
+    }
+
+    /// Returns a future that resolves when entity matches the query
+    /// and predicate returns `true` for the query item.
+    ///
+    /// See [`FlowEntity::wait_until`].
+    pub fn wait_until_with<Q, F>(self, query: Q, f: F) -> WaitEntity<Q::Query, F>
+    where
+        Q: IntoQuery,
+        Q::Query: ImmutableQuery,
+        F: FnMut(QueryItem<Q::Query>) -> bool,
+    {
+        WaitEntity {
+            entity: self.id(),
+            query: query.into_query(),
+            f,
+            token: WakeToken::default(),
+            world: self.world(),
+        }
+    }
+}
@@ -14,10 +14,10 @@ use crate::{
     relation::Relation,
     view::View,
     world::WorldLocal,
-    EntityError, NoSuchEntity,
+    DespawnError, EntityError, NoSuchEntity,
 };
 
-use super::{get_flow_world, Flow, FlowEntity};
+use super::{get_flow_world, Flow, FlowEntity, FlowPriority};
 
 /// Type that can be spawned as a flow.
 /// It can be an async function or a closure
@@ -35,7 +35,7 @@ use super::{get_flow_world, Flow, FlowEntity};
 /// });
 /// ```
 #[diagnostic::on_unimplemented(
-    note = "Try `async fn(world: FlowWorld)` or `|world: FlowWorld| async {{ ... }}`"
+    note = "Try `async fn(world: FlowWorld)` or `flow_fn!(|world: FlowWorld| {{ ... }})`"
 )]
 pub trait IntoFlow: 'static {
     /// Flow type that will be polled.
@@ -43,6 +43,13 @@ pub trait IntoFlow: 'static {
 
     /// Converts self into a flow.
     fn into_flow(self, world: FlowWorld) -> Option<Self::Flow>;
+
+    /// Returns priority of the flow.
+    ///
+    /// See [`with_priority`](super::with_priority).
+    fn priority(&self) -> FlowPriority {
+        FlowPriority::Normal
+    }
 }
 
 /// World reference that is updated when flow is polled.
@@ -61,20 +68,22 @@ pub struct FutureFlow<F> {
 
 impl<F> Flow for FutureFlow<F>
 where
-    F: Future<Output = ()> + Send,
+    F: Future + Send,
 {
-    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+    type Output = F::Output;
+
+    unsafe fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<F::Output>> {
         let this = unsafe { self.get_unchecked_mut() };
         let fut = unsafe { Pin::new_unchecked(&mut this.fut) };
-        
-        fut.poll(cx)
+
+        fut.poll(cx).map(Some)
     }
 }
 
 impl<F, Fut> IntoFlow for F
 where
     F: FnOnce(FlowWorld) -> Fut + 'static,
-    Fut: Future<Output = ()> + Send + 'static,
+    Fut: Future + Send + 'static,
 {
     type Flow = FutureFlow<Fut>;
 
@@ -1150,6 +1159,9 @@ impl FlowWorld {
     /// Despawns an entity with specified id.
     /// Returns [`Err(NoSuchEntity)`] if entity does not exists.
     ///
+    /// Entity protected from despawning by a relation is left alive.
+    /// Use [`FlowWorld::try_despawn`] to detect this.
+    ///
     /// # Example
     ///
     /// ```
@@ -1167,6 +1179,18 @@ impl FlowWorld {
         world.despawn(entity)
     }
 
+    /// Despawns an entity with specified id.
+    /// Returns [`Err(DespawnError::NoSuchEntity)`] if entity does not exists.
+    /// Returns [`Err(DespawnError::Denied)`] if entity is protected from despawning
+    /// by a relation.
+    #[inline]
+    pub fn try_despawn(self, entity: impl Entity) -> Result<(), DespawnError> {
+        // Safety: world reference does not escape this scope.
+        let world = unsafe { self.get() };
+
+        world.try_despawn(entity)
+    }
+
     /// Explicitly registers component type.
     ///
     /// Unlike [`WorldBuilder::register_component`](crate::world::WorldBuilder::register_component) method, this method does not return reference to component configuration,
@@ -47,9 +47,9 @@
 //! // Run systems without parallelism.
 //! scheduler.run_sequential(&mut world);
 //!
-//! # #[cfg(feature = "std")]
+//! # #[cfg(feature = "threaded-scheduler")]
 //! # {
-//! // Run systems using threads. Requires "std" feature.
+//! // Run systems using threads. Requires "threaded-scheduler" feature.
 //! scheduler.run_threaded(&mut world);
 //! # }
 //!
@@ -280,6 +280,7 @@
 //! Functions that return futures may serve as flows.
 //! For [`World::spawn_flow`] use function or closure with signature `FnOnce(FlowWorld) -> Future`
 //! For [`World::spawn_flow_for`] use function or closure with signature `FnOnce(FlowEntity) -> Future`
+//! Closures with async body may be created with [`flow_fn!`] macro.
 //!
 //! User may implement low-level futures using `poll*` methods of [`FlowWorld`] and [`FlowEntity`] to access tasks [`Context`].
 //! Edict provides only a couple of low-level futures that will do the waiting:
@@ -289,6 +290,12 @@
 //!
 //! [`WakeOnDrop`] component can be used when despawning entity should wake a task.
 //!
+//! Flows that should survive saving and loading the world may be written
+//! as explicit state machines implementing [`ResumableFlow`].
+//!
+//! With `"threaded-scheduler"` feature CPU-heavy parts of flows may be offloaded
+//! to worker threads with `FlowWorld::offload` and executed with `Flows::execute_with`.
+//!
 //! It is recommended to use flows for high-level logic that spans multiple ticks
 //! and use systems to do low-level logic that runs every tick.
 //! Flows may request systems to perform operations by adding special components to entities.
@@ -330,6 +337,7 @@
 //! [`EntityRef`]: crate::entity::EntityRef
 //! [`EntityRef::spawn_flow`]: crate::entity::EntityRef::spawn_flow
 //! [`flow`]: crate::flow
+//! [`flow_fn!`]: crate::flow::flow_fn
 //! [`FlowEntity`]: crate::flow::FlowEntity
 //! [`FlowEntity::spawn_flow`]: crate::flow::FlowEntity::spawn_flow
 //! [`FlowEntity::wait_despawned`]: crate::flow::FlowEntity::wait_despawned
@@ -351,6 +359,7 @@
 //! [`ResMut`]: crate::resources::ResMut
 //! [`ResLocal`]: crate::system::ResLocal
 //! [`ResMutLocal`]: crate::system::ResMutLocal
+//! [`ResumableFlow`]: crate::flow::ResumableFlow
 //! [`Scheduler`]: crate::scheduler::Scheduler
 //! [`Scheduler::run_rayon`]: crate::scheduler::Scheduler::run_rayon
 //! [`Scheduler::run_threaded`]: crate::scheduler::Scheduler::run_threaded
@@ -560,6 +569,7 @@ macro_rules! marker_type {
 }
 
 mod hash;
+mod wakers;
 
 pub mod action;
 pub mod archetype;
@@ -568,6 +578,7 @@ pub mod component;
 pub mod dump;
 pub mod entity;
 pub mod epoch;
+pub mod event;
 
 pub mod query;
 pub mod relation;
@@ -674,6 +685,36 @@ impl fmt::Display for EntityError {
 #[cfg(feature = "std")]
 impl std::error::Error for EntityError {}
 
+/// Error that may be returned when despawning an entity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DespawnError {
+    /// Error returned when an entity is not found in the world.
+    NoSuchEntity,
+
+    /// Entity is protected from despawning by a relation
+    /// with [`DespawnPolicy::Deny`](crate::relation::DespawnPolicy::Deny) policy.
+    Denied,
+}
+
+impl From<NoSuchEntity> for DespawnError {
+    #[inline]
+    fn from(_: NoSuchEntity) -> Self {
+        DespawnError::NoSuchEntity
+    }
+}
+
+impl fmt::Display for DespawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DespawnError::NoSuchEntity => fmt::Display::fmt(&NoSuchEntity, f),
+            DespawnError::Denied => f.write_str("Despawning of the entity is denied by a relation"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DespawnError {}
+
 /// Specifies kind of access query performs for particular component.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Access {
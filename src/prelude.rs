@@ -14,5 +14,5 @@ pub use crate::{
     system::{ResLocal, ResMutLocal, State, System},
     view::{View, ViewCell, ViewCellIter, ViewIter, ViewMut, ViewOne, ViewRef},
     world::{World, WorldBuilder},
-    EntityError, NoSuchEntity,
+    DespawnError, EntityError, NoSuchEntity,
 };
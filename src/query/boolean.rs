@@ -2,7 +2,7 @@ use core::{any::TypeId, marker::PhantomData, ops::ControlFlow};
 
 use crate::{
     archetype::Archetype, component::ComponentInfo, entity::EntityId, epoch::EpochId,
-    system::QueryArg,
+    system::QueryArg, world::World,
 };
 
 use super::{
@@ -288,6 +288,7 @@ macro_rules! impl_boolean {
             }
         }
 
+        #[allow(non_snake_case)]
         impl<Op $(, $a)+> QueryArg for BooleanQuery<($($a,)+), Op>
         where
             $($a: QueryArg,)+
@@ -300,6 +301,18 @@ macro_rules! impl_boolean {
                     op: PhantomData,
                 }
             }
+
+            #[inline]
+            fn before(&mut self, world: &World) {
+                let ($($a,)+) = &mut self.tuple;
+                $($a.before(world);)+
+            }
+
+            #[inline]
+            fn after(&mut self, world: &World) {
+                let ($($a,)+) = &mut self.tuple;
+                $($a.after(world);)+
+            }
         }
 
         #[allow(non_snake_case)]
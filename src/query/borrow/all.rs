@@ -581,6 +581,7 @@ where
 
                 let data = unsafe { component.data_mut() };
                 data.epoch.bump(epoch);
+                data.wakers.wake();
 
                 FetchBorrowAllComponent {
                     ptr: data.ptr,
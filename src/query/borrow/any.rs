@@ -324,6 +324,7 @@ where
 
         let data = unsafe { component.data_mut() };
         data.epoch.bump(epoch);
+        data.wakers.wake();
 
         FetchBorrowAnyWrite {
             ptr: data.ptr,
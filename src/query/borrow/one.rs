@@ -282,6 +282,7 @@ where
         let data = unsafe { component.data_mut() };
 
         data.epoch.bump(epoch);
+        data.wakers.wake();
 
         FetchBorrowOneWrite {
             ptr: data.ptr,
@@ -2,6 +2,7 @@ use core::any::TypeId;
 
 use crate::{
     archetype::Archetype, component::ComponentInfo, epoch::EpochId, system::QueryArg, type_id,
+    world::World,
 };
 
 use super::{
@@ -235,6 +236,16 @@ where
     fn new() -> Not<T::Query> {
         Not(T::new())
     }
+
+    #[inline]
+    fn before(&mut self, world: &World) {
+        self.0.before(world);
+    }
+
+    #[inline]
+    fn after(&mut self, world: &World) {
+        self.0.after(world);
+    }
 }
 
 unsafe impl<T> Query for Not<T>
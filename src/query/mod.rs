@@ -50,8 +50,8 @@ pub use self::{
     fetch::{BatchFetch, Fetch, UnitFetch, VerifyFetch},
     filter::{FilteredFetch, Not, With, Without},
     modified::{
-        Modified, ModifiedFetchAlt, ModifiedFetchCopied, ModifiedFetchRead, ModifiedFetchWith,
-        ModifiedFetchWrite,
+        Modified, ModifiedBatch, ModifiedBatchMut, ModifiedFetchAlt, ModifiedFetchCopied,
+        ModifiedFetchRead, ModifiedFetchWith, ModifiedFetchWrite, ModifiedMask, ModifiedMaskIter,
     },
     read::{FetchRead, Read},
     with_epoch::{EpochOf, FetchEpoch, WithEpoch},
@@ -0,0 +1,295 @@
+use smallvec::SmallVec;
+
+use crate::epoch::EpochId;
+
+/// Number of rows covered by one word of [`ModifiedMask`].
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Bitmask of modified components in a batch.
+///
+/// Row `idx` is set if component in that row of the batch
+/// was modified after the epoch specified in the [`Modified`](super::Modified) query.
+///
+/// Mask is computed once per batch and stored as `u64` words.
+/// Row `idx` is bit `idx % 64` of word `idx / 64`.
+/// Bits past the last row are always unset.
+/// Masks for batches up to 256 rows do not allocate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModifiedMask {
+    words: SmallVec<[u64; 4]>,
+    len: usize,
+}
+
+impl ModifiedMask {
+    /// Builds mask over epochs of the components in the batch.
+    #[inline]
+    pub(crate) fn new(epochs: &[EpochId], after_epoch: EpochId) -> Self {
+        let words = epochs
+            .chunks(WORD_BITS)
+            .map(|chunk| {
+                chunk.iter().enumerate().fold(0u64, |word, (bit, epoch)| {
+                    word | (u64::from(epoch.after(after_epoch)) << bit)
+                })
+            })
+            .collect();
+
+        ModifiedMask {
+            words,
+            len: epochs.len(),
+        }
+    }
+
+    /// Returns number of rows covered by the mask.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if mask covers no rows.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns words of the mask.
+    ///
+    /// Row `idx` is bit `idx % 64` of word `idx / 64`.
+    #[inline]
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Returns `true` if component in row `idx` was modified.
+    ///
+    /// # Panics
+    ///
+    /// If `idx` is out of bounds.
+    #[inline]
+    pub fn is_modified(&self, idx: usize) -> bool {
+        assert!(idx < self.len, "Row index is out of bounds");
+        self.words[idx / WORD_BITS] & (1 << (idx % WORD_BITS)) != 0
+    }
+
+    /// Returns `true` if any row in the mask was modified.
+    #[inline]
+    pub fn any(&self) -> bool {
+        self.words.iter().any(|word| *word != 0)
+    }
+
+    /// Returns number of modified rows.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns iterator over indices of modified rows.
+    #[inline]
+    pub fn iter(&self) -> ModifiedMaskIter<'_> {
+        ModifiedMaskIter {
+            words: &self.words,
+            word: 0,
+            base: 0,
+            next_base: 0,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a ModifiedMask {
+    type Item = usize;
+    type IntoIter = ModifiedMaskIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> ModifiedMaskIter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over indices of modified rows in [`ModifiedMask`].
+#[derive(Clone)]
+pub struct ModifiedMaskIter<'a> {
+    /// Words not yet loaded.
+    words: &'a [u64],
+
+    /// Remaining bits of the loaded word.
+    word: u64,
+
+    /// Index of the first row of the loaded word.
+    base: usize,
+
+    /// Index of the first row of the next word.
+    next_base: usize,
+}
+
+impl Iterator for ModifiedMaskIter<'_> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        while self.word == 0 {
+            let (&word, rest) = self.words.split_first()?;
+            self.words = rest;
+            self.word = word;
+            self.base = self.next_base;
+            self.next_base += WORD_BITS;
+        }
+
+        let bit = self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+        Some(self.base + bit)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.word.count_ones() as usize
+            + self
+                .words
+                .iter()
+                .map(|word| word.count_ones() as usize)
+                .sum::<usize>();
+        (count, Some(count))
+    }
+}
+
+impl ExactSizeIterator for ModifiedMaskIter<'_> {}
+
+/// Batch item of the [`Modified<&T>`](super::Modified) query.
+///
+/// Contains components of all rows in the batch
+/// and a mask of rows that were modified after the epoch.
+pub struct ModifiedBatch<'a, T> {
+    items: &'a [T],
+    mask: ModifiedMask,
+}
+
+impl<'a, T> ModifiedBatch<'a, T> {
+    #[inline]
+    pub(crate) fn new(items: &'a [T], mask: ModifiedMask) -> Self {
+        debug_assert_eq!(items.len(), mask.len());
+        ModifiedBatch { items, mask }
+    }
+
+    /// Returns components of all rows in the batch.
+    #[inline]
+    pub fn items(&self) -> &'a [T] {
+        self.items
+    }
+
+    /// Returns mask of modified rows.
+    #[inline]
+    pub fn mask(&self) -> &ModifiedMask {
+        &self.mask
+    }
+
+    /// Returns iterator over modified components in the batch.
+    #[inline]
+    pub fn iter_modified(&self) -> impl Iterator<Item = &'a T> + '_ {
+        let items = self.items;
+        self.mask.iter().map(move |idx| &items[idx])
+    }
+}
+
+/// Batch item of the [`Modified<&mut T>`](super::Modified) query.
+///
+/// Contains components of all rows in the batch
+/// and a mask of rows that were modified after the epoch.
+///
+/// Rows are marked as modified in the current epoch only when
+/// they are accessed mutably through the batch.
+/// Such rows are set in the mask from then on.
+pub struct ModifiedBatchMut<'a, T> {
+    items: &'a mut [T],
+    epochs: &'a mut [EpochId],
+    epoch: EpochId,
+    after_epoch: EpochId,
+}
+
+impl<'a, T> ModifiedBatchMut<'a, T> {
+    #[inline]
+    pub(crate) fn new(
+        items: &'a mut [T],
+        epochs: &'a mut [EpochId],
+        epoch: EpochId,
+        after_epoch: EpochId,
+    ) -> Self {
+        debug_assert_eq!(items.len(), epochs.len());
+        ModifiedBatchMut {
+            items,
+            epochs,
+            epoch,
+            after_epoch,
+        }
+    }
+
+    /// Returns components of all rows in the batch.
+    #[inline]
+    pub fn items(&self) -> &[T] {
+        self.items
+    }
+
+    /// Returns mutable components of all rows in the batch.
+    ///
+    /// Marks all rows as modified.
+    #[inline]
+    pub fn items_mut(&mut self) -> &mut [T] {
+        for epoch in self.epochs.iter_mut() {
+            epoch.bump_again(self.epoch);
+        }
+        self.items
+    }
+
+    /// Returns mutable components of all rows in the batch.
+    ///
+    /// Marks all rows as modified.
+    #[inline]
+    pub fn into_items(self) -> &'a mut [T] {
+        for epoch in self.epochs.iter_mut() {
+            epoch.bump_again(self.epoch);
+        }
+        self.items
+    }
+
+    /// Returns mutable component in row `idx`.
+    ///
+    /// Marks only this row as modified.
+    ///
+    /// # Panics
+    ///
+    /// If `idx` is out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, idx: usize) -> &mut T {
+        self.epochs[idx].bump_again(self.epoch);
+        &mut self.items[idx]
+    }
+
+    /// Returns mask of modified rows.
+    ///
+    /// Mask is computed on each call and includes rows
+    /// accessed mutably through the batch so far.
+    #[inline]
+    pub fn mask(&self) -> ModifiedMask {
+        ModifiedMask::new(self.epochs, self.after_epoch)
+    }
+
+    /// Returns iterator over modified components in the batch.
+    ///
+    /// Marks only rows set in the mask as modified.
+    #[inline]
+    pub fn iter_modified_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        let epoch = self.epoch;
+        let after_epoch = self.after_epoch;
+        self.items
+            .iter_mut()
+            .zip(self.epochs.iter_mut())
+            .filter_map(move |(item, item_epoch)| {
+                if item_epoch.after(after_epoch) {
+                    item_epoch.bump_again(epoch);
+                    Some(item)
+                } else {
+                    None
+                }
+            })
+    }
+}
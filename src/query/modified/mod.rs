@@ -1,4 +1,5 @@
 mod alt;
+mod batch;
 // mod any_of;
 mod copied;
 mod read;
@@ -8,8 +9,12 @@ mod write;
 use crate::epoch::EpochId;
 
 pub use self::{
-    alt::ModifiedFetchAlt, copied::ModifiedFetchCopied, read::ModifiedFetchRead,
-    with::ModifiedFetchWith, write::ModifiedFetchWrite,
+    alt::ModifiedFetchAlt,
+    batch::{ModifiedBatch, ModifiedBatchMut, ModifiedMask, ModifiedMaskIter},
+    copied::ModifiedFetchCopied,
+    read::ModifiedFetchRead,
+    with::ModifiedFetchWith,
+    write::ModifiedFetchWrite,
 };
 
 /// Query over modified component.
@@ -17,11 +22,18 @@ pub use self::{
 /// Should be used as either [`Modified<&T>`], [`Modified<&mut T>`]
 /// or [`Modified<Alt<T>>`].
 ///
+/// Relation queries can be wrapped too, e.g. `Modified<Relates<&R>>` or `Modified<Related<With<R>>>`,
+/// to filter out entities with unmodified relations.
+///
 /// This is tracking query that uses epoch lower bound to filter out entities with unmodified components.
+///
+/// When iterated in batches, `Modified<&T>` and `Modified<&mut T>` do not filter entities.
+/// Instead each batch contains components of all rows
+/// along with [`ModifiedMask`] of rows modified after the epoch.
 #[derive(Clone, Copy, Debug)]
 pub struct Modified<T> {
-    after_epoch: EpochId,
-    query: T,
+    pub(crate) after_epoch: EpochId,
+    pub(crate) query: T,
 }
 
 impl<T> Modified<T> {
@@ -37,6 +49,15 @@ impl<T> Modified<T> {
         }
     }
 
+    /// Creates new `Modified` query that wraps provided query.
+    /// Uses provided `after_epoch` id to skip components that are last modified not after this epoch.
+    ///
+    /// Useful for stateful queries that can't be default-constructed,
+    /// like [`RelatesTo`](crate::relation::RelatesTo).
+    pub fn with_query(after_epoch: EpochId, query: T) -> Self {
+        Modified { after_epoch, query }
+    }
+
     /// Epoch id threshold for this query.
     pub fn after_epoch(&self) -> EpochId {
         self.after_epoch
@@ -5,15 +5,15 @@ use crate::{
     component::ComponentInfo,
     epoch::EpochId,
     query::{
-        option::OptionQuery, read::Read, Access, AsQuery, Fetch, ImmutableQuery, IntoQuery, Query,
-        SendQuery, WriteAlias,
+        option::OptionQuery, read::Read, Access, AsQuery, BatchFetch, Fetch, ImmutableQuery,
+        IntoQuery, Query, SendQuery, WriteAlias,
     },
     system::QueryArg,
     type_id,
     world::World,
 };
 
-use super::Modified;
+use super::{Modified, ModifiedBatch, ModifiedMask};
 
 /// [`Fetch`] type for the [`Modified<&T>`] query.
 pub struct ModifiedFetchRead<'a, T> {
@@ -59,6 +59,31 @@ where
     }
 }
 
+unsafe impl<'a, T> BatchFetch<'a> for ModifiedFetchRead<'a, T>
+where
+    T: 'a,
+{
+    type Batch = ModifiedBatch<'a, T>;
+
+    #[inline]
+    unsafe fn get_batch(&mut self, start: u32, end: u32) -> ModifiedBatch<'a, T> {
+        debug_assert!(end >= start);
+
+        let count = end - start;
+        let items = unsafe {
+            core::slice::from_raw_parts(self.ptr.as_ptr().add(start as usize), count as usize)
+        };
+        let epochs = unsafe {
+            core::slice::from_raw_parts(
+                self.entity_epochs.as_ptr().add(start as usize),
+                count as usize,
+            )
+        };
+
+        ModifiedBatch::new(items, ModifiedMask::new(epochs, self.after_epoch))
+    }
+}
+
 impl<T> AsQuery for Modified<&T>
 where
     T: 'static,
@@ -5,15 +5,15 @@ use crate::{
     component::ComponentInfo,
     epoch::EpochId,
     query::{
-        option::OptionQuery, write::Write, Access, AsQuery, Fetch, IntoQuery, Query, SendQuery,
-        WriteAlias,
+        option::OptionQuery, write::Write, Access, AsQuery, BatchFetch, Fetch, IntoQuery, Query,
+        SendQuery, WriteAlias,
     },
     system::QueryArg,
     type_id,
     world::World,
 };
 
-use super::Modified;
+use super::{Modified, ModifiedBatchMut};
 
 /// [`Fetch`] type for the [`Modified<&mut T>`] query.
 pub struct ModifiedFetchWrite<'a, T> {
@@ -70,6 +70,32 @@ where
     }
 }
 
+unsafe impl<'a, T> BatchFetch<'a> for ModifiedFetchWrite<'a, T>
+where
+    T: 'a,
+{
+    type Batch = ModifiedBatchMut<'a, T>;
+
+    #[inline]
+    unsafe fn get_batch(&mut self, start: u32, end: u32) -> ModifiedBatchMut<'a, T> {
+        debug_assert!(end >= start);
+
+        let count = end - start;
+        let epochs = unsafe {
+            core::slice::from_raw_parts_mut(
+                self.entity_epochs.as_ptr().add(start as usize),
+                count as usize,
+            )
+        };
+
+        let items = unsafe {
+            core::slice::from_raw_parts_mut(self.ptr.as_ptr().add(start as usize), count as usize)
+        };
+
+        ModifiedBatchMut::new(items, epochs, self.epoch, self.after_epoch)
+    }
+}
+
 impl<T> AsQuery for Modified<&mut T>
 where
     T: 'static,
@@ -161,6 +187,7 @@ where
 
         debug_assert!(data.epoch.after(self.after_epoch));
         data.epoch.bump(epoch);
+        data.wakers.wake();
 
         ModifiedFetchWrite {
             after_epoch: self.after_epoch,
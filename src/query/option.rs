@@ -2,7 +2,7 @@ use core::any::TypeId;
 
 use crate::{
     archetype::Archetype, component::ComponentInfo, entity::EntityId, epoch::EpochId,
-    system::QueryArg,
+    system::QueryArg, world::World,
 };
 
 use super::{
@@ -65,7 +65,8 @@ where
 
     /// Returns fetched item at specified index.
     unsafe fn get_batch(&mut self, start: u32, end: u32) -> Option<T::Batch> {
-        self.as_mut().map(|fetch| unsafe { fetch.get_batch(start, end) })
+        self.as_mut()
+            .map(|fetch| unsafe { fetch.get_batch(start, end) })
     }
 }
 
@@ -123,6 +124,16 @@ where
     fn new() -> Self {
         OptionQuery(T::new())
     }
+
+    #[inline]
+    fn before(&mut self, world: &World) {
+        self.0.before(world);
+    }
+
+    #[inline]
+    fn after(&mut self, world: &World) {
+        self.0.after(world);
+    }
 }
 
 unsafe impl<T> Query for OptionQuery<T>
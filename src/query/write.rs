@@ -62,6 +62,17 @@ where
         debug_assert!(end >= start);
 
         let count = end - start;
+
+        let entity_epochs = unsafe {
+            core::slice::from_raw_parts_mut(
+                self.entity_epochs.as_ptr().add(start as usize),
+                count as usize,
+            )
+        };
+        for entity_epoch in entity_epochs {
+            entity_epoch.bump(self.epoch);
+        }
+
         unsafe {
             core::slice::from_raw_parts_mut(self.ptr.as_ptr().add(start as usize), count as usize)
         }
@@ -167,6 +178,7 @@ where
 
         let data = unsafe { component.data_mut() };
         data.epoch.bump(epoch);
+        data.wakers.wake();
 
         FetchWrite {
             ptr: data.ptr.cast(),
@@ -1,5 +1,8 @@
 use alloc::{vec, vec::Vec};
-use core::{marker::PhantomData, mem::ManuallyDrop};
+use core::{
+    marker::PhantomData,
+    mem::{replace, ManuallyDrop},
+};
 use smallvec::SmallVec;
 
 use crate::{
@@ -8,10 +11,12 @@ use crate::{
     entity::EntityId,
 };
 
-use super::Relation;
+use super::{Relation, RelationEvents};
 
 pub(crate) union OriginComponent<R: Relation> {
-    exclusive: ManuallyDrop<(EntityId, R)>,
+    /// Exclusive relation is `None` only after it was removed
+    /// and before component is dropped.
+    exclusive: ManuallyDrop<Option<(EntityId, R)>>,
     non_exclusive: ManuallyDrop<Vec<(EntityId, R)>>,
 }
 
@@ -39,7 +44,7 @@ where
                 non_exclusive: ManuallyDrop::new(vec![(target, relation)]),
             },
             true => OriginComponent {
-                exclusive: ManuallyDrop::new((target, relation)),
+                exclusive: ManuallyDrop::new(Some((target, relation))),
             },
         }
     }
@@ -56,7 +61,7 @@ where
             false => {
                 let relations = unsafe { &mut *self.non_exclusive };
                 for r in relations.iter_mut() {
-                    if r.0 == target {
+                    if r.0 == target && r.1.same_kind(&relation) {
                         let call_on_drop = R::on_replace(
                             &mut r.1,
                             &relation,
@@ -65,11 +70,11 @@ where
                             target,
                             encoder.reborrow(),
                         );
+
+                        let old = replace(&mut r.1, relation);
                         if call_on_drop {
-                            R::on_drop(r.1, origin, r.0, encoder.reborrow());
+                            R::on_drop(old, origin, r.0, encoder.reborrow());
                         }
-
-                        r.1 = relation;
                         return false;
                     }
                 }
@@ -77,12 +82,18 @@ where
                 true
             }
             true => {
-                let r = unsafe { &mut *self.exclusive };
+                let exclusive = unsafe { &mut *self.exclusive };
+                let Some(r) = exclusive else {
+                    *exclusive = Some((target, relation));
+                    return true;
+                };
 
                 let call_on_drop =
                     R::on_replace(&mut r.1, &relation, origin, r.0, target, encoder.reborrow());
+
+                let old = replace(&mut r.1, relation);
                 if call_on_drop {
-                    R::on_drop(r.1, origin, r.0, encoder.reborrow());
+                    R::on_drop(old, origin, r.0, encoder.reborrow());
                 }
 
                 if r.0 != target {
@@ -106,20 +117,39 @@ where
         }
     }
 
+    /// Called when relation value is replaced on the other side of symmetric relation.
+    /// This won't trigger any hooks.
+    pub fn set_relation(&mut self, target: EntityId, relation: R) {
+        debug_assert!(R::SYMMETRIC);
+        if let Some(r) = self
+            .targets_mut()
+            .iter_mut()
+            .find(|r| r.0 == target && r.1.same_kind(&relation))
+        {
+            r.1 = relation;
+        }
+    }
+
     /// Called when relation is removed from an entity.
+    /// Removes first relation with the target that matches the predicate.
     /// This won't trigger any hooks.
     pub fn remove_relation(
         &mut self,
         origin: EntityId,
         target: EntityId,
+        pred: impl Fn(&R) -> bool,
         mut encoder: LocalActionEncoder,
     ) -> Option<R> {
         match R::EXCLUSIVE {
             false => {
                 let relations = unsafe { &mut *self.non_exclusive };
                 for idx in 0..relations.len() {
-                    if relations[idx].0 == target {
-                        let r = relations.swap_remove(idx);
+                    if relations[idx].0 == target && pred(&relations[idx].1) {
+                        let r = if R::ORDERED {
+                            relations.remove(idx)
+                        } else {
+                            relations.swap_remove(idx)
+                        };
                         if relations.is_empty() {
                             encoder.drop::<Self>(origin);
                         }
@@ -129,12 +159,16 @@ where
                 None
             }
             true => {
-                let r = unsafe { &mut *self.exclusive };
-                if r.0 == target {
-                    encoder.drop::<Self>(origin);
-                    return Some(r.1);
+                let relation = unsafe { &mut *self.exclusive };
+                match relation {
+                    Some(r) if r.0 == target && pred(&r.1) => {
+                        // Take relation out so that dropping component won't trigger hooks.
+                        let r = relation.take().unwrap();
+                        encoder.drop::<Self>(origin);
+                        Some(r.1)
+                    }
+                    _ => None,
                 }
-                None
             }
         }
     }
@@ -165,13 +199,7 @@ where
                     };
 
                     let targets = unsafe { &mut *comp.non_exclusive };
-
-                    for idx in 0..targets.len() {
-                        if targets[idx].0 == target {
-                            targets.swap_remove(idx);
-                            break;
-                        }
-                    }
+                    targets.retain(|r| r.0 != target);
 
                     if targets.is_empty() {
                         if R::OWNED {
@@ -189,7 +217,7 @@ where
     pub fn targets(&self) -> &[(EntityId, R)] {
         match R::EXCLUSIVE {
             false => unsafe { &self.non_exclusive },
-            true => core::slice::from_ref(unsafe { &*self.exclusive }),
+            true => unsafe { self.exclusive.as_slice() },
         }
     }
 
@@ -197,7 +225,7 @@ where
     pub fn targets_mut(&mut self) -> &mut [(EntityId, R)] {
         match R::EXCLUSIVE {
             false => unsafe { &mut self.non_exclusive },
-            true => core::slice::from_mut(unsafe { &mut *self.exclusive }),
+            true => unsafe { self.exclusive.as_mut_slice() },
         }
     }
 }
@@ -224,6 +252,25 @@ where
                     encoder.reborrow(),
                 );
             }
+
+            // Component is dropped with despawned origin.
+            if !encoder.is_alive(origin) {
+                // Relations with already despawned targets were recorded by them.
+                let targets = self
+                    .targets()
+                    .iter()
+                    .filter(|r| encoder.is_alive(r.0))
+                    .map(|r| (origin, r.0))
+                    .collect::<SmallVec<[_; 8]>>();
+                RelationEvents::<R>::record_removed(targets, encoder.reborrow());
+
+                let policy = if R::SYMMETRIC {
+                    R::ON_TARGET_DESPAWN
+                } else {
+                    R::ON_ORIGIN_DESPAWN
+                };
+                policy.apply(self.targets().iter().map(|r| r.0), encoder);
+            }
         }
     }
 
@@ -266,22 +313,44 @@ where
 
     pub fn add(&mut self, origin: EntityId, relation: R) {
         debug_assert!(!R::SYMMETRIC);
-        debug_assert!(self.origins.iter().all(|r| r.0 != origin));
+        debug_assert!(self
+            .origins
+            .iter()
+            .all(|r| r.0 != origin || !r.1.same_kind(&relation)));
         self.origins.push((origin, relation));
     }
 
+    /// Called when relation value is replaced on origin entity.
+    /// This won't trigger any hooks.
+    pub fn set_relation(&mut self, origin: EntityId, relation: R) {
+        debug_assert!(!R::SYMMETRIC);
+        if let Some(r) = self
+            .origins
+            .iter_mut()
+            .find(|r| r.0 == origin && r.1.same_kind(&relation))
+        {
+            r.1 = relation;
+        }
+    }
+
     /// Called when relation is removed from an entity.
+    /// Removes first relation with the origin that matches the predicate.
     /// This won't trigger any hooks.
     pub fn remove_relation(
         &mut self,
         origin: EntityId,
         target: EntityId,
+        pred: impl Fn(&R) -> bool,
         mut encoder: LocalActionEncoder,
     ) {
         debug_assert!(!R::SYMMETRIC);
         for idx in 0..self.origins.len() {
-            if self.origins[idx].0 == origin {
-                self.origins.swap_remove(idx);
+            if self.origins[idx].0 == origin && pred(&self.origins[idx].1) {
+                if R::ORDERED {
+                    self.origins.remove(idx);
+                } else {
+                    self.origins.swap_remove(idx);
+                }
                 if self.origins.is_empty() {
                     encoder.drop::<Self>(target);
                 }
@@ -303,18 +372,13 @@ where
         encoder.closure(move |world| {
             for target in targets {
                 let Ok(mut target) = world.entity(target) else {
-                    return;
+                    continue;
                 };
                 let Some(comp) = target.get_mut::<&mut Self>() else {
-                    return;
+                    continue;
                 };
 
-                for idx in 0..comp.origins.len() {
-                    if comp.origins[idx].0 == origin {
-                        comp.origins.swap_remove(idx);
-                        break;
-                    }
-                }
+                comp.origins.retain(|r| r.0 != origin);
 
                 if comp.origins.is_empty() {
                     target.drop::<Self>();
@@ -350,6 +414,20 @@ where
                 target,
                 encoder.reborrow(),
             );
+
+            // Component is dropped with despawned target.
+            if !encoder.is_alive(target) {
+                // Relations with already despawned origins were recorded by them.
+                let origins = self
+                    .origins
+                    .iter()
+                    .filter(|r| encoder.is_alive(r.0))
+                    .map(|r| (r.0, target))
+                    .collect::<SmallVec<[_; 8]>>();
+                RelationEvents::<R>::record_removed(origins, encoder.reborrow());
+
+                R::ON_TARGET_DESPAWN.apply(self.origins.iter().map(|r| r.0), encoder);
+            }
         }
     }
 
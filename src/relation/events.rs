@@ -0,0 +1,119 @@
+use core::{marker::PhantomData, ops::Deref};
+
+use smallvec::SmallVec;
+
+use crate::{action::LocalActionEncoder, entity::EntityId, event::Events};
+
+use super::Relation;
+
+/// Kind of the [`RelationEvent`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RelationEventKind {
+    /// Relation between origin and target was inserted.
+    ///
+    /// Replacing value of existing relation does not produce this event.
+    Inserted,
+
+    /// Relation between origin and target was removed.
+    ///
+    /// Produced when relation is removed or dropped explicitly,
+    /// replaced by exclusive relation with another target,
+    /// or when either entity is despawned.
+    Removed,
+}
+
+/// Event of relation insertion or removal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RelationEvent {
+    /// Kind of the event.
+    pub kind: RelationEventKind,
+
+    /// Origin of the relation.
+    pub origin: EntityId,
+
+    /// Target of the relation.
+    pub target: EntityId,
+}
+
+/// Log of insertion and removal events of relation `R`.
+///
+/// Log is stored as a resource and is filled only after
+/// [`World::track_relation_events`](crate::world::World::track_relation_events) is called.
+/// Systems may access it with [`Res`](crate::resources::Res) and consume events
+/// with [`Events::read`] through [`Deref`].
+///
+/// Events are never removed automatically.
+/// Call [`RelationEvents::clear`] periodically, e.g. once per frame
+/// after all consumers have read events.
+pub struct RelationEvents<R> {
+    log: Events<RelationEvent>,
+    marker: PhantomData<fn() -> R>,
+}
+
+impl<R> Default for RelationEvents<R> {
+    #[inline]
+    fn default() -> Self {
+        RelationEvents::new()
+    }
+}
+
+impl<R> Deref for RelationEvents<R> {
+    type Target = Events<RelationEvent>;
+
+    #[inline]
+    fn deref(&self) -> &Events<RelationEvent> {
+        &self.log
+    }
+}
+
+impl<R> RelationEvents<R> {
+    /// Returns new empty event log.
+    #[inline]
+    pub const fn new() -> Self {
+        RelationEvents {
+            log: Events::new(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Removes all events from the log.
+    ///
+    /// Cursors remain valid.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.log.clear();
+    }
+
+    #[inline]
+    pub(crate) fn push(&mut self, kind: RelationEventKind, origin: EntityId, target: EntityId) {
+        self.log.send(RelationEvent {
+            kind,
+            origin,
+            target,
+        });
+    }
+}
+
+impl<R> RelationEvents<R>
+where
+    R: Relation,
+{
+    /// Records events of relations removed with despawned entity
+    /// when log is present.
+    pub(crate) fn record_removed(
+        pairs: SmallVec<[(EntityId, EntityId); 8]>,
+        mut encoder: LocalActionEncoder,
+    ) {
+        if pairs.is_empty() {
+            return;
+        }
+
+        encoder.closure(move |world| {
+            if let Some(mut events) = world.get_resource_mut::<Self>() {
+                for (origin, target) in pairs {
+                    events.push(RelationEventKind::Removed, origin, target);
+                }
+            }
+        });
+    }
+}
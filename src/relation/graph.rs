@@ -0,0 +1,319 @@
+//! Graph algorithms over relations.
+//!
+//! Relation of one type forms a directed graph where entities are nodes
+//! and each relation is an edge from origin to target.
+//! Symmetric relations form undirected graphs.
+//!
+//! Functions in this module read relation components of the [`World`] directly
+//! and borrow them at runtime for the duration of the call.
+
+use core::fmt;
+
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use hashbrown::HashMap;
+
+use crate::{
+    entity::{Entity, EntityId},
+    query::{Entities, With},
+    view::ViewValue,
+    world::World,
+};
+
+use super::{Related, Relates, Relation};
+
+/// Snapshot of the relation graph with dense node indices.
+struct Graph {
+    nodes: Vec<EntityId>,
+    index: HashMap<EntityId, usize>,
+
+    /// Edges from origin to targets.
+    targets: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    fn new<R>(world: &World) -> Self
+    where
+        R: Relation,
+    {
+        let mut graph = Graph {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            targets: Vec::new(),
+        };
+
+        let view = ViewValue::new_ref(world, (Entities, Relates::<With<R>>), ());
+        for (origin, targets) in view.iter() {
+            let origin = graph.node(origin.id());
+            for target in targets {
+                let target = graph.node(target.id());
+                graph.targets[origin].push(target);
+            }
+        }
+
+        let view = ViewValue::new_ref(world, (Entities, Related::<With<R>>), ());
+        for (target, _) in view.iter() {
+            graph.node(target.id());
+        }
+
+        graph
+    }
+
+    fn node(&mut self, entity: EntityId) -> usize {
+        *self.index.entry(entity).or_insert_with(|| {
+            self.nodes.push(entity);
+            self.targets.push(Vec::new());
+            self.nodes.len() - 1
+        })
+    }
+
+    /// Finds cycle among nodes that are not marked as done.
+    fn find_cycle(&self, done: &[bool]) -> Option<Vec<EntityId>> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum State {
+            New,
+            OnStack,
+            Visited,
+        }
+
+        let mut state = vec![State::New; self.nodes.len()];
+
+        for start in 0..self.nodes.len() {
+            if done[start] || state[start] != State::New {
+                continue;
+            }
+
+            // Stack of nodes with index of the next edge to follow.
+            let mut stack = vec![(start, 0)];
+            state[start] = State::OnStack;
+
+            while let Some((node, edge)) = stack.last_mut() {
+                let node = *node;
+                let Some(&next) = self.targets[node].get(*edge) else {
+                    state[node] = State::Visited;
+                    stack.pop();
+                    continue;
+                };
+                *edge += 1;
+
+                if done[next] {
+                    continue;
+                }
+
+                match state[next] {
+                    State::New => {
+                        state[next] = State::OnStack;
+                        stack.push((next, 0));
+                    }
+                    State::OnStack => {
+                        let from = stack.iter().position(|&(n, _)| n == next).unwrap();
+                        return Some(stack[from..].iter().map(|&(n, _)| self.nodes[n]).collect());
+                    }
+                    State::Visited => {}
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns connected components of the relation graph.
+///
+/// Direction of relations is ignored.
+/// Only entities that are origin or target of at least one relation are included.
+pub fn connected_components<R>(world: &World) -> Vec<Vec<EntityId>>
+where
+    R: Relation,
+{
+    let graph = Graph::new::<R>(world);
+
+    // Undirected adjacency.
+    let mut adjacent = graph.targets.clone();
+    for (origin, targets) in graph.targets.iter().enumerate() {
+        for &target in targets {
+            adjacent[target].push(origin);
+        }
+    }
+
+    let mut visited = vec![false; graph.nodes.len()];
+    let mut components = Vec::new();
+    let mut stack = Vec::new();
+
+    for start in 0..graph.nodes.len() {
+        if visited[start] {
+            continue;
+        }
+
+        let mut component = Vec::new();
+        visited[start] = true;
+        stack.push(start);
+
+        while let Some(node) = stack.pop() {
+            component.push(graph.nodes[node]);
+            for &next in &adjacent[node] {
+                if !visited[next] {
+                    visited[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        components.push(component);
+    }
+
+    components
+}
+
+/// Returns shortest path from `from` to `to` following relations from origins to targets.
+///
+/// Path includes both ends.
+/// Returns `None` if `to` is not reachable from `from`.
+pub fn shortest_path<R>(world: &World, from: impl Entity, to: impl Entity) -> Option<Vec<EntityId>>
+where
+    R: Relation,
+{
+    let (from, to) = (from.id(), to.id());
+    if from == to {
+        return Some(vec![from]);
+    }
+
+    let view = ViewValue::new_ref(world, Relates::<With<R>>, ());
+
+    // Maps visited entity to the previous entity in the path.
+    let mut previous = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    previous.insert(from, from);
+    queue.push_back(from);
+
+    while let Some(entity) = queue.pop_front() {
+        let Ok(targets) = view.try_get(entity) else {
+            continue;
+        };
+
+        for target in targets {
+            let target = target.id();
+            if previous.contains_key(&target) {
+                continue;
+            }
+            previous.insert(target, entity);
+
+            if target == to {
+                let mut path = vec![to];
+                let mut entity = entity;
+                while entity != from {
+                    path.push(entity);
+                    entity = previous[&entity];
+                }
+                path.push(from);
+                path.reverse();
+                return Some(path);
+            }
+
+            queue.push_back(target);
+        }
+    }
+
+    None
+}
+
+/// Returns entities of the relation graph in topological order.
+///
+/// Targets come before their origins,
+/// e.g. parents before children for [`ChildOf`](super::ChildOf)
+/// and dependencies before dependents.
+///
+/// Fails with [`CycleError`] that contains one of the cycles if graph is not acyclic.
+/// Symmetric relations always have cycles.
+pub fn toposort<R>(world: &World) -> Result<Vec<EntityId>, CycleError>
+where
+    R: Relation,
+{
+    let graph = Graph::new::<R>(world);
+
+    // Number of unsorted targets of each node.
+    let mut pending = graph.targets.iter().map(Vec::len).collect::<Vec<_>>();
+
+    let mut origins = vec![Vec::new(); graph.nodes.len()];
+    for (origin, targets) in graph.targets.iter().enumerate() {
+        for &target in targets {
+            origins[target].push(origin);
+        }
+    }
+
+    let mut queue = (0..graph.nodes.len())
+        .filter(|&node| pending[node] == 0)
+        .collect::<VecDeque<_>>();
+
+    let mut done = vec![false; graph.nodes.len()];
+    let mut sorted = Vec::with_capacity(graph.nodes.len());
+
+    while let Some(node) = queue.pop_front() {
+        done[node] = true;
+        sorted.push(graph.nodes[node]);
+
+        for &origin in &origins[node] {
+            pending[origin] -= 1;
+            if pending[origin] == 0 {
+                queue.push_back(origin);
+            }
+        }
+    }
+
+    if sorted.len() < graph.nodes.len() {
+        let cycle = graph
+            .find_cycle(&done)
+            .expect("Unsorted nodes must contain a cycle");
+        return Err(CycleError { cycle });
+    }
+
+    Ok(sorted)
+}
+
+/// Returns a cycle in the relation graph if there is one.
+///
+/// Cycle is returned as a list of entities where each entity
+/// is origin of relation with the next one as target
+/// and the last one is origin of relation with the first one.
+///
+/// Useful to validate relations that are acyclic by design,
+/// like [`ChildOf`](super::ChildOf).
+pub fn find_cycle<R>(world: &World) -> Option<Vec<EntityId>>
+where
+    R: Relation,
+{
+    let graph = Graph::new::<R>(world);
+    graph.find_cycle(&vec![false; graph.nodes.len()])
+}
+
+/// Error returned by [`toposort`] when relation graph contains a cycle.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CycleError {
+    cycle: Vec<EntityId>,
+}
+
+impl CycleError {
+    /// Returns entities that form the cycle.
+    ///
+    /// See [`find_cycle`] for the order of entities.
+    #[inline]
+    pub fn cycle(&self) -> &[EntityId] {
+        &self.cycle
+    }
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Relation graph contains a cycle: ")?;
+        for (idx, entity) in self.cycle.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(" -> ")?;
+            }
+            fmt::Display::fmt(entity, f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CycleError {}
@@ -0,0 +1,497 @@
+//! Hierarchy traversal on top of exclusive relations.
+//!
+//! Exclusive relation like [`ChildOf`](super::ChildOf) forms a forest
+//! where origin is a child and target is its parent.
+//! This module provides helpers to walk such hierarchies.
+
+use core::{any::TypeId, fmt, marker::PhantomData};
+
+use alloc::{collections::VecDeque, vec::Vec};
+
+use hashbrown::HashSet;
+
+use crate::{
+    archetype::Archetype,
+    component::ComponentInfo,
+    entity::{EntityBound, EntityId},
+    epoch::EpochId,
+    query::{AsQuery, DefaultQuery, ImmutableQuery, IntoQuery, Query, SendQuery, With, WriteAlias},
+    system::QueryArg,
+    view::{BorrowState, ViewValue},
+    Access, NoSuchEntity,
+};
+
+use super::{ExclusiveRelation, Related, RelatesExclusive, RelationIter};
+
+marker_type! {
+    /// Query that fetches both parent and children of an entity
+    /// in a hierarchy formed by exclusive relation `R`.
+    ///
+    /// Yields optional parent and optional children of the entity.
+    /// Views with this query implement [`HierarchyView`].
+    pub struct HierarchyQuery<R>;
+}
+
+type HierarchyTuple<R> = (Option<RelatesExclusive<With<R>>>, Option<Related<With<R>>>);
+
+#[inline]
+fn hierarchy_tuple<R: ExclusiveRelation>() -> <HierarchyTuple<R> as AsQuery>::Query {
+    HierarchyTuple::<R>::default_query()
+}
+
+impl<R> AsQuery for HierarchyQuery<R>
+where
+    R: ExclusiveRelation,
+{
+    type Query = Self;
+}
+
+impl<R> IntoQuery for HierarchyQuery<R>
+where
+    R: ExclusiveRelation,
+{
+    #[inline]
+    fn into_query(self) -> Self {
+        self
+    }
+}
+
+impl<R> DefaultQuery for HierarchyQuery<R>
+where
+    R: ExclusiveRelation,
+{
+    #[inline]
+    fn default_query() -> Self {
+        HierarchyQuery
+    }
+}
+
+impl<R> QueryArg for HierarchyQuery<R>
+where
+    R: ExclusiveRelation,
+{
+    #[inline]
+    fn new() -> Self {
+        HierarchyQuery
+    }
+}
+
+unsafe impl<R> Query for HierarchyQuery<R>
+where
+    R: ExclusiveRelation,
+{
+    type Item<'a> = (Option<EntityBound<'a>>, Option<RelationIter<'a, R>>);
+    type Fetch<'a> = <<HierarchyTuple<R> as AsQuery>::Query as Query>::Fetch<'a>;
+
+    const MUTABLE: bool = false;
+
+    #[inline]
+    fn component_access(&self, comp: &ComponentInfo) -> Result<Option<Access>, WriteAlias> {
+        hierarchy_tuple::<R>().component_access(comp)
+    }
+
+    #[inline]
+    fn visit_archetype(&self, archetype: &Archetype) -> bool {
+        hierarchy_tuple::<R>().visit_archetype(archetype)
+    }
+
+    #[inline]
+    unsafe fn access_archetype(&self, archetype: &Archetype, f: impl FnMut(TypeId, Access)) {
+        unsafe { hierarchy_tuple::<R>().access_archetype(archetype, f) }
+    }
+
+    #[inline]
+    unsafe fn fetch<'a>(
+        &self,
+        arch_idx: u32,
+        archetype: &'a Archetype,
+        epoch: EpochId,
+    ) -> Self::Fetch<'a> {
+        unsafe { hierarchy_tuple::<R>().fetch(arch_idx, archetype, epoch) }
+    }
+
+    #[inline]
+    fn reserved_entity_item<'a>(&self, id: EntityId, idx: u32) -> Option<Self::Item<'a>> {
+        hierarchy_tuple::<R>().reserved_entity_item(id, idx)
+    }
+}
+
+unsafe impl<R> ImmutableQuery for HierarchyQuery<R> where R: ExclusiveRelation {}
+unsafe impl<R> SendQuery for HierarchyQuery<R> where R: ExclusiveRelation {}
+
+/// Read access to a hierarchy formed by exclusive relation `R`.
+///
+/// Hierarchy is expected to be acyclic.
+/// Use [`World::reparent`](crate::world::World::reparent) to change parent
+/// of an entity with a cycle check.
+///
+/// Cycles may still be created with [`World::insert_relation`](crate::world::World::insert_relation)
+/// or by symmetric relations.
+/// Traversals never visit an entity twice, so they terminate on cycles.
+/// Walks over ancestors detect cycles without allocating.
+pub trait HierarchyView<R: ExclusiveRelation> {
+    /// Returns parent of the entity.
+    ///
+    /// Returns `None` if entity has no parent or is not alive.
+    fn parent(&self, entity: EntityId) -> Option<EntityId>;
+
+    /// Returns iterator over children of the entity.
+    ///
+    /// Returns empty iterator if entity has no children or is not alive.
+    fn children(&self, entity: EntityId) -> RelationIter<'_, R>;
+
+    /// Returns iterator over ancestors of the entity,
+    /// starting from its parent and ending with the root.
+    #[inline]
+    fn ancestors(&self, entity: EntityId) -> Ancestors<&Self, R>
+    where
+        Self: Sized,
+    {
+        Ancestors::new(self, entity)
+    }
+
+    /// Returns iterator over descendants of the entity in depth-first pre-order.
+    ///
+    /// Entity itself is not yielded.
+    #[inline]
+    fn descendants_dfs(&self, entity: EntityId) -> DescendantsDfs<&Self, R>
+    where
+        Self: Sized,
+    {
+        DescendantsDfs::new(self, entity)
+    }
+
+    /// Returns iterator over descendants of the entity in breadth-first order.
+    ///
+    /// Entity itself is not yielded.
+    #[inline]
+    fn descendants_bfs(&self, entity: EntityId) -> DescendantsBfs<&Self, R>
+    where
+        Self: Sized,
+    {
+        DescendantsBfs::new(self, entity)
+    }
+
+    /// Returns number of ancestors of the entity.
+    ///
+    /// Roots have depth `0`.
+    #[inline]
+    fn depth(&self, entity: EntityId) -> usize
+    where
+        Self: Sized,
+    {
+        count_ancestors(self, entity)
+    }
+
+    /// Returns root of the hierarchy the entity belongs to.
+    ///
+    /// Returns the entity itself if it has no parent.
+    /// If ancestors form a cycle, returns the last ancestor before the cycle repeats.
+    #[inline]
+    fn root(&self, entity: EntityId) -> EntityId
+    where
+        Self: Sized,
+    {
+        self.ancestors(entity).last().unwrap_or(entity)
+    }
+
+    /// Returns `true` if `ancestor` is an ancestor of the `entity`.
+    ///
+    /// Entity is not an ancestor of itself.
+    #[inline]
+    fn is_ancestor(&self, ancestor: EntityId, entity: EntityId) -> bool
+    where
+        Self: Sized,
+    {
+        self.ancestors(entity).any(|e| e == ancestor)
+    }
+}
+
+impl<R, H> HierarchyView<R> for &H
+where
+    R: ExclusiveRelation,
+    H: HierarchyView<R>,
+{
+    #[inline]
+    fn parent(&self, entity: EntityId) -> Option<EntityId> {
+        H::parent(*self, entity)
+    }
+
+    #[inline]
+    fn children(&self, entity: EntityId) -> RelationIter<'_, R> {
+        H::children(*self, entity)
+    }
+}
+
+impl<R, F, B, E> HierarchyView<R> for ViewValue<'_, HierarchyQuery<R>, F, B, E>
+where
+    R: ExclusiveRelation,
+    F: ImmutableQuery,
+    B: BorrowState,
+{
+    #[inline]
+    fn parent(&self, entity: EntityId) -> Option<EntityId> {
+        let (parent, _) = self.try_get(entity).ok()?;
+        Some(parent?.id())
+    }
+
+    #[inline]
+    fn children(&self, entity: EntityId) -> RelationIter<'_, R> {
+        match self.try_get(entity) {
+            Ok((_, Some(children))) => children,
+            _ => RelationIter::new(&[]),
+        }
+    }
+}
+
+/// Returns number of distinct ancestors of the entity.
+///
+/// Uses Brent's algorithm to find a cycle in the chain of parents.
+/// If there is one, ancestors are entities before the chain repeats.
+fn count_ancestors<R, H>(hierarchy: &H, entity: EntityId) -> usize
+where
+    R: ExclusiveRelation,
+    H: HierarchyView<R>,
+{
+    let mut power = 1;
+    let mut cycle = 0;
+    let mut tortoise = entity;
+    let mut hare = entity;
+    let mut count = 0;
+
+    loop {
+        match hierarchy.parent(hare) {
+            None => return count,
+            Some(parent) => hare = parent,
+        }
+        count += 1;
+        cycle += 1;
+
+        if hare == tortoise {
+            break;
+        }
+
+        if cycle == power {
+            tortoise = hare;
+            power *= 2;
+            cycle = 0;
+        }
+    }
+
+    // Find where the cycle starts.
+    // Chain is cyclic, so every entity on it has a parent.
+    let parent = |entity| hierarchy.parent(entity).unwrap_or(entity);
+
+    let mut tortoise = entity;
+    let mut hare = entity;
+    for _ in 0..cycle {
+        hare = parent(hare);
+    }
+
+    let mut tail = 0;
+    while tortoise != hare {
+        tortoise = parent(tortoise);
+        hare = parent(hare);
+        tail += 1;
+    }
+
+    // Entity itself is either in the tail or the first entity of the cycle.
+    tail + cycle - 1
+}
+
+/// Iterator over ancestors of an entity.
+///
+/// Created by [`HierarchyView::ancestors`] and [`World::ancestors`](crate::world::World::ancestors).
+///
+/// Number of ancestors is counted on construction,
+/// so chain of parents is walked once more.
+pub struct Ancestors<H, R> {
+    hierarchy: H,
+    next: Option<EntityId>,
+    remaining: usize,
+    marker: PhantomData<fn() -> R>,
+}
+
+impl<H, R> Ancestors<H, R>
+where
+    H: HierarchyView<R>,
+    R: ExclusiveRelation,
+{
+    /// Returns iterator over ancestors of the entity.
+    #[inline]
+    pub fn new(hierarchy: H, entity: EntityId) -> Self {
+        let remaining = count_ancestors(&hierarchy, entity);
+        let next = hierarchy.parent(entity);
+        Ancestors {
+            hierarchy,
+            next,
+            remaining,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<H, R> Iterator for Ancestors<H, R>
+where
+    H: HierarchyView<R>,
+    R: ExclusiveRelation,
+{
+    type Item = EntityId;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<EntityId> {
+        if self.remaining == 0 {
+            // Stop before cycle repeats.
+            return None;
+        }
+        let entity = self.next?;
+        self.remaining -= 1;
+        self.next = self.hierarchy.parent(entity);
+        Some(entity)
+    }
+}
+
+impl<H, R> ExactSizeIterator for Ancestors<H, R>
+where
+    H: HierarchyView<R>,
+    R: ExclusiveRelation,
+{
+}
+
+/// Iterator over descendants of an entity in depth-first pre-order.
+///
+/// Created by [`HierarchyView::descendants_dfs`] and [`World::descendants_dfs`](crate::world::World::descendants_dfs).
+pub struct DescendantsDfs<H, R> {
+    hierarchy: H,
+    stack: Vec<EntityId>,
+    visited: HashSet<EntityId>,
+    marker: PhantomData<fn() -> R>,
+}
+
+impl<H, R> DescendantsDfs<H, R>
+where
+    H: HierarchyView<R>,
+    R: ExclusiveRelation,
+{
+    /// Returns iterator over descendants of the entity.
+    #[inline]
+    pub fn new(hierarchy: H, entity: EntityId) -> Self {
+        let stack = hierarchy.children(entity).rev().map(|e| e.id()).collect();
+        let mut visited = HashSet::new();
+        visited.insert(entity);
+        DescendantsDfs {
+            hierarchy,
+            stack,
+            visited,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<H, R> Iterator for DescendantsDfs<H, R>
+where
+    H: HierarchyView<R>,
+    R: ExclusiveRelation,
+{
+    type Item = EntityId;
+
+    #[inline]
+    fn next(&mut self) -> Option<EntityId> {
+        loop {
+            let entity = self.stack.pop()?;
+            if !self.visited.insert(entity) {
+                // Skip entities reached through a cycle.
+                continue;
+            }
+            self.stack
+                .extend(self.hierarchy.children(entity).rev().map(|e| e.id()));
+            return Some(entity);
+        }
+    }
+}
+
+/// Iterator over descendants of an entity in breadth-first order.
+///
+/// Created by [`HierarchyView::descendants_bfs`] and [`World::descendants_bfs`](crate::world::World::descendants_bfs).
+pub struct DescendantsBfs<H, R> {
+    hierarchy: H,
+    queue: VecDeque<EntityId>,
+    visited: HashSet<EntityId>,
+    marker: PhantomData<fn() -> R>,
+}
+
+impl<H, R> DescendantsBfs<H, R>
+where
+    H: HierarchyView<R>,
+    R: ExclusiveRelation,
+{
+    /// Returns iterator over descendants of the entity.
+    #[inline]
+    pub fn new(hierarchy: H, entity: EntityId) -> Self {
+        let queue = hierarchy.children(entity).map(|e| e.id()).collect();
+        let mut visited = HashSet::new();
+        visited.insert(entity);
+        DescendantsBfs {
+            hierarchy,
+            queue,
+            visited,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<H, R> Iterator for DescendantsBfs<H, R>
+where
+    H: HierarchyView<R>,
+    R: ExclusiveRelation,
+{
+    type Item = EntityId;
+
+    #[inline]
+    fn next(&mut self) -> Option<EntityId> {
+        loop {
+            let entity = self.queue.pop_front()?;
+            if !self.visited.insert(entity) {
+                // Skip entities reached through a cycle.
+                continue;
+            }
+            self.queue
+                .extend(self.hierarchy.children(entity).map(|e| e.id()));
+            return Some(entity);
+        }
+    }
+}
+
+/// Error that may be returned by [`World::reparent`](crate::world::World::reparent).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ReparentError {
+    /// Error returned when either entity is not found in the world.
+    NoSuchEntity,
+
+    /// New parent is the entity itself or one of its descendants.
+    Cycle,
+}
+
+impl From<NoSuchEntity> for ReparentError {
+    #[inline]
+    fn from(_: NoSuchEntity) -> Self {
+        ReparentError::NoSuchEntity
+    }
+}
+
+impl fmt::Display for ReparentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReparentError::NoSuchEntity => fmt::Display::fmt(&NoSuchEntity, f),
+            ReparentError::Cycle => f.write_str("Reparenting would create a cycle in hierarchy"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReparentError {}
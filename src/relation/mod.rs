@@ -8,21 +8,41 @@ pub use edict_proc::Relation;
 
 pub use self::{
     child_of::ChildOf,
+    events::{RelationEvent, RelationEventKind, RelationEvents},
+    hierarchy::{
+        Ancestors, DescendantsBfs, DescendantsDfs, HierarchyQuery, HierarchyView, ReparentError,
+    },
+    pair::Pair,
+    path::{Follow, FollowState, FollowView, Matches, PathState, RelationLinks, RelationPath},
+    policy::DespawnPolicy,
     query::{
-        FetchFilterRelatedBy, FetchRelatedRead, FetchRelatedWith, FetchRelatedWrite,
-        FetchRelatesExclusiveRead, FetchRelatesExclusiveWith, FetchRelatesExclusiveWrite,
-        FetchRelatesRead, FetchRelatesToRead, FetchRelatesToWrite, FetchRelatesWith,
-        FetchRelatesWrite, FilterFetchRelatesTo, FilterRelated, FilterRelatedBy, FilterRelates,
-        FilterRelatesTo, Related, Relates, RelatesExclusive, RelatesTo, RelationIter,
-        RelationReadIter, RelationWriteIter,
+        FetchFilterRelatedBy, FetchRelatedPair, FetchRelatedRead, FetchRelatedWith,
+        FetchRelatedWrite, FetchRelatesExclusiveRead, FetchRelatesExclusiveWith,
+        FetchRelatesExclusiveWrite, FetchRelatesPair, FetchRelatesRead, FetchRelatesToRead,
+        FetchRelatesToWrite, FetchRelatesWith, FetchRelatesWrite, FetchRelations,
+        FilterFetchRelatesTo, FilterRelated, FilterRelatedBy, FilterRelates, FilterRelatesTo,
+        ModifiedFetchRelation, PairIter, Related, RelatedPair, Relates, RelatesExclusive,
+        RelatesPair, RelatesTo, RelationIter, RelationReadIter, RelationWriteIter, Relations,
+        RelationsIter,
     },
+    registry::{ErasedRelation, RelationInfo},
 };
 
-pub(crate) use self::components::{OriginComponent, TargetComponent};
+pub(crate) use self::{
+    components::{OriginComponent, TargetComponent},
+    registry::{ErasedRelations, RelationRegistry},
+};
 
 mod child_of;
 mod components;
+mod events;
+pub mod graph;
+mod hierarchy;
+mod pair;
+mod path;
+mod policy;
 mod query;
+mod registry;
 
 /// Trait that must be implemented for types to be
 /// used as relation components.
@@ -39,14 +59,18 @@ mod query;
 /// is dropped. Appropriate hook method is called when this happens.
 /// `on_drop` is called when relation is dropped from "origin" entity.
 /// `on_target_drop` is called when "target" entity is dropped.
-pub trait Relation: Copy + 'static {
+///
+/// Relation value is stored on both entities, so it is cloned
+/// when inserted into a symmetric relation or non-symmetric relation
+/// which target side is stored separately.
+pub trait Relation: Clone + 'static {
     /// If `true` then relation can be added only once to an entity.
     /// If another exclusive relation is added to the same entity,
     /// then the old one is removed.
     /// `on_replace` is called when this happens.
     ///
     /// Non-exclusive relations is replaced only if re-added
-    /// with same target.
+    /// with same target and of the same kind.
     ///
     /// When using `#[derive(Relation)]` add `#[edict(exclusive)]` attribute to set this to true.
     const EXCLUSIVE: bool = false;
@@ -63,6 +87,43 @@ pub trait Relation: Copy + 'static {
     /// When using `#[derive(Relation)]` add `#[edict(owned)]` attribute to set this to true.
     const OWNED: bool = false;
 
+    /// If `true` then relations keep their order when other relations are removed.
+    ///
+    /// Origins of the relation are yielded by [`Related`] query in the order
+    /// they were inserted, unless reordered with
+    /// [`World::insert_relation_at`](crate::world::World::insert_relation_at),
+    /// [`World::move_relation`](crate::world::World::move_relation) or
+    /// [`World::sort_related_by`](crate::world::World::sort_related_by).
+    /// Same for targets yielded by [`Relates`] query.
+    ///
+    /// Otherwise order may change when relations are removed.
+    ///
+    /// When using `#[derive(Relation)]` add `#[edict(ordered)]` attribute to set this to true.
+    const ORDERED: bool = false;
+
+    /// Policy applied to origins of the relation when target is despawned.
+    ///
+    /// Unlike [`Relation::OWNED`] it is applied regardless
+    /// of other targets origins may have.
+    /// For symmetric relations it is applied in both directions.
+    ///
+    /// With [`DespawnPolicy::Deny`] target can't be despawned
+    /// while it has origins.
+    ///
+    /// When using `#[derive(Relation)]` add `#[edict(on_target_despawn = <policy>)]` attribute to set this,
+    /// where `<policy>` is `keep`, `despawn`, `deny`, `drop(Type)` or an expression of [`DespawnPolicy`] type.
+    const ON_TARGET_DESPAWN: DespawnPolicy = DespawnPolicy::Keep;
+
+    /// Policy applied to targets of the relation when origin is despawned.
+    ///
+    /// With [`DespawnPolicy::Deny`] origin can't be despawned
+    /// while it has targets.
+    /// Ignored for symmetric relations.
+    ///
+    /// When using `#[derive(Relation)]` add `#[edict(on_origin_despawn = <policy>)]` attribute to set this,
+    /// where `<policy>` is `keep`, `despawn`, `deny`, `drop(Type)` or an expression of [`DespawnPolicy`] type.
+    const ON_ORIGIN_DESPAWN: DespawnPolicy = DespawnPolicy::Keep;
+
     /// Returns name of the relation type.
     ///
     /// Can be overridden to provide custom name.
@@ -72,15 +133,47 @@ pub trait Relation: Copy + 'static {
         core::any::type_name::<Self>()
     }
 
+    /// Returns `true` if two relation values are of the same kind.
+    ///
+    /// Non-exclusive relation replaces existing one with the same target
+    /// only if they are of the same kind.
+    /// Otherwise both relations are kept, allowing one origin to relate to
+    /// the same target multiple times, once per kind.
+    /// See [`Pair`] for relation parameterized by runtime kind.
+    ///
+    /// Returns `true` by default, making all values of the type the same kind.
+    #[inline]
+    fn same_kind(&self, other: &Self) -> bool {
+        let _ = other;
+        true
+    }
+
+    /// Hook that is called when relation is inserted
+    /// between origin and target that were not related before.
+    ///
+    /// For exclusive relations this includes replacing relation
+    /// with one that has different target.
+    /// Replacing value of existing relation calls [`Relation::on_replace`] instead.
+    ///
+    /// When using `#[derive(Relation)]` add `#[edict(on_insert = <function>)]` attribute to set this.
+    #[inline]
+    fn on_insert(&self, origin: EntityId, target: EntityId, encoder: LocalActionEncoder) {
+        let _ = origin;
+        let _ = target;
+        let _ = encoder;
+    }
+
     /// Method that is called when relation is re-inserted.
-    /// For non-exclusive relations this happens when relation is re-inserted with the same
-    /// origin-target entity pair.
+    /// For non-exclusive relations this happens when relation of the same kind
+    /// is re-inserted with the same origin-target entity pair.
     /// For exclusive relations this happens when relation is re-inserted with
     /// origin that has relation of this type with any target.
     ///
     /// If returns `true`, `on_drop` will be called.
     ///
     /// Does nothing by default and returns `true`, causing `on_drop` to be called.
+    ///
+    /// When using `#[derive(Relation)]` add `#[edict(on_replace = <function>)]` attribute to set this.
     #[inline]
     fn on_replace(
         old_value: &mut Self,
@@ -103,6 +196,8 @@ pub trait Relation: Copy + 'static {
     /// Hook that is called when relation is dropped
     /// via [`World::drop_relation`](crate::world::World::drop_relation) or similar method
     /// or is replaced and [`Relation::on_replace`] returns `true`.
+    ///
+    /// When using `#[derive(Relation)]` add `#[edict(on_drop = <function>)]` attribute to set this.
     #[inline]
     fn on_drop(self, origin: EntityId, target: EntityId, encoder: LocalActionEncoder) {
         let _ = origin;
@@ -111,6 +206,8 @@ pub trait Relation: Copy + 'static {
     }
 
     /// Hook that is called when origin is despawned.
+    ///
+    /// When using `#[derive(Relation)]` add `#[edict(on_origin_drop = <function>)]` attribute to set this.
     #[inline]
     fn on_origin_drop(origin: EntityId, targets: &[(EntityId, Self)], encoder: LocalActionEncoder) {
         let _ = origin;
@@ -119,6 +216,8 @@ pub trait Relation: Copy + 'static {
     }
 
     /// Hook that is called when target is despawned.
+    ///
+    /// When using `#[derive(Relation)]` add `#[edict(on_target_drop = <function>)]` attribute to set this.
     #[inline]
     fn on_target_drop(origins: &[(EntityId, Self)], target: EntityId, encoder: LocalActionEncoder) {
         let _ = origins;
@@ -0,0 +1,45 @@
+use crate::entity::EntityId;
+
+use super::Relation;
+
+/// Relation parameterized by runtime kind.
+///
+/// Kind is an entity id that acts as a key of the relation,
+/// allowing to create arbitrary number of relation kinds at runtime
+/// without declaring new types.
+/// Origin may relate to the same target with pairs of different kinds.
+///
+/// Kind entity is used only as a key.
+/// Pairs are not removed when kind entity is despawned.
+///
+/// Use [`RelatesPair`](super::RelatesPair) and [`RelatedPair`](super::RelatedPair)
+/// queries to filter pairs by kind.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Pair {
+    kind: EntityId,
+}
+
+impl Pair {
+    /// Returns pair of specified kind.
+    #[inline]
+    pub const fn new(kind: EntityId) -> Self {
+        Pair { kind }
+    }
+
+    /// Returns kind of the pair.
+    #[inline]
+    pub const fn kind(&self) -> EntityId {
+        self.kind
+    }
+}
+
+impl Relation for Pair {
+    const EXCLUSIVE: bool = false;
+    const OWNED: bool = false;
+    const SYMMETRIC: bool = false;
+
+    #[inline]
+    fn same_kind(&self, other: &Self) -> bool {
+        self.kind == other.kind
+    }
+}
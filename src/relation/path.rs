@@ -0,0 +1,656 @@
+//! Multi-hop relation paths.
+//!
+//! [`Follow`] is a path step that walks relation of one type from each entity
+//! for one or more hops and passes each reached entity
+//! to the next step of the path.
+//! Path ends with [`Matches`] step that applies a query or filter
+//! to entities at the far end, or with `()` that accepts any entity.
+//! Since [`Follow`] is a path step itself, steps over different relations
+//! can be chained.
+//!
+//! Paths are searched with [`FollowView`] returned by [`World::follow`],
+//! [`World::follow_back`] and [`World::follow_with`].
+//! [`FollowView`] is also a function-system argument.
+//! It yields first matched path of each entity
+//! and skips entities without matching paths.
+//!
+//! For example, "entities whose grandparent has `Tag`" is expressed as
+//! `Follow::new(Relates::<With<ChildOf>>::new(), 2..=2, Matches(With::<Tag>::new()))`.
+
+use core::{
+    any::TypeId,
+    ops::{Bound, RangeBounds},
+    ptr::NonNull,
+};
+
+use alloc::vec::Vec;
+use hashbrown::HashSet;
+
+use crate::{
+    archetype::{chunk_idx, Archetype},
+    component::ComponentInfo,
+    entity::EntityId,
+    epoch::EpochId,
+    query::{DefaultQuery, Fetch, ImmutableQuery, SendQuery, With, WriteAlias},
+    system::{ActionBufferQueue, FnArg, FnArgState},
+    view::{BorrowState, RuntimeBorrowState},
+    world::World,
+    Access,
+};
+
+use super::{Related, Relates, Relation, RelationIter};
+
+/// Reusable buffers for searching relation paths.
+///
+/// Holds the path being built and entities reached by walks in progress,
+/// so searching paths does not allocate once buffers have grown.
+#[derive(Debug, Default)]
+pub struct PathState {
+    /// Entities of the path being built.
+    path: Vec<EntityId>,
+
+    /// Entities reached by walks in progress.
+    /// Each walk uses the tail of the buffer starting at its base index.
+    reached: Vec<Reached>,
+
+    /// Entities visited by walks in progress keyed by walk's base index.
+    visited: HashSet<(usize, EntityId)>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Reached {
+    entity: EntityId,
+
+    /// Index of the entity this one was reached from.
+    from: usize,
+
+    /// Number of hops from the start of the walk.
+    hops: usize,
+}
+
+impl PathState {
+    /// Returns new empty state.
+    #[inline]
+    pub fn new() -> Self {
+        PathState::default()
+    }
+
+    /// Returns entities of the path being built.
+    #[inline]
+    pub fn path(&self) -> &[EntityId] {
+        &self.path
+    }
+
+    /// Appends entity to the path being built.
+    #[inline]
+    pub fn push(&mut self, entity: EntityId) {
+        self.path.push(entity);
+    }
+
+    /// Removes last entity from the path being built.
+    #[inline]
+    pub fn pop(&mut self) {
+        self.path.pop();
+    }
+}
+
+/// Step of a relation path.
+///
+/// Implemented for `()` that accepts any entity,
+/// [`Matches`] that accepts entities matching a query
+/// and [`Follow`] that walks relation links.
+///
+/// # Safety
+///
+/// `component_access` must report all components that `visit_paths` reads.
+pub unsafe trait RelationPath: Copy + Send + Sync + 'static {
+    /// Returns access to the component type performed by this step.
+    fn component_access(&self, comp: &ComponentInfo) -> Option<Access>;
+
+    /// Calls `f` with each path that starts at `entity` and matches.
+    ///
+    /// Path's entities are pushed to the `state` path before `f` is called
+    /// and popped afterwards.
+    /// Stops early and returns `false` if `f` returns `false`.
+    ///
+    /// # Safety
+    ///
+    /// Components reported by `component_access` must not be borrowed mutably
+    /// in the `world` without runtime borrow tracking while this method runs.
+    unsafe fn visit_paths(
+        &self,
+        world: &World,
+        epoch: EpochId,
+        entity: EntityId,
+        state: &mut PathState,
+        f: &mut dyn FnMut(&[EntityId]) -> bool,
+    ) -> bool;
+}
+
+/// Accepts any entity.
+unsafe impl RelationPath for () {
+    #[inline]
+    fn component_access(&self, _comp: &ComponentInfo) -> Option<Access> {
+        None
+    }
+
+    #[inline]
+    unsafe fn visit_paths(
+        &self,
+        _world: &World,
+        _epoch: EpochId,
+        entity: EntityId,
+        state: &mut PathState,
+        f: &mut dyn FnMut(&[EntityId]) -> bool,
+    ) -> bool {
+        state.push(entity);
+        let cont = f(state.path());
+        state.pop();
+        cont
+    }
+}
+
+/// Path step that accepts entities that match the query.
+///
+/// Query may be any immutable query or filter.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Matches<Q>(pub Q);
+
+unsafe impl<Q> RelationPath for Matches<Q>
+where
+    Q: ImmutableQuery + SendQuery,
+{
+    #[inline]
+    fn component_access(&self, comp: &ComponentInfo) -> Option<Access> {
+        match self.0.component_access(comp) {
+            Ok(access) => access,
+            Err(WriteAlias) => Some(Access::Read),
+        }
+    }
+
+    #[inline]
+    unsafe fn visit_paths(
+        &self,
+        world: &World,
+        epoch: EpochId,
+        entity: EntityId,
+        state: &mut PathState,
+        f: &mut dyn FnMut(&[EntityId]) -> bool,
+    ) -> bool {
+        if unsafe { with_item(self.0, world, epoch, entity, |_| ()) }.is_none() {
+            return true;
+        }
+
+        state.push(entity);
+        let cont = f(state.path());
+        state.pop();
+        cont
+    }
+}
+
+/// Source of relation links for [`Follow`].
+///
+/// Implemented for [`Relates<With<R>>`] query
+/// that links origins to targets
+/// and [`Related<With<R>>`] query
+/// that links targets to origins.
+pub trait RelationLinks: ImmutableQuery + SendQuery {
+    /// Relation type.
+    type Relation: Relation;
+
+    /// Returns iterator over linked entities from the query item.
+    fn links<'a>(item: Self::Item<'a>) -> RelationIter<'a, Self::Relation>;
+}
+
+impl<R> RelationLinks for Relates<With<R>>
+where
+    R: Relation,
+{
+    type Relation = R;
+
+    #[inline]
+    fn links<'a>(item: Self::Item<'a>) -> RelationIter<'a, R> {
+        item
+    }
+}
+
+impl<R> RelationLinks for Related<With<R>>
+where
+    R: Relation,
+{
+    type Relation = R;
+
+    #[inline]
+    fn links<'a>(item: Self::Item<'a>) -> RelationIter<'a, R> {
+        item
+    }
+}
+
+/// Path step that follows relation links for a range of hops
+/// and continues with the next step from each reached entity.
+///
+/// Matched path is the entity itself followed by entities reached at each hop
+/// and then entities reached by the next steps.
+///
+/// Links are walked breadth-first and each entity is visited
+/// at most once per walk, at the smallest number of hops it is reached at,
+/// so shortest paths are found first and cycles in relation graph
+/// are not followed.
+///
+/// Use [`FollowView`] to search paths.
+#[derive(Clone, Copy, Debug)]
+pub struct Follow<L, N> {
+    links: L,
+    min_hops: usize,
+    max_hops: usize,
+    next: N,
+}
+
+impl<L, N> Follow<L, N>
+where
+    L: RelationLinks,
+    N: RelationPath,
+{
+    /// Returns path step that follows `links` for number of hops in `hops` range
+    /// and continues with `next`.
+    #[inline]
+    pub fn new(links: L, hops: impl RangeBounds<usize>, next: N) -> Self {
+        let min_hops = match hops.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+        let max_hops = match hops.end_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.saturating_sub(1),
+            Bound::Unbounded => usize::MAX,
+        };
+
+        Follow {
+            links,
+            min_hops,
+            max_hops,
+            next,
+        }
+    }
+
+    /// Returns `true` if entities of the archetype may start a matching path.
+    #[inline]
+    fn visit_archetype(&self, archetype: &Archetype) -> bool {
+        self.min_hops <= self.max_hops
+            && (self.min_hops == 0 || self.links.visit_archetype(archetype))
+    }
+
+    /// Walks links breadth-first from `entity`
+    /// and calls the next step with each entity reached
+    /// within the hops range.
+    unsafe fn walk(
+        &self,
+        world: &World,
+        epoch: EpochId,
+        entity: EntityId,
+        state: &mut PathState,
+        f: &mut dyn FnMut(&[EntityId]) -> bool,
+    ) -> bool {
+        let prefix = state.path.len();
+        let base = state.reached.len();
+
+        state.reached.push(Reached {
+            entity,
+            from: base,
+            hops: 0,
+        });
+        state.visited.insert((base, entity));
+
+        let cont = unsafe { self.walk_from(world, epoch, base, state, f) };
+
+        for reached in state.reached.drain(base..) {
+            state.visited.remove(&(base, reached.entity));
+        }
+        state.path.truncate(prefix);
+
+        cont
+    }
+
+    unsafe fn walk_from(
+        &self,
+        world: &World,
+        epoch: EpochId,
+        base: usize,
+        state: &mut PathState,
+        f: &mut dyn FnMut(&[EntityId]) -> bool,
+    ) -> bool {
+        let prefix = state.path.len();
+
+        let mut from_idx = base;
+        while from_idx < state.reached.len() {
+            let Reached {
+                entity: from, hops, ..
+            } = state.reached[from_idx];
+
+            if hops >= self.max_hops {
+                break;
+            }
+
+            let hops = hops + 1;
+            let first_reached = state.reached.len();
+
+            let PathState {
+                path,
+                reached,
+                visited,
+            } = &mut *state;
+
+            unsafe {
+                with_item(self.links, world, epoch, from, |item| {
+                    for linked in L::links(item) {
+                        let linked = linked.id();
+                        if path.contains(&linked) || !visited.insert((base, linked)) {
+                            continue;
+                        }
+                        reached.push(Reached {
+                            entity: linked,
+                            from: from_idx,
+                            hops,
+                        });
+                    }
+                });
+            }
+
+            let last_reached = state.reached.len();
+
+            if hops >= self.min_hops && first_reached < last_reached {
+                let mut idx = from_idx;
+                loop {
+                    state.path.push(state.reached[idx].entity);
+                    if idx == base {
+                        break;
+                    }
+                    idx = state.reached[idx].from;
+                }
+                state.path[prefix..].reverse();
+
+                for idx in first_reached..last_reached {
+                    let linked = state.reached[idx].entity;
+                    if !unsafe { self.next.visit_paths(world, epoch, linked, state, f) } {
+                        return false;
+                    }
+                }
+                state.path.truncate(prefix);
+            }
+
+            from_idx += 1;
+        }
+
+        true
+    }
+}
+
+unsafe impl<L, N> RelationPath for Follow<L, N>
+where
+    L: RelationLinks,
+    N: RelationPath,
+{
+    #[inline]
+    fn component_access(&self, comp: &ComponentInfo) -> Option<Access> {
+        let links = match self.links.component_access(comp) {
+            Ok(access) => access,
+            Err(WriteAlias) => Some(Access::Read),
+        };
+        links.or(self.next.component_access(comp))
+    }
+
+    unsafe fn visit_paths(
+        &self,
+        world: &World,
+        epoch: EpochId,
+        entity: EntityId,
+        state: &mut PathState,
+        f: &mut dyn FnMut(&[EntityId]) -> bool,
+    ) -> bool {
+        if self.min_hops > self.max_hops {
+            return true;
+        }
+
+        if self.min_hops == 0 && !unsafe { self.next.visit_paths(world, epoch, entity, state, f) } {
+            return false;
+        }
+
+        if self.max_hops == 0 {
+            return true;
+        }
+
+        unsafe { self.walk(world, epoch, entity, state, f) }
+    }
+}
+
+impl<L, N> Default for Follow<L, N>
+where
+    L: RelationLinks + DefaultQuery<Query = L>,
+    N: RelationPath + Default,
+{
+    /// Returns path step that follows exactly one hop.
+    #[inline]
+    fn default() -> Self {
+        Follow::new(L::default_query(), 1..=1, N::default())
+    }
+}
+
+/// View that searches paths of a [`Follow`] step in the world.
+///
+/// Components of entities along the path are borrowed at runtime
+/// only while they are read,
+/// so conflicting mutable borrows held elsewhere result in a panic.
+///
+/// Found paths are written to buffers owned by the view,
+/// so searching paths does not allocate once buffers have grown.
+///
+/// As a function-system argument the view follows exactly one hop.
+/// Chain [`Follow`] steps to follow more.
+/// System declares read access to components of every archetype
+/// that the path may read.
+pub struct FollowView<'a, L, N> {
+    world: &'a World,
+    follow: Follow<L, N>,
+    epoch: EpochId,
+    state: PathState,
+    found: Vec<EntityId>,
+}
+
+impl<'a, L, N> FollowView<'a, L, N>
+where
+    L: RelationLinks,
+    N: RelationPath,
+{
+    /// Returns view that searches paths of `follow` in the `world`.
+    #[inline]
+    pub fn new(world: &'a World, follow: Follow<L, N>) -> Self {
+        FollowView {
+            world,
+            follow,
+            epoch: world.epoch(),
+            state: PathState::new(),
+            found: Vec::new(),
+        }
+    }
+
+    /// Returns first matched path that starts at the entity.
+    ///
+    /// Returns `None` if entity is not alive or has no matching paths.
+    #[inline]
+    pub fn get(&mut self, entity: EntityId) -> Option<&[EntityId]> {
+        let found = &mut self.found;
+        found.clear();
+
+        let mut matched = false;
+
+        // Safety: View borrows the world immutably,
+        // so components may be borrowed mutably only with runtime borrow tracking.
+        unsafe {
+            self.follow.visit_paths(
+                self.world,
+                self.epoch,
+                entity,
+                &mut self.state,
+                &mut |path| {
+                    found.extend_from_slice(path);
+                    matched = true;
+                    false
+                },
+            );
+        }
+
+        if matched {
+            Some(&self.found)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the entity has a matching path.
+    #[inline]
+    pub fn contains(&mut self, entity: EntityId) -> bool {
+        self.get(entity).is_some()
+    }
+
+    /// Calls `f` with first matched path of each entity that has one.
+    pub fn for_each(&mut self, mut f: impl FnMut(&[EntityId])) {
+        for archetype in self.world.archetypes() {
+            if !self.follow.visit_archetype(archetype) {
+                continue;
+            }
+
+            for &entity in archetype.entities() {
+                // Safety: View borrows the world immutably,
+                // so components may be borrowed mutably only with runtime borrow tracking.
+                unsafe {
+                    self.follow.visit_paths(
+                        self.world,
+                        self.epoch,
+                        entity,
+                        &mut self.state,
+                        &mut |path| {
+                            f(path);
+                            false
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// State type used by corresponding [`FollowView`].
+pub struct FollowState<L, N> {
+    follow: Follow<L, N>,
+}
+
+impl<'a, L, N> FnArg for FollowView<'a, L, N>
+where
+    L: RelationLinks + DefaultQuery<Query = L>,
+    N: RelationPath + Default,
+{
+    type State = FollowState<L, N>;
+}
+
+unsafe impl<L, N> FnArgState for FollowState<L, N>
+where
+    L: RelationLinks + DefaultQuery<Query = L>,
+    N: RelationPath + Default,
+{
+    type Arg<'a> = FollowView<'a, L, N>;
+
+    #[inline]
+    fn new() -> Self {
+        FollowState {
+            follow: Follow::default(),
+        }
+    }
+
+    #[inline]
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn world_access(&self) -> Option<Access> {
+        Some(Access::Read)
+    }
+
+    #[inline]
+    fn visit_archetype(&self, _archetype: &Archetype) -> bool {
+        // Paths may reach entities of any archetype.
+        true
+    }
+
+    #[inline]
+    fn borrows_components_at_runtime(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn component_access(&self, comp: &ComponentInfo) -> Option<Access> {
+        RelationPath::component_access(&self.follow, comp)
+    }
+
+    #[inline]
+    fn resource_type_access(&self, _ty: TypeId) -> Option<Access> {
+        None
+    }
+
+    #[inline]
+    unsafe fn get_unchecked<'a>(
+        &'a mut self,
+        world: NonNull<World>,
+        _queue: &mut dyn ActionBufferQueue,
+    ) -> FollowView<'a, L, N> {
+        // Safety: Declares read access.
+        let world = unsafe { world.as_ref() };
+        FollowView::new(world, self.follow)
+    }
+}
+
+/// Fetches item of immutable query `Q` from the entity and calls `f` with it.
+///
+/// Borrows components accessed by the query for the duration of the call.
+/// Returns `None` if entity is not alive or does not match the query.
+unsafe fn with_item<Q, R>(
+    query: Q,
+    world: &World,
+    epoch: EpochId,
+    entity: EntityId,
+    f: impl FnOnce(Q::Item<'_>) -> R,
+) -> Option<R>
+where
+    Q: ImmutableQuery,
+{
+    let loc = world.entities().get_location(entity)?;
+
+    if loc.arch == u32::MAX {
+        return query.reserved_entity_item(entity, loc.idx).map(f);
+    }
+
+    let archetype = &world.archetypes()[loc.arch as usize];
+
+    if !query.visit_archetype(archetype) {
+        return None;
+    }
+
+    RuntimeBorrowState::new().with(query, (), archetype, || {
+        if !unsafe { query.visit_archetype_late(archetype) } {
+            return None;
+        }
+
+        let mut fetch = unsafe { query.fetch(loc.arch, archetype, epoch) };
+
+        if !unsafe { fetch.visit_chunk(chunk_idx(loc.idx)) } {
+            return None;
+        }
+
+        if !unsafe { fetch.visit_item(loc.idx) } {
+            return None;
+        }
+
+        Some(f(unsafe { fetch.get_item(loc.idx) }))
+    })
+}
@@ -0,0 +1,89 @@
+use core::{any::TypeId, fmt};
+
+use crate::{action::LocalActionEncoder, component::Component, entity::EntityId, type_id};
+
+/// Cleanup policy that relation applies to entities on the other side
+/// when one side of the relation is despawned.
+///
+/// See [`Relation::ON_TARGET_DESPAWN`](super::Relation::ON_TARGET_DESPAWN)
+/// and [`Relation::ON_ORIGIN_DESPAWN`](super::Relation::ON_ORIGIN_DESPAWN).
+#[derive(Clone, Copy)]
+pub enum DespawnPolicy {
+    /// Only the relation is removed.
+    Keep,
+
+    /// Entities on the other side are despawned as well.
+    Despawn,
+
+    /// Component with returned type id is dropped from entities on the other side.
+    ///
+    /// Use [`DespawnPolicy::drop_component`] to construct this variant.
+    DropComponent(fn() -> TypeId),
+
+    /// Despawning is denied while relation exists.
+    ///
+    /// [`World::try_despawn`](crate::world::World::try_despawn) and
+    /// [`World::try_despawn_batch`](crate::world::World::try_despawn_batch) fail with
+    /// `Err(DespawnError::Denied)` for such entities.
+    /// Other despawn methods, including deferred despawns and despawns
+    /// cascaded by [`DespawnPolicy::Despawn`], leave them alive.
+    Deny,
+}
+
+impl DespawnPolicy {
+    /// Returns policy that drops component `T` from entities on the other side.
+    #[inline]
+    pub const fn drop_component<T>() -> Self
+    where
+        T: Component,
+    {
+        DespawnPolicy::DropComponent(type_id::<T>)
+    }
+
+    /// Returns `true` if policy is [`DespawnPolicy::Deny`].
+    #[inline]
+    pub const fn is_deny(&self) -> bool {
+        matches!(self, DespawnPolicy::Deny)
+    }
+
+    /// Applies policy to entities on the other side of the despawned entity.
+    pub(crate) fn apply(
+        self,
+        entities: impl Iterator<Item = EntityId>,
+        mut encoder: LocalActionEncoder,
+    ) {
+        match self {
+            DespawnPolicy::Keep | DespawnPolicy::Deny => {}
+            DespawnPolicy::Despawn => encoder.despawn_batch(entities),
+            DespawnPolicy::DropComponent(ty) => encoder.drop_erased_batch(entities, ty()),
+        }
+    }
+}
+
+impl PartialEq for DespawnPolicy {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (DespawnPolicy::Keep, DespawnPolicy::Keep) => true,
+            (DespawnPolicy::Despawn, DespawnPolicy::Despawn) => true,
+            (DespawnPolicy::DropComponent(a), DespawnPolicy::DropComponent(b)) => a() == b(),
+            (DespawnPolicy::Deny, DespawnPolicy::Deny) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for DespawnPolicy {}
+
+impl fmt::Debug for DespawnPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DespawnPolicy::Keep => f.write_str("Keep"),
+            DespawnPolicy::Despawn => f.write_str("Despawn"),
+            DespawnPolicy::DropComponent(ty) => {
+                f.debug_tuple("DropComponent").field(&ty()).finish()
+            }
+            DespawnPolicy::Deny => f.write_str("Deny"),
+        }
+    }
+}
@@ -14,6 +14,12 @@
 //! [`RelatesExclusive`] - matches relation origins and fetches exclusive relation instance and target.
 //! [`RelatesTo`] - matches relation origin with specified target and fetches relation instance.
 //! [`Related`] - matches relation targets and fetches slice of origins.
+//! [`RelatesPair`] - matches origins of [`Pair`]s of specified kind and fetches their targets.
+//! [`RelatedPair`] - matches targets of [`Pair`]s of specified kind and fetches their origins.
+//! [`Relations`] - matches all entities and fetches type-erased relations of any type.
+//!
+//! Any of the queries above can be wrapped into [`Modified`] to match only entities
+//! which relations of the type were modified after specified epoch.
 //!
 //! # Filters
 //!
@@ -26,16 +32,22 @@
 //! [`FilterRelatedBy`] - filters relations targets with specified origin.
 //! [`FilterNotRelated`] - filters entities that are not relation targets.
 //! [`FilterNotRelatedBy`] - filters entities that are not relation targets with specified origin.
+//!
+//! [`Modified`]: crate::query::Modified
+//! [`Pair`]: crate::relation::Pair
 
 mod filter_related;
 mod filter_related_by;
 mod filter_relates;
 mod filter_relates_to;
 mod iter;
+mod modified;
+mod pair;
 mod related;
 mod relates;
 mod relates_exclusive;
 mod relates_to;
+mod relations;
 
 pub use self::{
     filter_related::FilterRelated,
@@ -43,6 +55,8 @@ pub use self::{
     filter_relates::FilterRelates,
     filter_relates_to::{FilterFetchRelatesTo, FilterRelatesTo},
     iter::{RelationIter, RelationReadIter, RelationWriteIter},
+    modified::ModifiedFetchRelation,
+    pair::{FetchRelatedPair, FetchRelatesPair, PairIter, RelatedPair, RelatesPair},
     related::{FetchRelatedRead, FetchRelatedWith, FetchRelatedWrite, Related},
     relates::{FetchRelatesRead, FetchRelatesWith, FetchRelatesWrite, Relates},
     relates_exclusive::{
@@ -50,4 +64,5 @@ pub use self::{
         RelatesExclusive,
     },
     relates_to::{FetchRelatesToRead, FetchRelatesToWrite, RelatesTo},
+    relations::{FetchRelations, Relations, RelationsIter},
 };
@@ -0,0 +1,229 @@
+use core::{any::TypeId, marker::PhantomData, ptr::NonNull};
+
+use crate::{
+    archetype::Archetype,
+    component::ComponentInfo,
+    epoch::EpochId,
+    query::{
+        AsQuery, Fetch, ImmutableQuery, IntoQuery, Modified, Query, Read, SendQuery, With, Write,
+        WriteAlias,
+    },
+    relation::{
+        ExclusiveRelation, OriginComponent, Related, Relates, RelatesExclusive, RelatesTo,
+        Relation, TargetComponent,
+    },
+    system::QueryArg,
+    type_id,
+    world::World,
+    Access,
+};
+
+/// [`Fetch`] type for the [`Modified`] relation queries.
+///
+/// Wraps fetch of the relation query and skips entities
+/// which relation component was not modified after the epoch.
+pub struct ModifiedFetchRelation<'a, F> {
+    after_epoch: EpochId,
+    entity_epochs: NonNull<EpochId>,
+    chunk_epochs: NonNull<EpochId>,
+    fetch: F,
+    marker: PhantomData<&'a [EpochId]>,
+}
+
+unsafe impl<'a, F> Fetch<'a> for ModifiedFetchRelation<'a, F>
+where
+    F: Fetch<'a>,
+{
+    type Item = F::Item;
+
+    #[inline]
+    fn dangling() -> Self {
+        ModifiedFetchRelation {
+            after_epoch: EpochId::start(),
+            entity_epochs: NonNull::dangling(),
+            chunk_epochs: NonNull::dangling(),
+            fetch: F::dangling(),
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    unsafe fn visit_chunk(&mut self, chunk_idx: u32) -> bool {
+        let chunk_epoch = unsafe { *self.chunk_epochs.as_ptr().add(chunk_idx as usize) };
+        chunk_epoch.after(self.after_epoch) && unsafe { self.fetch.visit_chunk(chunk_idx) }
+    }
+
+    #[inline]
+    unsafe fn visit_item(&mut self, idx: u32) -> bool {
+        let epoch = unsafe { *self.entity_epochs.as_ptr().add(idx as usize) };
+        epoch.after(self.after_epoch) && unsafe { self.fetch.visit_item(idx) }
+    }
+
+    #[inline]
+    unsafe fn touch_chunk(&mut self, chunk_idx: u32) {
+        unsafe { self.fetch.touch_chunk(chunk_idx) }
+    }
+
+    #[inline]
+    unsafe fn get_item(&mut self, idx: u32) -> F::Item {
+        unsafe { self.fetch.get_item(idx) }
+    }
+}
+
+/// Returns type id of the component that holds relation on the target side.
+#[inline]
+fn related_component<R: Relation>() -> TypeId {
+    if R::SYMMETRIC {
+        type_id::<OriginComponent<R>>()
+    } else {
+        type_id::<TargetComponent<R>>()
+    }
+}
+
+macro_rules! impl_modified_relation {
+    ($query:ty $(as $alias:ty)? where R: $bound:ident => $component:expr) => {
+        $(
+            impl<R> AsQuery for Modified<$alias>
+            where
+                R: $bound,
+            {
+                type Query = Modified<$query>;
+            }
+        )?
+
+        impl<R> AsQuery for Modified<$query>
+        where
+            R: $bound,
+        {
+            type Query = Self;
+        }
+
+        impl<R> IntoQuery for Modified<$query>
+        where
+            R: $bound,
+        {
+            #[inline]
+            fn into_query(self) -> Self {
+                self
+            }
+        }
+
+        unsafe impl<R> Query for Modified<$query>
+        where
+            R: $bound,
+        {
+            type Item<'a> = <$query as Query>::Item<'a>;
+            type Fetch<'a> = ModifiedFetchRelation<'a, <$query as Query>::Fetch<'a>>;
+
+            const MUTABLE: bool = <$query as Query>::MUTABLE;
+            const FILTERS_ENTITIES: bool = true;
+
+            #[inline]
+            fn component_access(&self, comp: &ComponentInfo) -> Result<Option<Access>, WriteAlias> {
+                self.query.component_access(comp)
+            }
+
+            #[inline]
+            fn visit_archetype(&self, archetype: &Archetype) -> bool {
+                self.query.visit_archetype(archetype)
+            }
+
+            #[inline]
+            unsafe fn visit_archetype_late(&self, archetype: &Archetype) -> bool {
+                let component = unsafe { archetype.component($component).unwrap_unchecked() };
+                let data = unsafe { component.data() };
+                data.epoch.after(self.after_epoch)
+                    && unsafe { self.query.visit_archetype_late(archetype) }
+            }
+
+            #[inline]
+            unsafe fn access_archetype(&self, archetype: &Archetype, f: impl FnMut(TypeId, Access)) {
+                unsafe { self.query.access_archetype(archetype, f) }
+            }
+
+            #[inline]
+            unsafe fn fetch<'a>(
+                &self,
+                arch_idx: u32,
+                archetype: &'a Archetype,
+                epoch: EpochId,
+            ) -> ModifiedFetchRelation<'a, <$query as Query>::Fetch<'a>> {
+                let fetch = unsafe { self.query.fetch(arch_idx, archetype, epoch) };
+
+                let component = unsafe { archetype.component($component).unwrap_unchecked() };
+                let data = unsafe { component.data() };
+
+                ModifiedFetchRelation {
+                    after_epoch: self.after_epoch,
+                    entity_epochs: unsafe {
+                        NonNull::new_unchecked(data.entity_epochs.as_ptr() as *mut EpochId)
+                    },
+                    chunk_epochs: unsafe {
+                        NonNull::new_unchecked(data.chunk_epochs.as_ptr() as *mut EpochId)
+                    },
+                    fetch,
+                    marker: PhantomData,
+                }
+            }
+        }
+
+        unsafe impl<R> ImmutableQuery for Modified<$query>
+        where
+            R: $bound,
+            $query: ImmutableQuery,
+        {
+        }
+
+        unsafe impl<R> SendQuery for Modified<$query>
+        where
+            R: $bound,
+            $query: SendQuery,
+        {
+        }
+    };
+}
+
+macro_rules! impl_modified_relation_arg {
+    ($query:ty where R: $bound:ident) => {
+        impl<R> QueryArg for Modified<$query>
+        where
+            R: $bound,
+            $query: QueryArg,
+        {
+            #[inline]
+            fn new() -> Self {
+                Modified::with_query(EpochId::start(), <$query as QueryArg>::new())
+            }
+
+            #[inline]
+            fn after(&mut self, world: &World) {
+                self.after_epoch = world.epoch();
+            }
+        }
+    };
+}
+
+impl_modified_relation!(Relates<With<R>> where R: Relation => type_id::<OriginComponent<R>>());
+impl_modified_relation!(Relates<Read<R>> as Relates<&R> where R: Relation => type_id::<OriginComponent<R>>());
+impl_modified_relation!(Relates<Write<R>> as Relates<&mut R> where R: Relation => type_id::<OriginComponent<R>>());
+
+impl_modified_relation!(RelatesExclusive<With<R>> where R: ExclusiveRelation => type_id::<OriginComponent<R>>());
+impl_modified_relation!(RelatesExclusive<Read<R>> as RelatesExclusive<&R> where R: ExclusiveRelation => type_id::<OriginComponent<R>>());
+impl_modified_relation!(RelatesExclusive<Write<R>> as RelatesExclusive<&mut R> where R: ExclusiveRelation => type_id::<OriginComponent<R>>());
+
+impl_modified_relation!(RelatesTo<Read<R>> as RelatesTo<&R> where R: Relation => type_id::<OriginComponent<R>>());
+impl_modified_relation!(RelatesTo<Write<R>> as RelatesTo<&mut R> where R: Relation => type_id::<OriginComponent<R>>());
+
+impl_modified_relation!(Related<With<R>> where R: Relation => related_component::<R>());
+impl_modified_relation!(Related<Read<R>> as Related<&R> where R: Relation => related_component::<R>());
+impl_modified_relation!(Related<Write<R>> as Related<&mut R> where R: Relation => related_component::<R>());
+
+impl_modified_relation_arg!(Relates<With<R>> where R: Relation);
+impl_modified_relation_arg!(Relates<Read<R>> where R: Relation);
+impl_modified_relation_arg!(Relates<Write<R>> where R: Relation);
+impl_modified_relation_arg!(RelatesExclusive<With<R>> where R: ExclusiveRelation);
+impl_modified_relation_arg!(RelatesExclusive<Read<R>> where R: ExclusiveRelation);
+impl_modified_relation_arg!(RelatesExclusive<Write<R>> where R: ExclusiveRelation);
+impl_modified_relation_arg!(Related<With<R>> where R: Relation);
+impl_modified_relation_arg!(Related<Read<R>> where R: Relation);
+impl_modified_relation_arg!(Related<Write<R>> where R: Relation);
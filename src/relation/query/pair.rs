@@ -0,0 +1,280 @@
+use core::{any::TypeId, marker::PhantomData, ptr::NonNull};
+
+use crate::{
+    archetype::Archetype,
+    component::ComponentInfo,
+    entity::{EntityBound, EntityId},
+    epoch::EpochId,
+    query::{AsQuery, Fetch, ImmutableQuery, IntoQuery, Query, SendQuery, WriteAlias},
+    relation::{OriginComponent, Pair, TargetComponent},
+    type_id, Access,
+};
+
+/// Iterator over entities related by [`Pair`]s of one kind.
+#[derive(Clone)]
+pub struct PairIter<'a> {
+    kind: EntityId,
+    iter: core::slice::Iter<'a, (EntityId, Pair)>,
+}
+
+impl<'a> PairIter<'a> {
+    /// Creates a new iterator over entities related by pairs of the kind.
+    #[inline]
+    pub fn new(kind: EntityId, relations: &'a [(EntityId, Pair)]) -> Self {
+        PairIter {
+            kind,
+            iter: relations.iter(),
+        }
+    }
+}
+
+impl<'a> Iterator for PairIter<'a> {
+    type Item = EntityBound<'a>;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<EntityBound<'a>> {
+        let kind = self.kind;
+        let r = self.iter.find(|r| r.1.kind() == kind)?;
+        Some(EntityBound::new(r.0))
+    }
+}
+
+impl<'a> DoubleEndedIterator for PairIter<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<EntityBound<'a>> {
+        let kind = self.kind;
+        let r = self.iter.rfind(|r| r.1.kind() == kind)?;
+        Some(EntityBound::new(r.0))
+    }
+}
+
+/// Fetch for the [`RelatesPair`] query.
+pub struct FetchRelatesPair<'a> {
+    kind: EntityId,
+    ptr: NonNull<OriginComponent<Pair>>,
+    marker: PhantomData<&'a OriginComponent<Pair>>,
+}
+
+unsafe impl<'a> Fetch<'a> for FetchRelatesPair<'a> {
+    type Item = PairIter<'a>;
+
+    #[inline]
+    fn dangling() -> Self {
+        FetchRelatesPair {
+            kind: EntityId::dangling(),
+            ptr: NonNull::dangling(),
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    unsafe fn visit_item(&mut self, idx: u32) -> bool {
+        let component = unsafe { &*self.ptr.as_ptr().add(idx as usize) };
+        component.targets().iter().any(|r| r.1.kind() == self.kind)
+    }
+
+    #[inline]
+    unsafe fn get_item(&mut self, idx: u32) -> PairIter<'a> {
+        let component = unsafe { &*self.ptr.as_ptr().add(idx as usize) };
+        PairIter::new(self.kind, component.targets())
+    }
+}
+
+/// Query for origins of [`Pair`]s of specified kind.
+///
+/// Skips entities without pairs of the kind.
+/// Yields iterator over targets of the pairs for each origin.
+#[derive(Clone, Copy, Debug)]
+pub struct RelatesPair {
+    kind: EntityId,
+}
+
+impl RelatesPair {
+    /// Returns query for origins of pairs of specified kind.
+    #[inline]
+    pub const fn new(kind: EntityId) -> Self {
+        RelatesPair { kind }
+    }
+}
+
+impl AsQuery for RelatesPair {
+    type Query = Self;
+}
+
+impl IntoQuery for RelatesPair {
+    #[inline]
+    fn into_query(self) -> Self {
+        self
+    }
+}
+
+unsafe impl Query for RelatesPair {
+    type Item<'a> = PairIter<'a>;
+    type Fetch<'a> = FetchRelatesPair<'a>;
+
+    const MUTABLE: bool = false;
+    const FILTERS_ENTITIES: bool = true;
+
+    #[inline]
+    fn component_access(&self, comp: &ComponentInfo) -> Result<Option<Access>, WriteAlias> {
+        if comp.id() == type_id::<OriginComponent<Pair>>() {
+            Ok(Some(Access::Read))
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[inline]
+    fn visit_archetype(&self, archetype: &Archetype) -> bool {
+        archetype.has_component(type_id::<OriginComponent<Pair>>())
+    }
+
+    #[inline]
+    unsafe fn access_archetype(&self, _archetype: &Archetype, mut f: impl FnMut(TypeId, Access)) {
+        f(type_id::<OriginComponent<Pair>>(), Access::Read)
+    }
+
+    #[inline]
+    unsafe fn fetch<'a>(
+        &self,
+        _arch_idx: u32,
+        archetype: &'a Archetype,
+        _epoch: EpochId,
+    ) -> FetchRelatesPair<'a> {
+        let component = unsafe {
+            archetype
+                .component(type_id::<OriginComponent<Pair>>())
+                .unwrap_unchecked()
+        };
+        debug_assert_eq!(component.id(), type_id::<OriginComponent<Pair>>());
+
+        let data = unsafe { component.data() };
+
+        FetchRelatesPair {
+            kind: self.kind,
+            ptr: data.ptr.cast(),
+            marker: PhantomData,
+        }
+    }
+}
+
+unsafe impl ImmutableQuery for RelatesPair {}
+unsafe impl SendQuery for RelatesPair {}
+
+/// Fetch for the [`RelatedPair`] query.
+pub struct FetchRelatedPair<'a> {
+    kind: EntityId,
+    ptr: NonNull<TargetComponent<Pair>>,
+    marker: PhantomData<&'a TargetComponent<Pair>>,
+}
+
+unsafe impl<'a> Fetch<'a> for FetchRelatedPair<'a> {
+    type Item = PairIter<'a>;
+
+    #[inline]
+    fn dangling() -> Self {
+        FetchRelatedPair {
+            kind: EntityId::dangling(),
+            ptr: NonNull::dangling(),
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    unsafe fn visit_item(&mut self, idx: u32) -> bool {
+        let component = unsafe { &*self.ptr.as_ptr().add(idx as usize) };
+        component.origins().iter().any(|r| r.1.kind() == self.kind)
+    }
+
+    #[inline]
+    unsafe fn get_item(&mut self, idx: u32) -> PairIter<'a> {
+        let component = unsafe { &*self.ptr.as_ptr().add(idx as usize) };
+        PairIter::new(self.kind, component.origins())
+    }
+}
+
+/// Query for targets of [`Pair`]s of specified kind.
+///
+/// Skips entities without pairs of the kind.
+/// Yields iterator over origins of the pairs for each target.
+#[derive(Clone, Copy, Debug)]
+pub struct RelatedPair {
+    kind: EntityId,
+}
+
+impl RelatedPair {
+    /// Returns query for targets of pairs of specified kind.
+    #[inline]
+    pub const fn new(kind: EntityId) -> Self {
+        RelatedPair { kind }
+    }
+}
+
+impl AsQuery for RelatedPair {
+    type Query = Self;
+}
+
+impl IntoQuery for RelatedPair {
+    #[inline]
+    fn into_query(self) -> Self {
+        self
+    }
+}
+
+unsafe impl Query for RelatedPair {
+    type Item<'a> = PairIter<'a>;
+    type Fetch<'a> = FetchRelatedPair<'a>;
+
+    const MUTABLE: bool = false;
+    const FILTERS_ENTITIES: bool = true;
+
+    #[inline]
+    fn component_access(&self, comp: &ComponentInfo) -> Result<Option<Access>, WriteAlias> {
+        if comp.id() == type_id::<TargetComponent<Pair>>() {
+            Ok(Some(Access::Read))
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[inline]
+    fn visit_archetype(&self, archetype: &Archetype) -> bool {
+        archetype.has_component(type_id::<TargetComponent<Pair>>())
+    }
+
+    #[inline]
+    unsafe fn access_archetype(&self, _archetype: &Archetype, mut f: impl FnMut(TypeId, Access)) {
+        f(type_id::<TargetComponent<Pair>>(), Access::Read)
+    }
+
+    #[inline]
+    unsafe fn fetch<'a>(
+        &self,
+        _arch_idx: u32,
+        archetype: &'a Archetype,
+        _epoch: EpochId,
+    ) -> FetchRelatedPair<'a> {
+        let component = unsafe {
+            archetype
+                .component(type_id::<TargetComponent<Pair>>())
+                .unwrap_unchecked()
+        };
+        debug_assert_eq!(component.id(), type_id::<TargetComponent<Pair>>());
+
+        let data = unsafe { component.data() };
+
+        FetchRelatedPair {
+            kind: self.kind,
+            ptr: data.ptr.cast(),
+            marker: PhantomData,
+        }
+    }
+}
+
+unsafe impl ImmutableQuery for RelatedPair {}
+unsafe impl SendQuery for RelatedPair {}
@@ -291,7 +291,13 @@ where
 
     #[inline]
     fn component_access(&self, comp: &ComponentInfo) -> Result<Option<Access>, WriteAlias> {
-        if comp.id() == type_id::<TargetComponent<R>>() {
+        if R::SYMMETRIC {
+            if comp.id() == type_id::<OriginComponent<R>>() {
+                Ok(Some(Access::Read))
+            } else {
+                Ok(None)
+            }
+        } else if comp.id() == type_id::<TargetComponent<R>>() {
             Ok(Some(Access::Read))
         } else {
             Ok(None)
@@ -480,11 +486,17 @@ where
     type Item<'a> = RelationWriteIter<'a, R>;
     type Fetch<'a> = FetchRelatedWrite<'a, R>;
 
-    const MUTABLE: bool = false;
+    const MUTABLE: bool = true;
 
     #[inline]
     fn component_access(&self, comp: &ComponentInfo) -> Result<Option<Access>, WriteAlias> {
-        if comp.id() == type_id::<TargetComponent<R>>() {
+        if R::SYMMETRIC {
+            if comp.id() == type_id::<OriginComponent<R>>() {
+                Ok(Some(Access::Write))
+            } else {
+                Ok(None)
+            }
+        } else if comp.id() == type_id::<TargetComponent<R>>() {
             Ok(Some(Access::Write))
         } else {
             Ok(None)
@@ -519,13 +531,14 @@ where
         if R::SYMMETRIC {
             let component = unsafe {
                 archetype
-                    .component(type_id::<TargetComponent<R>>())
+                    .component(type_id::<OriginComponent<R>>())
                     .unwrap_unchecked()
             };
-            debug_assert_eq!(component.id(), type_id::<TargetComponent<R>>());
+            debug_assert_eq!(component.id(), type_id::<OriginComponent<R>>());
 
             let data = unsafe { component.data_mut() };
             data.epoch.bump(epoch);
+            data.wakers.wake();
 
             FetchRelatedWrite {
                 ptr: data.ptr.cast(),
@@ -537,13 +550,14 @@ where
         } else {
             let component = unsafe {
                 archetype
-                    .component(type_id::<OriginComponent<R>>())
+                    .component(type_id::<TargetComponent<R>>())
                     .unwrap_unchecked()
             };
-            debug_assert_eq!(component.id(), type_id::<OriginComponent<R>>());
+            debug_assert_eq!(component.id(), type_id::<TargetComponent<R>>());
 
             let data = unsafe { component.data_mut() };
             data.epoch.bump(epoch);
+            data.wakers.wake();
 
             FetchRelatedWrite {
                 ptr: data.ptr.cast(),
@@ -556,7 +570,6 @@ where
     }
 }
 
-unsafe impl<R> ImmutableQuery for Related<Write<R>> where R: Relation {}
 unsafe impl<R> SendQuery for Related<Write<R>> where R: Relation + Send {}
 
 impl<R> QueryArg for Related<Write<R>>
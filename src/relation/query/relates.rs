@@ -410,6 +410,7 @@ where
 
         let data = unsafe { component.data_mut() };
         data.epoch.bump(epoch);
+        data.wakers.wake();
 
         FetchRelatesWrite {
             ptr: data.ptr.cast(),
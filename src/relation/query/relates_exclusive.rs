@@ -413,6 +413,7 @@ where
 
         let data = unsafe { component.data_mut() };
         data.epoch.bump(epoch);
+        data.wakers.wake();
 
         FetchRelatesExclusiveWrite {
             epoch,
@@ -290,6 +290,7 @@ where
 
         let data = unsafe { component.data_mut() };
         data.epoch.bump(epoch);
+        data.wakers.wake();
 
         FetchRelatesToWrite {
             target: self.target,
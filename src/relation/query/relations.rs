@@ -0,0 +1,163 @@
+use core::{any::TypeId, ptr::NonNull};
+
+use hashbrown::hash_map::Values;
+
+use crate::{
+    archetype::{Archetype, ArchetypeComponent},
+    component::ComponentInfo,
+    entity::EntityId,
+    epoch::EpochId,
+    query::{
+        AsQuery, DefaultQuery, Fetch, ImmutableQuery, IntoQuery, Query, SendQuery, WriteAlias,
+    },
+    relation::{ErasedRelation, ErasedRelations},
+    system::QueryArg,
+    Access,
+};
+
+marker_type! {
+    /// Query for all relations of any type.
+    ///
+    /// Matches all entities and yields iterator over
+    /// type-erased relations where entity is either origin or target.
+    /// Use [`ErasedRelation::is_origin`] to tell the side.
+    /// For symmetric relations entity is the origin on both sides.
+    pub struct Relations;
+}
+
+impl AsQuery for Relations {
+    type Query = Self;
+}
+
+impl IntoQuery for Relations {
+    #[inline]
+    fn into_query(self) -> Self {
+        self
+    }
+}
+
+impl DefaultQuery for Relations {
+    #[inline]
+    fn default_query() -> Self {
+        Relations
+    }
+}
+
+impl QueryArg for Relations {
+    #[inline]
+    fn new() -> Self {
+        Relations
+    }
+}
+
+/// Fetch type for [`Relations`].
+pub struct FetchRelations<'a> {
+    archetype: Option<&'a Archetype>,
+}
+
+unsafe impl<'a> Fetch<'a> for FetchRelations<'a> {
+    type Item = RelationsIter<'a>;
+
+    #[inline]
+    fn dangling() -> Self {
+        FetchRelations { archetype: None }
+    }
+
+    #[inline]
+    unsafe fn get_item(&mut self, idx: u32) -> RelationsIter<'a> {
+        let archetype = unsafe { self.archetype.unwrap_unchecked() };
+
+        RelationsIter {
+            components: archetype.components(),
+            entity: unsafe { *archetype.entities().get_unchecked(idx as usize) },
+            idx,
+            relations: None,
+        }
+    }
+}
+
+/// Iterator over type-erased relations of one entity.
+///
+/// Yielded by [`Relations`] query.
+pub struct RelationsIter<'a> {
+    components: Values<'a, TypeId, ArchetypeComponent>,
+    entity: EntityId,
+    idx: u32,
+    relations: Option<ErasedRelations<'a>>,
+}
+
+impl<'a> Iterator for RelationsIter<'a> {
+    type Item = ErasedRelation<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<ErasedRelation<'a>> {
+        loop {
+            if let Some(relation) = self.relations.as_mut().and_then(Iterator::next) {
+                return Some(relation);
+            }
+
+            let component = self.components.next()?;
+            let Some(info) = component.relation() else {
+                continue;
+            };
+
+            // Safety: component is borrowed by the view for `'a`.
+            let data = unsafe { component.data() };
+            let ptr = unsafe {
+                NonNull::new_unchecked(
+                    data.ptr
+                        .as_ptr()
+                        .add(self.idx as usize * component.layout().size()),
+                )
+            };
+
+            let origin = component.is_relation_origin();
+            self.relations = Some(unsafe { info.relations(self.entity, ptr, origin) });
+        }
+    }
+}
+
+unsafe impl Query for Relations {
+    type Item<'a> = RelationsIter<'a>;
+    type Fetch<'a> = FetchRelations<'a>;
+
+    const MUTABLE: bool = false;
+
+    #[inline]
+    fn component_access(&self, comp: &ComponentInfo) -> Result<Option<Access>, WriteAlias> {
+        if comp.relation().is_some() {
+            Ok(Some(Access::Read))
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[inline]
+    fn visit_archetype(&self, _archetype: &Archetype) -> bool {
+        true
+    }
+
+    #[inline]
+    unsafe fn access_archetype(&self, archetype: &Archetype, mut f: impl FnMut(TypeId, Access)) {
+        for info in archetype.infos() {
+            if info.relation().is_some() {
+                f(info.id(), Access::Read);
+            }
+        }
+    }
+
+    #[inline]
+    unsafe fn fetch<'a>(
+        &self,
+        _arch_idx: u32,
+        archetype: &'a Archetype,
+        _epoch: EpochId,
+    ) -> FetchRelations<'a> {
+        FetchRelations {
+            archetype: Some(archetype),
+        }
+    }
+}
+
+unsafe impl ImmutableQuery for Relations {}
+unsafe impl SendQuery for Relations {}
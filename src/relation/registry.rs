@@ -0,0 +1,358 @@
+use core::{
+    alloc::Layout,
+    any::TypeId,
+    marker::PhantomData,
+    mem::{offset_of, size_of},
+    ptr::NonNull,
+};
+
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+
+use crate::{
+    entity::{EntityBound, EntityId},
+    hash::NoOpHasherBuilder,
+    type_id,
+};
+
+use super::{DespawnPolicy, OriginComponent, Relation, TargetComponent};
+
+/// Type-erased information about relation type.
+///
+/// Allows to inspect relations without knowing their types.
+/// See [`Relations`](super::Relations) query.
+#[derive(Clone, Copy, Debug)]
+pub struct RelationInfo {
+    ty: TypeId,
+    name: &'static str,
+    layout: Layout,
+    exclusive: bool,
+    symmetric: bool,
+    owned: bool,
+    ordered: bool,
+    on_target_despawn: DespawnPolicy,
+    on_origin_despawn: DespawnPolicy,
+    origin_component: TypeId,
+    target_component: Option<TypeId>,
+
+    /// Layout of `(EntityId, R)` pairs stored in origin component.
+    stride: usize,
+    entity_offset: usize,
+    value_offset: usize,
+
+    /// Returns pointer to and length of `(EntityId, R)` pairs stored in origin component.
+    targets: unsafe fn(NonNull<u8>) -> (NonNull<u8>, usize),
+
+    /// Returns pointer to and length of `(EntityId, R)` pairs stored in target component.
+    origins: unsafe fn(NonNull<u8>) -> (NonNull<u8>, usize),
+}
+
+impl RelationInfo {
+    /// Returns relation information for specified relation type.
+    #[inline]
+    #[must_use]
+    pub fn of<R>() -> Self
+    where
+        R: Relation,
+    {
+        RelationInfo {
+            ty: type_id::<R>(),
+            name: R::name(),
+            layout: Layout::new::<R>(),
+            exclusive: R::EXCLUSIVE,
+            symmetric: R::SYMMETRIC,
+            owned: R::OWNED,
+            ordered: R::ORDERED,
+            on_target_despawn: R::ON_TARGET_DESPAWN,
+            on_origin_despawn: R::ON_ORIGIN_DESPAWN,
+            origin_component: type_id::<OriginComponent<R>>(),
+            target_component: if R::SYMMETRIC {
+                None
+            } else {
+                Some(type_id::<TargetComponent<R>>())
+            },
+            stride: size_of::<(EntityId, R)>(),
+            entity_offset: offset_of!((EntityId, R), 0),
+            value_offset: offset_of!((EntityId, R), 1),
+            targets: targets::<R>,
+            origins: origins::<R>,
+        }
+    }
+
+    /// Returns relation's `TypeId`.
+    #[inline]
+    pub fn id(&self) -> TypeId {
+        self.ty
+    }
+
+    /// Returns relation's name.
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns relation's memory layout.
+    #[inline]
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Returns `true` if relation is exclusive.
+    #[inline]
+    pub fn is_exclusive(&self) -> bool {
+        self.exclusive
+    }
+
+    /// Returns `true` if relation is symmetric.
+    #[inline]
+    pub fn is_symmetric(&self) -> bool {
+        self.symmetric
+    }
+
+    /// Returns `true` if origin is owned by the target.
+    #[inline]
+    pub fn is_owned(&self) -> bool {
+        self.owned
+    }
+
+    /// Returns `true` if relation is ordered.
+    #[inline]
+    pub fn is_ordered(&self) -> bool {
+        self.ordered
+    }
+
+    /// Returns policy applied to origins when target is despawned.
+    #[inline]
+    pub fn on_target_despawn(&self) -> DespawnPolicy {
+        self.on_target_despawn
+    }
+
+    /// Returns policy applied to targets when origin is despawned.
+    #[inline]
+    pub fn on_origin_despawn(&self) -> DespawnPolicy {
+        self.on_origin_despawn
+    }
+
+    /// Returns `TypeId` of the component that stores relation on origin entity.
+    #[inline]
+    pub fn origin_component(&self) -> TypeId {
+        self.origin_component
+    }
+
+    /// Returns `TypeId` of the component that stores relation on target entity.
+    ///
+    /// Symmetric relations store origin component on both sides
+    /// and this method returns `None` for them.
+    #[inline]
+    pub fn target_component(&self) -> Option<TypeId> {
+        self.target_component
+    }
+
+    /// Returns iterator over relations stored in relation component of the entity.
+    ///
+    /// # Safety
+    ///
+    /// `component` must point to valid origin component of this relation type if `origin` is `true`
+    /// and to valid target component otherwise.
+    /// Component must not be borrowed mutably for lifetime `'a`.
+    #[inline]
+    pub(crate) unsafe fn relations<'a>(
+        &'a self,
+        entity: EntityId,
+        component: NonNull<u8>,
+        origin: bool,
+    ) -> ErasedRelations<'a> {
+        let (ptr, len) = if origin {
+            unsafe { (self.targets)(component) }
+        } else {
+            unsafe { (self.origins)(component) }
+        };
+
+        ErasedRelations {
+            info: self,
+            entity,
+            origin,
+            ptr,
+            len,
+            marker: PhantomData,
+        }
+    }
+}
+
+unsafe fn targets<R>(component: NonNull<u8>) -> (NonNull<u8>, usize)
+where
+    R: Relation,
+{
+    let component = unsafe { component.cast::<OriginComponent<R>>().as_ref() };
+    let targets = component.targets();
+    (NonNull::from(targets).cast(), targets.len())
+}
+
+unsafe fn origins<R>(component: NonNull<u8>) -> (NonNull<u8>, usize)
+where
+    R: Relation,
+{
+    let component = unsafe { component.cast::<TargetComponent<R>>().as_ref() };
+    let origins = component.origins();
+    (NonNull::from(origins).cast(), origins.len())
+}
+
+/// Iterator over type-erased relations stored in one relation component.
+#[derive(Clone)]
+pub(crate) struct ErasedRelations<'a> {
+    info: &'a RelationInfo,
+    entity: EntityId,
+    origin: bool,
+    ptr: NonNull<u8>,
+    len: usize,
+    marker: PhantomData<&'a [u8]>,
+}
+
+impl<'a> Iterator for ErasedRelations<'a> {
+    type Item = ErasedRelation<'a>;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<ErasedRelation<'a>> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let pair = self.ptr;
+        self.ptr = unsafe { pair.add(self.info.stride) };
+        self.len -= 1;
+
+        let other = unsafe { pair.add(self.info.entity_offset).cast::<EntityId>().read() };
+
+        let (origin, target) = if self.origin {
+            (self.entity, other)
+        } else {
+            (other, self.entity)
+        };
+
+        Some(ErasedRelation {
+            info: self.info,
+            origin,
+            target,
+            is_origin: self.origin,
+            value: unsafe { pair.add(self.info.value_offset) },
+            marker: PhantomData,
+        })
+    }
+}
+
+/// Type-erased relation yielded by [`Relations`](super::Relations) query.
+#[derive(Clone, Copy)]
+pub struct ErasedRelation<'a> {
+    info: &'a RelationInfo,
+    origin: EntityId,
+    target: EntityId,
+    is_origin: bool,
+    value: NonNull<u8>,
+    marker: PhantomData<&'a [u8]>,
+}
+
+impl<'a> ErasedRelation<'a> {
+    /// Returns information about relation type.
+    #[inline]
+    pub fn info(&self) -> &'a RelationInfo {
+        self.info
+    }
+
+    /// Returns origin of the relation.
+    #[inline]
+    pub fn origin(&self) -> EntityBound<'a> {
+        EntityBound::new(self.origin)
+    }
+
+    /// Returns target of the relation.
+    #[inline]
+    pub fn target(&self) -> EntityBound<'a> {
+        EntityBound::new(self.target)
+    }
+
+    /// Returns `true` if queried entity is the origin of the relation
+    /// and `false` if it is the target.
+    ///
+    /// Symmetric relations are stored on both sides
+    /// and queried entity is always the origin.
+    #[inline]
+    pub fn is_origin(&self) -> bool {
+        self.is_origin
+    }
+
+    /// Returns pointer to the relation value.
+    ///
+    /// Value has type and layout described by [`ErasedRelation::info`].
+    #[inline]
+    pub fn value_ptr(&self) -> NonNull<u8> {
+        self.value
+    }
+
+    /// Returns reference to the relation value
+    /// if relation is of type `R`.
+    #[inline]
+    pub fn downcast_ref<R>(&self) -> Option<&'a R>
+    where
+        R: Relation + Sync,
+    {
+        if self.info.ty != type_id::<R>() {
+            return None;
+        }
+
+        Some(unsafe { self.value.cast::<R>().as_ref() })
+    }
+}
+
+/// Container for [`RelationInfo`]s.
+pub(crate) struct RelationRegistry {
+    relations: HashMap<TypeId, RelationInfo, NoOpHasherBuilder>,
+
+    /// Relation components which presence denies despawning of the entity.
+    deny_despawn: Vec<TypeId>,
+}
+
+impl RelationRegistry {
+    pub const fn new() -> Self {
+        RelationRegistry {
+            relations: HashMap::with_hasher(NoOpHasherBuilder),
+            deny_despawn: Vec::new(),
+        }
+    }
+
+    pub fn get_or_register<R>(&mut self) -> &RelationInfo
+    where
+        R: Relation,
+    {
+        self.relations.entry(type_id::<R>()).or_insert_with(|| {
+            let info = RelationInfo::of::<R>();
+
+            if info.on_target_despawn.is_deny() {
+                self.deny_despawn
+                    .push(info.target_component.unwrap_or(info.origin_component));
+            }
+            if info.on_origin_despawn.is_deny() && !info.symmetric {
+                self.deny_despawn.push(info.origin_component);
+            }
+
+            info
+        })
+    }
+
+    pub fn get_info(&self, ty: TypeId) -> Option<&RelationInfo> {
+        self.relations.get(&ty)
+    }
+
+    pub fn iter_info(&self) -> impl Iterator<Item = &RelationInfo> {
+        self.relations.values()
+    }
+
+    /// Returns relation components which presence denies despawning of the entity.
+    pub fn deny_despawn(&self) -> &[TypeId] {
+        &self.deny_despawn
+    }
+}
@@ -10,12 +10,14 @@ use core::{
     fmt::{self, Debug, Display},
     hash::{Hash, Hasher},
     ops::{Deref, DerefMut},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering},
 };
 
+use amity::spin::Spin;
 use atomicell::{AtomicCell, Ref, RefMut};
-use hashbrown::HashMap;
+use hashbrown::{hash_map::Entry, HashMap};
 
-use crate::type_id;
+use crate::{type_id, wakers::Wakers};
 
 /// Resource borrowed immutably.
 /// Derefs to the resource type.
@@ -342,6 +344,10 @@ struct Resource {
     // Box<AtomicCell> instead of AtomicCell<Box> to avoid false sharing
     data: Box<AtomicCell<dyn Any>>,
     name: &'static str,
+
+    /// Version of the resource.
+    /// Updated when resource is inserted or borrowed mutably.
+    version: AtomicU64,
 }
 
 impl Debug for Resource {
@@ -362,6 +368,16 @@ unsafe impl Sync for Resource {}
 /// Type-erased container for singleton resources.
 pub(crate) struct Resources {
     resources: HashMap<TypeId, Resource>,
+
+    /// Last version given to a resource.
+    version: AtomicU64,
+
+    /// Wakers woken when resource changes its version or is removed.
+    wakers: Spin<HashMap<TypeId, Wakers>>,
+
+    /// Whether `wakers` may be non-empty.
+    /// Allows mutable borrows to skip locking.
+    has_wakers: AtomicBool,
 }
 
 impl Debug for Resources {
@@ -376,6 +392,52 @@ impl Resources {
     pub fn new() -> Self {
         Resources {
             resources: HashMap::new(),
+            version: AtomicU64::new(0),
+            wakers: Spin::new(HashMap::new()),
+            has_wakers: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns new unique version.
+    #[inline]
+    fn next_version(&self) -> u64 {
+        self.version.fetch_add(1, AtomicOrdering::Relaxed) + 1
+    }
+
+    /// Registers waker to be woken when resource is inserted,
+    /// borrowed mutably or removed.
+    #[cfg(feature = "flow")]
+    pub fn register_waker(
+        &mut self,
+        id: TypeId,
+        token: &crate::wakers::WakeToken,
+        waker: &core::task::Waker,
+    ) {
+        self.wakers
+            .get_mut()
+            .entry(id)
+            .or_default()
+            .register(token, waker);
+        *self.has_wakers.get_mut() = true;
+    }
+
+    /// Wakes wakers registered for the resource.
+    #[inline]
+    fn wake(&self, id: TypeId) {
+        if self.has_wakers.load(AtomicOrdering::Relaxed) {
+            self.wake_slow(id);
+        }
+    }
+
+    #[cold]
+    fn wake_slow(&self, id: TypeId) {
+        let mut wakers = self.wakers.lock();
+        if let Some(mut removed) = wakers.remove(&id) {
+            if wakers.is_empty() {
+                self.has_wakers.store(false, AtomicOrdering::Relaxed);
+            }
+            drop(wakers);
+            removed.wake();
         }
     }
 
@@ -388,40 +450,48 @@ impl Resources {
     /// `Resources<NoSend>` accepts any `'static` resource type.
     pub fn insert<T: 'static>(&mut self, resource: T) {
         let id = type_id::<T>();
+        let version = AtomicU64::new(self.next_version());
         self.resources.insert(
             id,
             Resource {
                 data: Box::new(AtomicCell::new(resource)),
                 name: type_name::<T>(),
+                version,
             },
         );
+        self.wake(id);
     }
 
-    /// Inserts resource into container.
-    /// Old value is replaced.
-    ///
-    /// For `Resources<Send>` resource type have to implement `Send`.
-    /// Allowing `Resources<Send>` to be moved into another thread and drop resources there.
+    /// Returns mutable reference to the resource.
+    /// Inserts resource returned by `f` if it is not present.
     ///
-    /// `Resources<NoSend>` accepts any `'static` resource type.
+    /// Version of the resource changes only when it is inserted.
     pub fn with<T: 'static>(&mut self, f: impl FnOnce() -> T) -> &mut T {
         let id = type_id::<T>();
-        self.resources
-            .entry(id)
-            .or_insert_with(|| Resource {
-                data: Box::new(AtomicCell::new(f())),
-                name: type_name::<T>(),
-            })
-            .data
-            .get_mut()
-            .downcast_mut()
-            .unwrap()
+        let resource = match self.resources.entry(id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let version = self.version.fetch_add(1, AtomicOrdering::Relaxed) + 1;
+                if *self.has_wakers.get_mut() {
+                    if let Some(mut wakers) = self.wakers.get_mut().remove(&id) {
+                        wakers.wake();
+                    }
+                }
+                entry.insert(Resource {
+                    data: Box::new(AtomicCell::new(f())),
+                    name: type_name::<T>(),
+                    version: AtomicU64::new(version),
+                })
+            }
+        };
+        resource.data.get_mut().downcast_mut().unwrap()
     }
 
     /// Removes resource from container.
     /// Returns `None` if resource is not found.
     pub fn remove<T: 'static>(&mut self) -> Option<T> {
         let mut resource = self.resources.remove(&type_id::<T>())?;
+        self.wake(type_id::<T>());
         let data = AtomicCell::into_inner(*unsafe {
             assert!(resource.data.get_mut().is::<T>());
 
@@ -524,10 +594,25 @@ impl Resources {
             );
         };
 
+        resource
+            .version
+            .store(self.next_version(), AtomicOrdering::Relaxed);
+        self.wake(id);
+
         let r = RefMut::map(r, |r| r.downcast_mut::<T>().unwrap());
         Some(ResMut { inner: r })
     }
 
+    /// Returns version of the resource.
+    /// Returns none if resource is not found.
+    ///
+    /// Version changes when resource is inserted or borrowed mutably.
+    #[inline]
+    pub fn version(&self, id: TypeId) -> Option<u64> {
+        let resource = self.resources.get(&id)?;
+        Some(resource.version.load(AtomicOrdering::Relaxed))
+    }
+
     /// Reset all possible leaks on resources.
     /// Mutable reference guarantees that no borrows are active.
     pub fn undo_leaks(&mut self) {
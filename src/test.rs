@@ -2,8 +2,11 @@ use alloc::{vec, vec::Vec};
 
 use crate::{
     component::Component,
-    query::{Entities, ImmutableQuery, Modified, Not, With, Without},
-    relation::{ChildOf, Relation},
+    epoch::EpochId,
+    query::{Entities, EpochOf, ImmutableQuery, Modified, Not, With, Without},
+    relation::{
+        ChildOf, HierarchyView, RelatedPair, Relates, RelatesPair, Relation, ReparentError,
+    },
     system::{IntoSystem, System},
     view::View,
     world::World,
@@ -244,6 +247,135 @@ fn version_insert_test() {
     assert_eq!(*view[1].1, U32(50));
 }
 
+#[test]
+fn version_batch_test() {
+    let mut world = World::new();
+
+    let ids = world
+        .spawn_batch((0..100).map(|i| (U32(i),)))
+        .map(|e| e.id())
+        .collect::<Vec<_>>();
+
+    let epoch = world.epoch();
+
+    *world.get::<&mut U32>(ids[3]).unwrap() = U32(1003);
+    *world.get::<&mut U32>(ids[70]).unwrap() = U32(1070);
+
+    let mut modified = Vec::new();
+    for (entities, batch) in world
+        .view_mut::<Entities>()
+        .modified::<U32>(epoch)
+        .into_iter_batched(64)
+    {
+        assert_eq!(entities.len(), batch.items().len());
+        assert_eq!(batch.mask().len(), batch.items().len());
+        modified.extend(
+            batch
+                .mask()
+                .iter()
+                .map(|idx| (entities[idx], batch.items()[idx])),
+        );
+    }
+
+    assert_eq!(modified, vec![(ids[3], U32(1003)), (ids[70], U32(1070))]);
+
+    for (_, batch) in world
+        .view_mut::<Entities>()
+        .modified::<U32>(epoch)
+        .into_iter_batched(128)
+    {
+        assert_eq!(batch.mask().words(), &[1 << 3, 1 << 6]);
+        assert_eq!(batch.mask().count(), 2);
+        assert!(batch.mask().is_modified(70));
+    }
+
+    let epoch = world.epoch();
+
+    *world.get::<&mut U32>(ids[5]).unwrap() = U32(1005);
+
+    let mut visited = 0;
+    let mut modified = Vec::new();
+    for (entities, batch) in world
+        .view_mut::<Entities>()
+        .modified_mut::<U32>(epoch)
+        .into_iter_batched(64)
+    {
+        assert_eq!(batch.mask().len(), batch.items().len());
+        visited += batch.items().len();
+        modified.extend(batch.mask().iter().map(|idx| entities[idx]));
+    }
+
+    // Rows that were not modified are visited with unset mask bits.
+    assert_eq!(visited, 100);
+    assert_eq!(modified, vec![ids[5]]);
+
+    // Only rows written through the batch are marked as modified.
+    let epoch = world.epoch();
+
+    for (entities, mut batch) in world
+        .view_mut::<Entities>()
+        .modified_mut::<U32>(EpochId::start())
+        .into_iter_batched(64)
+    {
+        for (idx, e) in entities.iter().enumerate() {
+            if *e == ids[7] || *e == ids[90] {
+                batch.get_mut(idx).0 += 1;
+            }
+        }
+    }
+
+    let mut modified = Vec::new();
+    for (entities, mut batch) in world
+        .view_mut::<Entities>()
+        .modified_mut::<U32>(epoch)
+        .into_iter_batched(64)
+    {
+        modified.extend(batch.mask().iter().map(|idx| entities[idx]));
+        for item in batch.iter_modified_mut() {
+            item.0 += 1;
+        }
+    }
+
+    assert_eq!(modified, vec![ids[7], ids[90]]);
+    assert_eq!(*world.get::<&U32>(ids[7]).unwrap(), U32(9));
+    assert_eq!(*world.get::<&U32>(ids[90]).unwrap(), U32(92));
+
+    let epoch = world.epoch();
+
+    assert_eq!(
+        world
+            .view_mut::<Entities>()
+            .modified::<U32>(epoch)
+            .into_iter()
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn version_batch_write_test() {
+    let mut world = World::new();
+
+    world
+        .spawn_batch((0..100).map(|i| (U32(i),)))
+        .for_each(drop);
+
+    let epoch = world.epoch();
+
+    for batch in world.view_mut::<&mut U32>().into_iter_batched(32) {
+        batch[0].0 += 1;
+    }
+
+    assert_eq!(
+        world
+            .view_mut::<Entities>()
+            .modified::<U32>(epoch)
+            .into_iter()
+            .count(),
+        100
+    );
+}
+
 #[test]
 fn test_relation() {
     let mut world = World::new();
@@ -512,6 +644,432 @@ fn test_symmetric_exclusive_relation() {
     );
 }
 
+#[test]
+fn test_modified_relation() {
+    let mut world = World::new();
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct A(u32);
+
+    impl Relation for A {}
+
+    let a = world.spawn(()).id();
+    let b = world.spawn(()).id();
+    let c = world.spawn(()).id();
+
+    let epoch = world.epoch();
+
+    world.insert_relation(a, A(0), c).unwrap();
+    world.insert_relation(b, A(1), c).unwrap();
+
+    assert_eq!(
+        world
+            .view_mut::<Entities>()
+            .modified_relates_ref::<A>(epoch)
+            .into_iter()
+            .count(),
+        2
+    );
+
+    let epoch = world.epoch();
+
+    assert_eq!(
+        world
+            .view_mut::<Entities>()
+            .modified_relates_ref::<A>(epoch)
+            .into_iter()
+            .count(),
+        0
+    );
+
+    world.insert_relation(b, A(2), a).unwrap();
+
+    let modified = world
+        .view_mut::<Entities>()
+        .modified_relates_ref::<A>(epoch)
+        .into_iter()
+        .map(|(e, _)| e.id())
+        .collect::<Vec<_>>();
+    assert_eq!(modified, vec![b]);
+
+    let modified = world
+        .view_mut::<Entities>()
+        .modified_related_ref::<A>(epoch)
+        .into_iter()
+        .map(|(e, _)| e.id())
+        .collect::<Vec<_>>();
+    assert_eq!(modified, vec![a]);
+
+    let epoch = world.epoch();
+
+    for relates in world.view_mut::<Relates<&mut A>>() {
+        for (a, _) in relates {
+            a.0 += 1;
+        }
+    }
+
+    assert_eq!(
+        world
+            .view_mut::<Entities>()
+            .filter_modified_relates::<A>(epoch)
+            .into_iter()
+            .count(),
+        2
+    );
+    assert_eq!(
+        world
+            .view_mut::<Entities>()
+            .filter_modified_related::<A>(epoch)
+            .into_iter()
+            .count(),
+        0
+    );
+
+    let epoch = world.epoch();
+
+    for (_, a) in world.view_mut::<Entities>().relates_to_mut::<A>(c) {
+        a.0 += 1;
+    }
+
+    let mut modified = world
+        .view_mut::<Entities>()
+        .modified_relates_to::<A>(c, epoch)
+        .into_iter()
+        .map(|(e, a)| (e.id(), *a))
+        .collect::<Vec<_>>();
+    modified.sort_by_key(|(_, a)| a.0);
+    assert_eq!(modified, vec![(a, A(2)), (b, A(3))]);
+
+    let epoch = world.epoch();
+    assert_eq!(
+        world
+            .view_mut::<Entities>()
+            .modified_relates_to::<A>(c, epoch)
+            .into_iter()
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn test_hierarchy() {
+    let mut world = World::new();
+
+    let root = world.spawn(()).id();
+    let a = world.spawn(()).id();
+    let b = world.spawn(()).id();
+    let a1 = world.spawn(()).id();
+    let a2 = world.spawn(()).id();
+    let b1 = world.spawn(()).id();
+
+    world.insert_relation(a, ChildOf, root).unwrap();
+    world.insert_relation(b, ChildOf, root).unwrap();
+    world.insert_relation(a1, ChildOf, a).unwrap();
+    world.insert_relation(a2, ChildOf, a).unwrap();
+    world.insert_relation(b1, ChildOf, b).unwrap();
+
+    assert_eq!(
+        world.ancestors::<ChildOf>(a2).collect::<Vec<_>>(),
+        vec![a, root]
+    );
+    assert_eq!(
+        world.descendants_dfs::<ChildOf>(root).collect::<Vec<_>>(),
+        vec![a, a1, a2, b, b1]
+    );
+    assert_eq!(
+        world.descendants_bfs::<ChildOf>(root).collect::<Vec<_>>(),
+        vec![a, b, a1, a2, b1]
+    );
+    assert_eq!(world.depth::<ChildOf>(root), 0);
+    assert_eq!(world.depth::<ChildOf>(b1), 2);
+    assert_eq!(world.root::<ChildOf>(a1), root);
+
+    assert_eq!(world.reparent(a, ChildOf, a1), Err(ReparentError::Cycle));
+    assert_eq!(world.reparent(a, ChildOf, a), Err(ReparentError::Cycle));
+
+    world.reparent(a, ChildOf, b1).unwrap();
+
+    let hierarchy = world.hierarchy::<ChildOf>();
+    assert_eq!(hierarchy.parent(a), Some(b1));
+    assert_eq!(hierarchy.depth(a2), 4);
+    assert_eq!(
+        hierarchy.children(root).map(|e| e.id()).collect::<Vec<_>>(),
+        vec![b]
+    );
+    assert!(hierarchy.is_ancestor(b, a1));
+    drop(hierarchy);
+
+    // Cycle created without `reparent` does not hang traversals.
+    world.insert_relation(root, ChildOf, a1).unwrap();
+
+    assert_eq!(
+        world.ancestors::<ChildOf>(a2).collect::<Vec<_>>(),
+        vec![a, b1, b, root, a1]
+    );
+    assert_eq!(world.depth::<ChildOf>(a2), 5);
+    assert_eq!(world.root::<ChildOf>(a1), root);
+    assert!(!world.hierarchy::<ChildOf>().is_ancestor(a2, a));
+    assert_eq!(world.descendants_dfs::<ChildOf>(root).count(), 5);
+    assert_eq!(world.descendants_bfs::<ChildOf>(a).count(), 5);
+}
+
+#[test]
+fn test_propagate() {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct Total(u32);
+    impl Component for Total {}
+
+    fn total(parent: Option<&Total>, value: &U32) -> Total {
+        Total(parent.map_or(0, |total| total.0) + value.0)
+    }
+
+    let mut world = World::new();
+
+    let root = world.spawn((U32(1), Total(0))).id();
+    let a = world.spawn((U32(2), Total(0))).id();
+    let b = world.spawn((U32(3), Total(0))).id();
+    let c = world.spawn((U32(4), Total(0))).id();
+
+    world.insert_relation(a, ChildOf, root).unwrap();
+    world.insert_relation(b, ChildOf, a).unwrap();
+    world.insert_relation(c, ChildOf, root).unwrap();
+
+    world.propagate::<ChildOf, U32, Total>(total);
+
+    assert_eq!(world.get::<&Total>(b).unwrap(), &Total(6));
+    assert_eq!(world.get::<&Total>(c).unwrap(), &Total(5));
+
+    let epoch = world.epoch();
+    world.get::<&mut U32>(a).unwrap().0 = 5;
+
+    let totals_epoch = world.epoch();
+
+    let mut visited = Vec::new();
+    world.propagate_modified::<ChildOf, U32, Total>(epoch, |parent, value| {
+        visited.push(value.0);
+        total(parent, value)
+    });
+
+    assert_eq!(visited, vec![5, 3]);
+    assert_eq!(world.get::<&Total>(b).unwrap(), &Total(9));
+    assert_eq!(world.get::<&Total>(c).unwrap(), &Total(5));
+
+    // Reading parent's `Total` does not mark it as modified.
+    assert!(!world
+        .get::<EpochOf<Total>>(root)
+        .unwrap()
+        .after(totals_epoch));
+    assert!(!world.get::<EpochOf<Total>>(c).unwrap().after(totals_epoch));
+
+    let mut modified = world
+        .view_mut::<Entities>()
+        .modified::<Total>(totals_epoch)
+        .into_iter()
+        .map(|(e, _)| e.id())
+        .collect::<Vec<_>>();
+    modified.sort();
+    let mut expected = vec![a, b];
+    expected.sort();
+    assert_eq!(modified, expected);
+}
+
+#[test]
+fn test_propagate_same_archetype() {
+    // Derived component doesn't need to be `Clone`.
+    #[derive(Debug, PartialEq, Eq)]
+    struct Path(Vec<u32>);
+    impl Component for Path {}
+
+    let mut world = World::new();
+
+    let root = world.spawn((U32(0), Path(Vec::new()))).id();
+    let a = world.spawn((U32(1), Path(Vec::new()))).id();
+    let b = world.spawn((U32(2), Path(Vec::new()))).id();
+    let c = world.spawn((U32(3), Path(Vec::new()))).id();
+
+    world.insert_relation(a, ChildOf, root).unwrap();
+    world.insert_relation(b, ChildOf, a).unwrap();
+    world.insert_relation(c, ChildOf, b).unwrap();
+
+    // Parent and child share archetype.
+    assert_eq!(
+        world.entities().get_location(a).unwrap().arch,
+        world.entities().get_location(b).unwrap().arch
+    );
+
+    world.propagate::<ChildOf, U32, Path>(|parent, value| {
+        let mut path = parent.map_or_else(Vec::new, |path| path.0.clone());
+        path.push(value.0);
+        Path(path)
+    });
+
+    assert_eq!(world.get::<&Path>(b).unwrap(), &Path(vec![0, 1, 2]));
+    assert_eq!(world.get::<&Path>(c).unwrap(), &Path(vec![0, 1, 2, 3]));
+}
+
+#[test]
+fn test_non_copy_relation() {
+    use alloc::sync::Arc;
+
+    use crate::relation::Related;
+
+    #[derive(Clone, Debug)]
+    struct Joint(Arc<Vec<u32>>);
+
+    impl Relation for Joint {}
+
+    #[derive(Clone, Debug)]
+    struct Link(Arc<Vec<u32>>);
+
+    impl Relation for Link {
+        const SYMMETRIC: bool = true;
+    }
+
+    let data = Arc::new(vec![1, 2, 3]);
+
+    let mut world = World::new();
+    let a = world.spawn(()).id();
+    let b = world.spawn(()).id();
+    let c = world.spawn(()).id();
+
+    world.insert_relation(a, Joint(data.clone()), b).unwrap();
+    world.insert_relation(a, Link(data.clone()), c).unwrap();
+
+    // Origin and target sides hold their own clones.
+    assert_eq!(Arc::strong_count(&data), 5);
+
+    for (_, relates) in world.view::<(Entities, Relates<&Joint>)>() {
+        for (joint, target) in relates {
+            assert_eq!(*joint.0, [1, 2, 3]);
+            assert_eq!(target.id(), b);
+        }
+    }
+    assert_eq!(world.view::<Related<&Joint>>().into_iter().count(), 1);
+
+    // Replacing relation updates both sides.
+    world
+        .insert_relation(a, Joint(Arc::new(vec![4])), b)
+        .unwrap();
+    assert_eq!(Arc::strong_count(&data), 3);
+    for relates in world.view::<Related<&Joint>>() {
+        for (joint, _) in relates {
+            assert_eq!(*joint.0, [4]);
+        }
+    }
+
+    world.insert_relation(c, Link(data.clone()), a).unwrap();
+    assert_eq!(Arc::strong_count(&data), 3);
+
+    let removed = world.remove_relation::<Link>(a, c).unwrap().unwrap();
+    assert!(Arc::ptr_eq(&removed.0, &data));
+    drop(removed);
+    assert_eq!(Arc::strong_count(&data), 1);
+
+    world.insert_relation(b, Joint(data.clone()), c).unwrap();
+    assert_eq!(Arc::strong_count(&data), 3);
+
+    world.despawn(c).unwrap();
+    assert_eq!(Arc::strong_count(&data), 1);
+
+    world.despawn(a).unwrap();
+    world.despawn(b).unwrap();
+    assert_eq!(Arc::strong_count(&data), 1);
+}
+
+#[test]
+fn test_erased_relations() {
+    use core::any::TypeId;
+
+    use crate::relation::Relations;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct Likes(u32);
+
+    impl Relation for Likes {}
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct Friend;
+
+    impl Relation for Friend {
+        const SYMMETRIC: bool = true;
+    }
+
+    let mut world = World::new();
+    let a = world.spawn(()).id();
+    let b = world.spawn(()).id();
+    let c = world.spawn(()).id();
+
+    world.insert_relation(a, ChildOf, b).unwrap();
+    world.insert_relation(a, Likes(1), b).unwrap();
+    world.insert_relation(a, Likes(2), c).unwrap();
+    world.insert_relation(b, Friend, c).unwrap();
+
+    assert_eq!(world.iter_relation_info().count(), 3);
+    let info = world.relation_info(TypeId::of::<Friend>()).unwrap();
+    assert!(info.is_symmetric());
+    assert!(!info.is_exclusive());
+    assert_eq!(info.target_component(), None);
+
+    let view = world.view_mut::<Relations>();
+
+    let mut relations = view
+        .try_get(a)
+        .unwrap()
+        .map(|r| {
+            (
+                r.info().id(),
+                r.target().id(),
+                r.downcast_ref::<Likes>().copied(),
+            )
+        })
+        .collect::<Vec<_>>();
+    relations.sort_by_key(|(_, _, likes)| likes.map(|l| l.0));
+
+    assert_eq!(
+        relations,
+        vec![
+            (TypeId::of::<ChildOf>(), b, None),
+            (TypeId::of::<Likes>(), b, Some(Likes(1))),
+            (TypeId::of::<Likes>(), c, Some(Likes(2))),
+        ]
+    );
+
+    assert!(view.try_get(a).unwrap().all(|r| r.is_origin()));
+
+    // Target side relations are yielded too.
+    let mut relations = view
+        .try_get(c)
+        .unwrap()
+        .map(|r| {
+            (
+                r.info().name(),
+                r.origin().id(),
+                r.target().id(),
+                r.is_origin(),
+            )
+        })
+        .collect::<Vec<_>>();
+    relations.sort_by_key(|(name, ..)| *name);
+
+    let mut expected = vec![(Friend::name(), c, b, true), (Likes::name(), a, c, false)];
+    expected.sort_by_key(|(name, ..)| *name);
+    assert_eq!(relations, expected);
+
+    let relations = view.try_get(b).unwrap().collect::<Vec<_>>();
+    assert_eq!(relations.len(), 3);
+    assert_eq!(relations.iter().filter(|r| !r.is_origin()).count(), 2);
+    assert!(relations
+        .iter()
+        .all(|r| r.is_origin() || r.origin().id() == a));
+
+    assert_eq!(
+        view.iter()
+            .map(|relations| relations.count())
+            .sum::<usize>(),
+        8
+    );
+}
+
 #[test]
 fn test_filters() {
     use crate::query::AsQuery;
@@ -608,8 +1166,720 @@ fn test_entity_flow() {
     assert_eq!(world.view::<&U32>().iter().count(), 1);
 }
 
+#[cfg(feature = "flow")]
 #[test]
-fn test_aliasing_borrows() {
+fn test_flow_handle() {
+    use crate::flow::JoinError;
+
+    let mut world = World::new();
+    let mut flows = Flows::new();
+
+    let answer = world.spawn_flow(|_: FlowWorld| async move { 42u32 });
+    let mut doubled = world.spawn_flow(|world: FlowWorld| async move {
+        let answer = answer.await.unwrap();
+        world.spawn((U32(answer),));
+        answer * 2
+    });
+
+    assert!(doubled.try_join().is_none());
+    flows.execute(&mut world);
+    flows.execute(&mut world);
+    assert_eq!(doubled.try_join(), Some(Ok(84)));
+    assert_eq!(world.view::<&U32>().iter().count(), 1);
+
+    struct DropFlag(alloc::sync::Arc<core::sync::atomic::AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, core::sync::atomic::Ordering::Relaxed);
+        }
+    }
+
+    let dropped = alloc::sync::Arc::new(core::sync::atomic::AtomicBool::new(false));
+    let flag = DropFlag(dropped.clone());
+    let mut endless = world.spawn_flow(|_: FlowWorld| async move {
+        let _flag = flag;
+        loop {
+            crate::yield_now!();
+        }
+    });
+
+    flows.execute(&mut world);
+    assert!(!endless.is_finished());
+
+    endless.abort();
+    flows.execute(&mut world);
+    assert!(dropped.load(core::sync::atomic::Ordering::Relaxed));
+    assert_eq!(endless.try_join(), Some(Err(JoinError::Aborted)));
+
+    let e = world.spawn(()).id();
+    let mut entity_flow = world.spawn_flow_for(e, |_: FlowEntity| async move {
+        loop {
+            crate::yield_now!();
+        }
+    });
+
+    flows.execute(&mut world);
+    world.despawn(e).unwrap();
+    flows.execute(&mut world);
+    assert_eq!(entity_flow.try_join(), Some(Err(JoinError::Cancelled)));
+}
+
+#[cfg(feature = "flow")]
+#[test]
+fn test_flow_timers() {
+    use core::time::Duration;
+
+    use crate::flow::{Elapsed, FlowTime};
+
+    let mut world = World::new();
+    let mut flows = Flows::new();
+
+    world.insert_resource(FlowTime::with_step(Duration::from_millis(100)));
+
+    let e = world.spawn(()).id();
+    let mut slept = world.spawn_flow_for(e, |e: FlowEntity| async move {
+        e.sleep(Duration::from_millis(250)).await;
+        e.insert(U32(1));
+    });
+
+    let mut ticked = world.spawn_flow(|world: FlowWorld| async move {
+        world.sleep_ticks(2).await;
+    });
+
+    let mut timed_out = world.spawn_flow(|world: FlowWorld| async move {
+        world
+            .timeout(Duration::from_millis(150), world.sleep_ticks(10))
+            .await
+    });
+
+    // Registering timers does not change the resource.
+    let version = world.resource_version::<FlowTime>();
+    flows.execute(&mut world);
+    assert_eq!(world.resource_version::<FlowTime>(), version);
+
+    for _ in 0..2 {
+        world.expect_resource_mut::<FlowTime>().tick();
+        flows.execute(&mut world);
+    }
+
+    assert_eq!(ticked.try_join(), Some(Ok(())));
+    assert_eq!(timed_out.try_join(), Some(Ok(Err(Elapsed))));
+    assert!(slept.try_join().is_none());
+
+    // Timer of the dropped inner sleep is cancelled.
+    assert_eq!(world.expect_resource::<FlowTime>().timers(), 1);
+    assert!(!world.try_has_component::<U32>(e).unwrap());
+
+    crate::flow::flow_time_system
+        .into_system()
+        .run_alone(&mut world);
+    flows.execute(&mut world);
+
+    assert_eq!(slept.try_join(), Some(Ok(())));
+    assert!(world.try_has_component::<U32>(e).unwrap());
+}
+
+#[cfg(feature = "flow")]
+#[test]
+fn test_flow_wait_until() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static CHECKS: AtomicUsize = AtomicUsize::new(0);
+
+    let mut world = World::new();
+    let mut flows = Flows::new();
+
+    let e = world.spawn((U32(0),)).id();
+    let other = world.spawn((Str("other"),)).id();
+
+    let mut reached = world.spawn_flow_for(e, |e: FlowEntity| async move {
+        e.wait_until::<&U32, _>(|value| {
+            CHECKS.fetch_add(1, Ordering::Relaxed);
+            value.0 >= 2
+        })
+        .await
+        .unwrap();
+    });
+
+    let mut counted = world.spawn_flow(|world: FlowWorld| async move {
+        world
+            .wait_until::<&U32, _>(|view| view.into_iter().count() >= 2)
+            .await;
+    });
+
+    flows.execute(&mut world);
+    assert_eq!(CHECKS.load(Ordering::Relaxed), 1);
+
+    // Waiting does not insert resources.
+    assert_eq!(world.resource_types().count(), 0);
+
+    // Entity flow inserts `WakeOnDrop` after first poll,
+    // moving the entity to another archetype.
+    flows.execute(&mut world);
+    assert_eq!(CHECKS.load(Ordering::Relaxed), 2);
+
+    // Unrelated changes do not wake the flow.
+    world.get::<&mut Str>(other).unwrap().0 = "changed";
+    world.spawn((Str("unrelated"),));
+    flows.execute(&mut world);
+    flows.execute(&mut world);
+    assert_eq!(CHECKS.load(Ordering::Relaxed), 2);
+
+    world.get::<&mut U32>(e).unwrap().0 = 1;
+    flows.execute(&mut world);
+    assert_eq!(CHECKS.load(Ordering::Relaxed), 3);
+    assert!(reached.try_join().is_none());
+
+    world.get::<&mut U32>(e).unwrap().0 = 2;
+    flows.execute(&mut world);
+    assert_eq!(reached.try_join(), Some(Ok(())));
+
+    assert!(counted.try_join().is_none());
+    world.insert(other, U32(5)).unwrap();
+    flows.execute(&mut world);
+    assert_eq!(counted.try_join(), Some(Ok(())));
+
+    // Waiting on despawned entity fails.
+    let victim = world.spawn((U32(0),)).id();
+    let mut gone = world.spawn_flow(move |world: FlowWorld| async move {
+        let victim = world.entity(victim).unwrap();
+        victim.wait_until::<&U32, _>(|_| false).await
+    });
+    flows.execute(&mut world);
+    assert!(gone.try_join().is_none());
+
+    world.despawn(victim).unwrap();
+    flows.execute(&mut world);
+    assert_eq!(gone.try_join(), Some(Ok(Err(crate::NoSuchEntity))));
+}
+
+#[cfg(feature = "flow")]
+#[test]
+fn test_flow_wait_resource_and_events() {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum GameState {
+        Menu,
+        Playing,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Scored(u32);
+
+    let mut world = World::new();
+    let mut flows = Flows::new();
+
+    let mut started = world.spawn_flow(|world: FlowWorld| async move {
+        world.wait_resource::<GameState>().await;
+        loop {
+            if world.copy_resource::<GameState>() == GameState::Playing {
+                break;
+            }
+            world.wait_resource_changed::<GameState>().await;
+        }
+    });
+
+    let mut scores = world.spawn_flow(|world: FlowWorld| async move {
+        let mut scored = world.subscribe::<Scored>();
+        let mut total = 0;
+        while total < 5 {
+            total += scored.recv().await.0;
+        }
+        total
+    });
+
+    flows.execute(&mut world);
+    flows.execute(&mut world);
+    assert!(started.try_join().is_none());
+
+    world.insert_resource(GameState::Menu);
+    flows.execute(&mut world);
+    assert!(started.try_join().is_none());
+
+    *world.expect_resource_mut::<GameState>() = GameState::Playing;
+    flows.execute(&mut world);
+    assert_eq!(started.try_join(), Some(Ok(())));
+
+    world.spawn_flow(|world: FlowWorld| async move {
+        world.send_event(Scored(2));
+        world.send_event(Scored(1));
+    });
+    flows.execute(&mut world);
+    flows.execute(&mut world);
+    assert!(scores.try_join().is_none());
+
+    world
+        .expect_resource_mut::<crate::event::Events<Scored>>()
+        .send(Scored(3));
+    flows.execute(&mut world);
+    assert_eq!(scores.try_join(), Some(Ok(6)));
+}
+
+#[cfg(feature = "flow")]
+#[test]
+fn test_flow_events_between_flows() {
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Ping(u32);
+
+    let mut world = World::new();
+    let mut flows = Flows::new();
+
+    let mut received = world.spawn_flow(|world: FlowWorld| async move {
+        let mut pings = world.subscribe::<Ping>();
+        let mut total = 0;
+        for _ in 0..3 {
+            total += pings.recv().await.0;
+        }
+        total
+    });
+    flows.execute(&mut world);
+
+    // Receiver waits for each event sent by another flow.
+    for n in 1..=3 {
+        assert!(received.try_join().is_none());
+        world.spawn_flow(move |world: FlowWorld| async move {
+            world.send_event(Ping(n));
+        });
+        flows.execute(&mut world);
+        flows.execute(&mut world);
+    }
+    assert_eq!(received.try_join(), Some(Ok(6)));
+}
+
+#[cfg(feature = "flow")]
+#[test]
+fn test_flow_combinators() {
+    use crate::flow::{join, join_all, race, select, AbortHandle, Either, FlowTime};
+
+    let mut world = World::new();
+    let mut flows = Flows::new();
+    world.insert_resource(FlowTime::with_step(core::time::Duration::ZERO));
+
+    let mut joined = world.spawn_flow(|world: FlowWorld| async move {
+        let (a, b) = join(
+            async move {
+                world.sleep_ticks(2).await;
+                1
+            },
+            async move {
+                world.sleep_ticks(1).await;
+                2
+            },
+        )
+        .await;
+
+        let all = join_all((1..=3).map(|n| async move {
+            world.sleep_ticks(n).await;
+            n as u32
+        }))
+        .await;
+
+        a + b + all.iter().sum::<u32>()
+    });
+
+    let mut selected = world.spawn_flow(|world: FlowWorld| async move {
+        let first = select(world.sleep_ticks(5), async move {
+            world.sleep_ticks(1).await;
+            "fast"
+        })
+        .await;
+
+        let raced = race([world.sleep_ticks(3), world.sleep_ticks(1)]).await;
+        (first, raced)
+    });
+
+    let mut parent = world.spawn_flow(|world: FlowWorld| async move {
+        let mut scope = world.scope();
+        let endless = scope.spawn(|world: FlowWorld| async move {
+            loop {
+                world.sleep_ticks(1).await;
+            }
+        });
+        world.insert_resource(endless.abort_handle());
+
+        let quick = scope.spawn(|world: FlowWorld| async move {
+            world.sleep_ticks(1).await;
+            7
+        });
+        quick.await.unwrap()
+    });
+
+    for _ in 0..6 {
+        flows.execute(&mut world);
+        world.expect_resource_mut::<FlowTime>().tick();
+    }
+    flows.execute(&mut world);
+
+    assert_eq!(joined.try_join(), Some(Ok(9)));
+    assert_eq!(selected.try_join(), Some(Ok((Either::Right("fast"), ()))));
+    assert_eq!(parent.try_join(), Some(Ok(7)));
+
+    // Child flow is aborted when parent's scope is dropped.
+    let endless = world.remove_resource::<AbortHandle>().unwrap();
+    assert!(endless.is_finished());
+}
+
+#[cfg(feature = "flow")]
+#[test]
+fn test_flow_budget_priorities() {
+    use crate::flow::{with_priority, FlowBudget, FlowPriority};
+
+    let mut world = World::new();
+    let mut flows = Flows::new();
+    flows.set_budget(FlowBudget::unlimited().max_polls(2));
+
+    for n in 0..3u32 {
+        world.spawn_flow(move |world: FlowWorld| async move {
+            world.map(|world| world.with_default_resource::<Vec<u32>>().push(n));
+        });
+    }
+
+    world.spawn_flow(with_priority(
+        FlowPriority::Low,
+        |world: FlowWorld| async move {
+            world.map(|world| world.with_default_resource::<Vec<u32>>().push(100));
+        },
+    ));
+
+    world.spawn_flow(with_priority(
+        FlowPriority::High,
+        |world: FlowWorld| async move {
+            world.map(|world| world.with_default_resource::<Vec<u32>>().push(10));
+        },
+    ));
+
+    // High priority flow is polled first, budget allows only two polls.
+    flows.execute(&mut world);
+    assert_eq!(*world.expect_resource::<Vec<u32>>(), [10, 0]);
+
+    // Remaining flows are carried over to the next call.
+    flows.execute(&mut world);
+    assert_eq!(*world.expect_resource::<Vec<u32>>(), [10, 0, 1, 2]);
+
+    flows.execute(&mut world);
+    assert_eq!(*world.expect_resource::<Vec<u32>>(), [10, 0, 1, 2, 100]);
+}
+
+#[cfg(feature = "flow")]
+#[test]
+fn test_flow_introspection() {
+    use crate::flow::{FlowEntity, FlowTime};
+
+    let mut world = World::new();
+    let mut flows = Flows::new();
+    world.insert_resource(FlowTime::with_step(core::time::Duration::ZERO));
+
+    let e = world.spawn(()).id();
+    let line = line!() + 1;
+    world.spawn_flow_for(e, |e: FlowEntity| async move {
+        loop {
+            e.sleep_ticks(1).await;
+        }
+    });
+
+    flows.execute(&mut world);
+    flows.execute(&mut world);
+
+    let info = flows.inspect();
+    assert_eq!(info.len(), 1);
+    assert_eq!(info[0].entity(), Some(e));
+    assert_eq!(info[0].polls(), 1);
+    assert_eq!(info[0].location().file(), file!());
+    assert_eq!(info[0].location().line(), line);
+    assert!(info[0].type_name().contains("test_flow_introspection"));
+
+    let dump = flows.dump();
+    assert!(dump.starts_with("1 live flows"));
+    assert!(dump.contains(&format!("for entity {}", e)));
+
+    world.despawn(e).unwrap();
+    flows.execute(&mut world);
+    assert!(flows.inspect().is_empty());
+}
+
+#[cfg(feature = "flow")]
+#[test]
+fn test_flow_fn() {
+    use crate::flow::{flow_fn, FlowEntity, FlowTime};
+
+    let mut world = World::new();
+    let mut flows = Flows::new();
+    world.insert_resource(FlowTime::with_step(core::time::Duration::ZERO));
+
+    let e = world.spawn((U32(1),)).id();
+
+    let step = 2;
+    let doubled = world.spawn_flow_for(
+        e,
+        flow_fn!(|e: FlowEntity| {
+            crate::yield_now!();
+            let value = e.get_cloned::<U32>().unwrap().0 * step;
+            e.insert(U32(value));
+            value
+        }),
+    );
+
+    // Awaits `doubled` so result does not depend on order of flows.
+    let mut sum = world.spawn_flow(flow_fn!(|world: FlowWorld| {
+        world.sleep_ticks(1).await;
+        doubled.await.unwrap() + world.try_get_cloned::<U32>(e).unwrap().0
+    }));
+
+    for _ in 0..3 {
+        flows.execute(&mut world);
+        world.expect_resource_mut::<FlowTime>().tick();
+    }
+
+    assert_eq!(sum.try_join(), Some(Ok(4)));
+    assert_eq!(world.try_get_cloned::<U32>(e), Ok(U32(2)));
+}
+
+#[cfg(feature = "flow")]
+#[test]
+fn test_flow_resumable() {
+    use core::task::{Context, Poll};
+
+    use crate::{
+        entity::EntityId,
+        flow::{FlowPriority, RestoreError, ResumableFlow, SavedFlow},
+    };
+
+    /// Adds entity's `U32` to the total one step at a time.
+    struct Quest {
+        target: EntityId,
+        step: u32,
+        steps: u32,
+    }
+
+    impl ResumableFlow for Quest {
+        const NAME: &'static str = "quest";
+
+        fn step(&mut self, world: FlowWorld, cx: &mut Context<'_>) -> Poll<()> {
+            if self.step == self.steps {
+                return Poll::Ready(());
+            }
+            self.step += 1;
+
+            let value = world.try_get_cloned::<U32>(self.target).unwrap().0;
+            world.map(|world| *world.with_default_resource::<u32>() += value);
+
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+
+        fn save(&self, out: &mut Vec<u8>) {
+            out.extend_from_slice(&self.target.bits().to_le_bytes());
+            out.extend_from_slice(&self.step.to_le_bytes());
+            out.extend_from_slice(&self.steps.to_le_bytes());
+        }
+
+        fn restore(data: &[u8]) -> Option<Self> {
+            let target = EntityId::from_bits(u64::from_le_bytes(data.get(0..8)?.try_into().ok()?))?;
+            let step = u32::from_le_bytes(data.get(8..12)?.try_into().ok()?);
+            let steps = u32::from_le_bytes(data.get(12..16)?.try_into().ok()?);
+            Some(Quest {
+                target,
+                step,
+                steps,
+            })
+        }
+    }
+
+    let mut world = World::new();
+    let mut flows = Flows::new();
+
+    let target = world.spawn((U32(5),)).id();
+    world.spawn_resumable(Quest {
+        target,
+        step: 0,
+        steps: 4,
+    });
+    let aborted = world.spawn_resumable(Quest {
+        target,
+        step: 0,
+        steps: 4,
+    });
+    aborted.abort();
+
+    flows.execute(&mut world);
+    assert_eq!(*world.expect_resource::<u32>(), 5);
+
+    let saved = flows.save(&mut world);
+    assert_eq!(saved.len(), 1);
+    assert_eq!(saved[0].name(), "quest");
+
+    // Restore into fresh world with the same entity.
+    let mut world2 = World::new();
+    world2.spawn_at(target, (U32(10),));
+
+    let mut flows2 = Flows::new();
+    assert_eq!(
+        flows2.restore(&mut world2, saved.clone()),
+        Err(RestoreError::UnknownFlow)
+    );
+
+    flows2.register_resumable::<Quest>();
+    assert_eq!(
+        flows2.restore(
+            &mut world2,
+            [SavedFlow::new(
+                "quest".into(),
+                FlowPriority::Normal,
+                Vec::new()
+            )]
+        ),
+        Err(RestoreError::InvalidState)
+    );
+
+    flows2.restore(&mut world2, saved).unwrap();
+    for _ in 0..4 {
+        flows2.execute(&mut world2);
+    }
+
+    // Resumed at step 1 of 4.
+    assert_eq!(*world2.expect_resource::<u32>(), 30);
+    assert!(flows2.inspect().is_empty());
+}
+
+#[cfg(feature = "flow")]
+#[test]
+fn test_flow_bound() {
+    use crate::flow::{FlowEntity, FlowTime, JoinError};
+
+    struct Chasing;
+
+    impl Component for Chasing {}
+
+    // Not a `Component`.
+    struct External;
+
+    let mut world = World::new();
+    world.ensure_external_registered::<External>();
+    let mut flows = Flows::new();
+    world.insert_resource(FlowTime::with_step(core::time::Duration::ZERO));
+
+    let endless = |e: FlowEntity| async move {
+        loop {
+            e.sleep_ticks(1).await;
+        }
+    };
+
+    let dropped = world.spawn((Chasing,)).id();
+    let replaced = world.spawn((Chasing,)).id();
+    let kept = world.spawn((Chasing,)).id();
+    let missing = world.spawn(()).id();
+    let early = world.spawn((Chasing,)).id();
+    let external = world.spawn_external((External,)).id();
+
+    let mut dropped_flow = world.spawn_flow_bound::<Chasing, _>(dropped, endless);
+    let mut replaced_flow = world.spawn_flow_bound::<Chasing, _>(replaced, endless);
+    let mut kept_flow = world.spawn_flow_bound::<Chasing, _>(kept, endless);
+    let mut finished_flow =
+        world.spawn_flow_bound::<Chasing, _>(kept, |e: FlowEntity| async move {
+            e.sleep_ticks(1).await;
+            e.id()
+        });
+    let mut missing_flow = world.spawn_flow_bound::<Chasing, _>(missing, endless);
+    let mut early_flow = world.spawn_flow_bound::<Chasing, _>(early, endless);
+    let mut external_flow = world.spawn_flow_bound::<External, _>(external, endless);
+
+    // Component replaced before flow is polled.
+    world.insert(early, Chasing).unwrap();
+
+    let epoch = world.epoch();
+    flows.execute(&mut world);
+    assert_eq!(missing_flow.try_join(), Some(Err(JoinError::Cancelled)));
+    assert_eq!(early_flow.try_join(), Some(Err(JoinError::Cancelled)));
+
+    // Binding flows does not modify components.
+    let modified = world.view_with(Modified::<crate::query::Read<Chasing>>::new(epoch));
+    assert_eq!(modified.into_iter().count(), 0);
+
+    world.drop::<Chasing>(dropped).unwrap();
+    world.insert(replaced, Chasing).unwrap();
+
+    // Moving entity to another archetype keeps the binding.
+    world.insert(kept, U32(1)).unwrap();
+    world.drop::<External>(external).unwrap();
+    world.expect_resource_mut::<FlowTime>().tick();
+    flows.execute(&mut world);
+
+    assert_eq!(dropped_flow.try_join(), Some(Err(JoinError::Cancelled)));
+    assert_eq!(replaced_flow.try_join(), Some(Err(JoinError::Cancelled)));
+    assert_eq!(finished_flow.try_join(), Some(Ok(kept)));
+    assert_eq!(kept_flow.try_join(), None);
+    assert_eq!(external_flow.try_join(), Some(Err(JoinError::Cancelled)));
+
+    // Finished flow is dropped, kept flow is still bound.
+    assert_eq!(flows.inspect().len(), 1);
+    world.drop::<Chasing>(kept).unwrap();
+    flows.execute(&mut world);
+    assert_eq!(kept_flow.try_join(), Some(Err(JoinError::Cancelled)));
+}
+
+#[cfg(all(feature = "flow", feature = "threaded-scheduler"))]
+#[test]
+fn test_flow_offload() {
+    use crate::flow::{FlowWorld, JoinError};
+
+    let mut world = World::new();
+    let mut flows = Flows::new();
+
+    let e = world.spawn((U32(10),)).id();
+
+    let mut sum = world.spawn_flow(move |world: FlowWorld| async move {
+        world
+            .offload(move |world| async move {
+                let n = world
+                    .on_world(move |world| world.try_get_cloned::<U32>(e).unwrap().0)
+                    .await;
+
+                // Runs on worker thread.
+                let sum = (1..=n).sum::<u32>();
+
+                world
+                    .on_world(move |world| world.insert(e, U32(sum)).unwrap())
+                    .await;
+                sum
+            })
+            .await
+    });
+
+    let mut aborted = world.spawn_flow(|world: FlowWorld| async move {
+        let handle = world.offload(|_| core::future::pending::<()>());
+        handle.abort();
+        handle.await
+    });
+
+    // Offloaded futures run past `execute_threaded` calls.
+    // Park until they request world access or the flows may progress.
+    struct Unpark(std::thread::Thread);
+
+    impl std::task::Wake for Unpark {
+        fn wake(self: std::sync::Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    flows.set_offload_waker(
+        &mut world,
+        std::sync::Arc::new(Unpark(std::thread::current())).into(),
+    );
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while !(sum.is_finished() && aborted.is_finished()) {
+        assert!(std::time::Instant::now() < deadline);
+        flows.execute_threaded(&mut world);
+        std::thread::park_timeout(std::time::Duration::from_millis(10));
+    }
+
+    assert_eq!(sum.try_join(), Some(Ok(Ok(55))));
+    assert_eq!(world.get::<&U32>(e).unwrap().0, 55);
+    assert_eq!(aborted.try_join(), Some(Ok(Err(JoinError::Aborted))));
+}
+
+#[test]
+fn test_aliasing_borrows() {
     let mut world = World::new();
 
     world.spawn_one(U32(42));
@@ -618,3 +1888,669 @@ fn test_aliasing_borrows() {
 
     system.into_system().run_alone(&mut world);
 }
+
+#[test]
+fn test_pairs() {
+    let mut world = World::new();
+
+    let likes = world.spawn(()).id();
+    let owes = world.spawn(()).id();
+
+    let a = world.spawn(()).id();
+    let b = world.spawn(()).id();
+    let c = world.spawn(()).id();
+
+    world.insert_pair(a, likes, b).unwrap();
+    world.insert_pair(a, owes, b).unwrap();
+    world.insert_pair(a, likes, c).unwrap();
+    world.insert_pair(c, owes, b).unwrap();
+
+    // Re-inserting pair of the same kind does not duplicate it.
+    world.insert_pair(a, likes, b).unwrap();
+
+    let view = world.view_with(RelatesPair::new(likes));
+    let mut liked = view.try_get(a).unwrap().map(|e| e.id()).collect::<Vec<_>>();
+    liked.sort();
+    assert_eq!(liked, vec![b, c]);
+    assert!(view.try_get(c).is_err());
+    drop(view);
+
+    let view = world.view_with(RelatedPair::new(owes));
+    let mut owed = view.try_get(b).unwrap().map(|e| e.id()).collect::<Vec<_>>();
+    owed.sort();
+    assert_eq!(owed, vec![a, c]);
+    assert!(view.try_get(c).is_err());
+    drop(view);
+
+    assert_eq!(world.remove_pair(a, owes, b), Ok(true));
+    assert_eq!(world.remove_pair(a, owes, b), Ok(false));
+
+    let view = world.view_with(RelatesPair::new(likes));
+    assert_eq!(view.try_get(a).unwrap().count(), 2);
+    drop(view);
+
+    let view = world.view_with(RelatedPair::new(owes));
+    let owed = view.try_get(b).unwrap().map(|e| e.id()).collect::<Vec<_>>();
+    assert_eq!(owed, vec![c]);
+    drop(view);
+
+    world.despawn(b).unwrap();
+
+    let view = world.view_with(RelatesPair::new(likes));
+    let liked = view.try_get(a).unwrap().map(|e| e.id()).collect::<Vec<_>>();
+    assert_eq!(liked, vec![c]);
+    drop(view);
+
+    let view = world.view_with(RelatesPair::new(owes));
+    assert!(view.try_get(c).is_err());
+}
+
+#[test]
+fn test_remove_pair_relation_in_sync() {
+    use crate::relation::Pair;
+
+    let mut world = World::new();
+
+    let k1 = world.spawn(()).id();
+    let k2 = world.spawn(()).id();
+    let k9 = world.spawn(()).id();
+
+    let o = world.spawn(()).id();
+    let t = world.spawn(()).id();
+    let u = world.spawn(()).id();
+
+    world.insert_pair(o, k9, u).unwrap();
+    world.insert_pair(o, k1, t).unwrap();
+    world.insert_pair(o, k2, t).unwrap();
+
+    // Swap-removal reorders origin side only.
+    assert_eq!(world.remove_pair(o, k9, u), Ok(true));
+
+    let removed = world.remove_relation::<Pair>(o, t).unwrap().unwrap();
+    let kept = if removed.kind() == k1 { k2 } else { k1 };
+
+    let view = world.view_with(RelatesPair::new(kept));
+    assert_eq!(
+        view.try_get(o).unwrap().map(|e| e.id()).collect::<Vec<_>>(),
+        vec![t]
+    );
+    drop(view);
+
+    let view = world.view_with(RelatedPair::new(kept));
+    assert_eq!(
+        view.try_get(t).unwrap().map(|e| e.id()).collect::<Vec<_>>(),
+        vec![o]
+    );
+    drop(view);
+
+    assert_eq!(world.remove_pair(o, kept, t), Ok(true));
+
+    // No dangling target entry is left.
+    assert!(world.view_with(RelatedPair::new(k1)).try_get(t).is_err());
+    assert!(world.view_with(RelatedPair::new(k2)).try_get(t).is_err());
+}
+
+#[test]
+fn test_relation_paths() {
+    use crate::{
+        query::With,
+        relation::{FilterRelatesTo, Follow, FollowView, Matches, Related},
+    };
+
+    #[derive(Clone, Copy, Debug)]
+    struct Targets;
+
+    impl Relation for Targets {}
+
+    #[derive(Clone, Copy, Debug)]
+    struct OwnedBy;
+
+    impl Relation for OwnedBy {
+        const EXCLUSIVE: bool = true;
+    }
+
+    let mut world = World::new();
+
+    let player_x = world.spawn((U32(1),)).id();
+    let player_y = world.spawn(()).id();
+
+    let enemy_x = world.spawn((Str("enemy"),)).id();
+    let enemy_y = world.spawn((Str("enemy"),)).id();
+    let ally_x = world.spawn(()).id();
+
+    world.insert_relation(enemy_x, OwnedBy, player_x).unwrap();
+    world.insert_relation(enemy_y, OwnedBy, player_y).unwrap();
+    world.insert_relation(ally_x, OwnedBy, player_x).unwrap();
+
+    let unit_a = world.spawn(()).id();
+    let unit_b = world.spawn(()).id();
+    let unit_c = world.spawn(()).id();
+
+    world.insert_relation(unit_a, Targets, enemy_y).unwrap();
+    world.insert_relation(unit_a, Targets, enemy_x).unwrap();
+    world.insert_relation(unit_b, Targets, enemy_y).unwrap();
+    world.insert_relation(unit_c, Targets, ally_x).unwrap();
+
+    // Units that target an enemy owned by player X.
+    let mut path = world.follow::<Targets, _>(
+        1..=1,
+        Matches((
+            With::<Str>::new(),
+            FilterRelatesTo::<OwnedBy>::new(player_x),
+        )),
+    );
+
+    assert_eq!(path.get(unit_a), Some(&[unit_a, enemy_x][..]));
+    assert_eq!(path.get(unit_b), None);
+    assert_eq!(path.get(unit_c), None);
+
+    let mut paths = Vec::new();
+    path.for_each(|found| paths.push(found.to_vec()));
+    assert_eq!(paths, vec![vec![unit_a, enemy_x]]);
+    drop(path);
+
+    // Units that target anything owned by player with `U32`, chaining two relations.
+    let mut path = world.follow::<Targets, _>(
+        1..=1,
+        Follow::new(
+            Relates::<With<OwnedBy>>::new(),
+            1..=1,
+            Matches(With::<U32>::new()),
+        ),
+    );
+
+    assert_eq!(path.get(unit_a), Some(&[unit_a, enemy_x, player_x][..]));
+    assert_eq!(path.get(unit_b), None);
+    assert_eq!(path.get(unit_c), Some(&[unit_c, ally_x, player_x][..]));
+    drop(path);
+
+    // Reverse direction: units targeting entities owned by player X.
+    let mut path = world.follow_back::<OwnedBy, _>(
+        1..=1,
+        Follow::new(Related::<With<Targets>>::new(), 1..=1, ()),
+    );
+    assert_eq!(path.get(player_x), Some(&[player_x, enemy_x, unit_a][..]));
+    assert_eq!(path.get(player_y), Some(&[player_y, enemy_y, unit_a][..]));
+    assert_eq!(path.get(unit_a), None);
+    drop(path);
+
+    // Hop ranges.
+    let root = world.spawn((U32(0),)).id();
+    let child = world.spawn(()).id();
+    let grandchild = world.spawn(()).id();
+    world.insert_relation(child, ChildOf, root).unwrap();
+    world.insert_relation(grandchild, ChildOf, child).unwrap();
+
+    let mut path = world.follow::<ChildOf, _>(2..=2, Matches(With::<U32>::new()));
+    assert_eq!(path.get(grandchild), Some(&[grandchild, child, root][..]));
+    assert_eq!(path.get(child), None);
+    drop(path);
+
+    assert_eq!(
+        world.follow::<ChildOf, _>(.., ()).get(grandchild),
+        Some(&[grandchild][..])
+    );
+    assert_eq!(
+        world.follow::<ChildOf, _>(1.., ()).get(grandchild),
+        Some(&[grandchild, child][..])
+    );
+
+    // Paths in function-systems.
+    type Grandparent<'a> =
+        FollowView<'a, Relates<With<ChildOf>>, Follow<Relates<With<ChildOf>>, Matches<With<U32>>>>;
+
+    fn grandparent_with_u32(mut view: Grandparent<'_>) {
+        let mut paths = Vec::new();
+        view.for_each(|path| paths.push(path.to_vec()));
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].len(), 3);
+    }
+
+    grandparent_with_u32.into_system().run_alone(&mut world);
+
+    // Each entity is visited once per walk,
+    // so graphs with exponential number of paths are walked in linear time.
+    let first = [world.spawn(()).id(), world.spawn(()).id()];
+    let mut layer = first;
+    for _ in 0..40 {
+        let next = [world.spawn(()).id(), world.spawn(()).id()];
+        for origin in layer {
+            for target in next {
+                world.insert_relation(origin, Targets, target).unwrap();
+            }
+        }
+        layer = next;
+    }
+    world.insert(layer[1], U32(2)).unwrap();
+
+    let mut path = world.follow::<Targets, _>(.., Matches(With::<U32>::new()));
+    let found = path.get(first[0]).unwrap();
+    assert_eq!(found.len(), 41);
+    assert_eq!(found[0], first[0]);
+    assert_eq!(found[40], layer[1]);
+    drop(path);
+
+    let mut path = world.follow::<Targets, _>(.., Matches(With::<Str>::new()));
+    assert_eq!(path.get(first[0]), None);
+}
+
+#[test]
+fn test_despawn_policies() {
+    use crate::DespawnError;
+
+    #[derive(Component)]
+    struct Aiming;
+
+    // Despawning the driver despawns the vehicle.
+    #[derive(Clone, Copy, Relation)]
+    #[edict(exclusive, on_origin_despawn = despawn)]
+    struct Drives;
+
+    // Aiming unit loses `Aiming` component when target is gone.
+    #[derive(Clone, Copy, Relation)]
+    #[edict(on_target_despawn = drop(Aiming))]
+    struct AimsAt;
+
+    // Containers can't be despawned while they have items.
+    #[derive(Clone, Copy, Relation)]
+    #[edict(exclusive, on_target_despawn = deny)]
+    struct StoredIn;
+
+    let mut world = World::new();
+
+    let driver = world.spawn(()).id();
+    let vehicle = world.spawn(()).id();
+    world.insert_relation(driver, Drives, vehicle).unwrap();
+
+    world.despawn(driver).unwrap();
+    assert!(!world.is_alive(vehicle));
+
+    let shooter = world.spawn((Aiming,)).id();
+    let target = world.spawn(()).id();
+    world.insert_relation(shooter, AimsAt, target).unwrap();
+
+    world.despawn(target).unwrap();
+    assert!(world.is_alive(shooter));
+    assert_eq!(world.try_has_component::<Aiming>(shooter), Ok(false));
+
+    let item = world.spawn(()).id();
+    let container = world.spawn(()).id();
+    world.insert_relation(item, StoredIn, container).unwrap();
+
+    assert_eq!(world.try_despawn(container), Err(DespawnError::Denied));
+    assert_eq!(
+        world.entity(container).unwrap().try_despawn(),
+        Err(DespawnError::Denied)
+    );
+
+    // Non-reporting methods leave protected entity alive.
+    assert_eq!(world.despawn(container), Ok(()));
+    world.entity(container).unwrap().despawn();
+    world.despawn_batch([container]);
+    assert!(world.is_alive(container));
+
+    // Batch is checked before anything is despawned.
+    let other = world.spawn(()).id();
+    assert_eq!(
+        world.try_despawn_batch([other, container]),
+        Err(DespawnError::Denied)
+    );
+    assert!(world.is_alive(other));
+    assert!(world.is_alive(container));
+
+    // Deferred and cascading despawns skip protected entities.
+    world.local().despawn_defer(container);
+    world.run_deferred();
+    assert!(world.is_alive(container));
+
+    let driver = world.spawn(()).id();
+    world.insert_relation(driver, Drives, container).unwrap();
+    world.despawn(driver).unwrap();
+    assert!(world.is_alive(container));
+
+    world.despawn(item).unwrap();
+    assert_eq!(world.try_despawn_batch([other, container]), Ok(()));
+    assert!(!world.is_alive(other));
+    assert!(!world.is_alive(container));
+}
+
+#[test]
+fn test_ordered_relations() {
+    use crate::relation::Related;
+
+    #[derive(Clone, Copy, Relation)]
+    #[edict(exclusive, ordered)]
+    struct SlotOf;
+
+    let mut world = World::new();
+
+    let parent = world.spawn(()).id();
+    let a = world.spawn((U32(3),)).id();
+    let b = world.spawn((U32(1),)).id();
+    let c = world.spawn((U32(2),)).id();
+    let d = world.spawn((U32(0),)).id();
+
+    world.insert_relation(a, SlotOf, parent).unwrap();
+    world.insert_relation(b, SlotOf, parent).unwrap();
+    world.insert_relation(c, SlotOf, parent).unwrap();
+
+    let children = |world: &World| {
+        world
+            .view::<Related<With<SlotOf>>>()
+            .try_get(parent)
+            .map(|children| children.map(|e| e.id()).collect::<Vec<_>>())
+            .unwrap_or_default()
+    };
+
+    assert_eq!(children(&world), vec![a, b, c]);
+
+    world.remove_relation::<SlotOf>(a, parent).unwrap();
+    assert_eq!(children(&world), vec![b, c]);
+
+    world.insert_relation_at(d, SlotOf, parent, 0).unwrap();
+    world.insert_relation_at(a, SlotOf, parent, 1).unwrap();
+    assert_eq!(children(&world), vec![d, a, b, c]);
+
+    assert_eq!(world.move_relation::<SlotOf>(d, parent, 2), Ok(true));
+    assert_eq!(children(&world), vec![a, b, d, c]);
+
+    assert_eq!(world.move_relation::<SlotOf>(c, parent, 0), Ok(true));
+    assert_eq!(children(&world), vec![c, a, b, d]);
+
+    assert_eq!(world.move_relation::<SlotOf>(parent, c, 0), Ok(false));
+
+    let keys = [a, b, c, d].map(|e| (e, world.get::<&U32>(e).unwrap().0));
+    world
+        .sort_related_by::<SlotOf>(parent, |x, y| {
+            let key = |e| keys.iter().find(|k| k.0 == e).unwrap().1;
+            key(x.0).cmp(&key(y.0))
+        })
+        .unwrap();
+    assert_eq!(children(&world), vec![d, b, c, a]);
+
+    world.despawn(b).unwrap();
+    assert_eq!(children(&world), vec![d, c, a]);
+}
+
+#[test]
+fn test_relation_events() {
+    use crate::{
+        action::LocalActionEncoder,
+        entity::EntityId,
+        event::EventCursor,
+        relation::{RelationEvent, RelationEventKind, RelationEvents},
+    };
+
+    #[derive(Clone, Copy, Relation)]
+    #[edict(exclusive, on_insert = connected)]
+    struct PoweredBy;
+
+    fn connected(_: &PoweredBy, origin: EntityId, _: EntityId, mut encoder: LocalActionEncoder) {
+        encoder.insert(origin, Bool(true));
+    }
+
+    let inserted = |origin, target| RelationEvent {
+        kind: RelationEventKind::Inserted,
+        origin,
+        target,
+    };
+    let removed = |origin, target| RelationEvent {
+        kind: RelationEventKind::Removed,
+        origin,
+        target,
+    };
+
+    let mut world = World::new();
+    world.track_relation_events::<PoweredBy>();
+
+    let a = world.spawn(()).id();
+    let b = world.spawn(()).id();
+    let c = world.spawn(()).id();
+
+    let mut cursor = EventCursor::default();
+
+    world.insert_relation(a, PoweredBy, b).unwrap();
+    assert_eq!(world.get::<&Bool>(a).unwrap(), &Bool(true));
+
+    // Replacing value does not produce events.
+    world.insert_relation(a, PoweredBy, b).unwrap();
+
+    world.insert_relation(a, PoweredBy, c).unwrap();
+    world.insert_relation(b, PoweredBy, c).unwrap();
+
+    assert_eq!(
+        world
+            .expect_resource::<RelationEvents<PoweredBy>>()
+            .read(&mut cursor),
+        [
+            inserted(a, b),
+            removed(a, b),
+            inserted(a, c),
+            inserted(b, c)
+        ]
+    );
+
+    world.remove_relation::<PoweredBy>(a, c).unwrap();
+    world.despawn(c).unwrap();
+
+    let mut events = world.expect_resource_mut::<RelationEvents<PoweredBy>>();
+    assert_eq!(events.read(&mut cursor), [removed(a, c), removed(b, c)]);
+    assert!(events.read(&mut cursor).is_empty());
+
+    let mut late = EventCursor::default();
+    events.clear();
+    assert!(events.is_empty());
+    assert!(events.read(&mut late).is_empty());
+    assert_eq!(late, events.cursor());
+}
+
+#[test]
+fn test_relation_derive_hooks() {
+    use crate::{action::LocalActionEncoder, entity::EntityId};
+
+    type Log = Vec<(&'static str, EntityId, u32)>;
+
+    fn log(mut encoder: LocalActionEncoder, hook: &'static str, entity: EntityId, value: u32) {
+        encoder.closure(move |world| {
+            world
+                .with_default_resource::<Log>()
+                .push((hook, entity, value));
+        });
+    }
+
+    #[derive(Clone, Copy, Relation)]
+    #[edict(
+        on_replace = replaced,
+        on_drop = dropped,
+        on_origin_drop = origin_dropped,
+        on_target_drop = target_dropped
+    )]
+    struct Wired(u32);
+
+    fn replaced(
+        old: &mut Wired,
+        new: &Wired,
+        origin: EntityId,
+        _: EntityId,
+        _: EntityId,
+        encoder: LocalActionEncoder,
+    ) -> bool {
+        log(encoder, "replaced", origin, old.0 * 10 + new.0);
+        true
+    }
+
+    fn dropped(wired: Wired, origin: EntityId, _: EntityId, encoder: LocalActionEncoder) {
+        log(encoder, "dropped", origin, wired.0);
+    }
+
+    fn origin_dropped(_: EntityId, targets: &[(EntityId, Wired)], mut encoder: LocalActionEncoder) {
+        for &(target, wired) in targets {
+            log(encoder.reborrow(), "origin_dropped", target, wired.0);
+        }
+    }
+
+    fn target_dropped(origins: &[(EntityId, Wired)], _: EntityId, mut encoder: LocalActionEncoder) {
+        for &(origin, wired) in origins {
+            log(encoder.reborrow(), "target_dropped", origin, wired.0);
+        }
+    }
+
+    let mut world = World::new();
+    let a = world.spawn(()).id();
+    let b = world.spawn(()).id();
+    let c = world.spawn(()).id();
+
+    world.insert_relation(a, Wired(1), b).unwrap();
+    world.insert_relation(a, Wired(2), b).unwrap();
+    world.insert_relation(c, Wired(3), a).unwrap();
+    world.despawn(a).unwrap();
+
+    let mut log = world.remove_resource::<Log>().unwrap();
+    log.sort();
+    assert_eq!(
+        log,
+        [
+            ("dropped", a, 1),
+            ("origin_dropped", b, 2),
+            ("replaced", a, 12),
+            ("target_dropped", c, 3),
+        ]
+    );
+}
+
+#[test]
+fn test_relation_graph() {
+    use crate::relation::graph;
+
+    #[derive(Clone, Copy, Debug)]
+    struct DependsOn;
+
+    impl Relation for DependsOn {}
+
+    let mut world = World::new();
+
+    let a = world.spawn(()).id();
+    let b = world.spawn(()).id();
+    let c = world.spawn(()).id();
+    let d = world.spawn(()).id();
+    let e = world.spawn(()).id();
+    let f = world.spawn(()).id();
+
+    // a -> b -> d, a -> c -> d, e -> f
+    world.insert_relation(a, DependsOn, b).unwrap();
+    world.insert_relation(a, DependsOn, c).unwrap();
+    world.insert_relation(b, DependsOn, d).unwrap();
+    world.insert_relation(c, DependsOn, d).unwrap();
+    world.insert_relation(e, DependsOn, f).unwrap();
+
+    let mut components = graph::connected_components::<DependsOn>(&world);
+    for component in &mut components {
+        component.sort();
+    }
+    components.sort();
+    assert_eq!(components, vec![vec![a, b, c, d], vec![e, f]]);
+
+    let path = graph::shortest_path::<DependsOn>(&world, a, d).unwrap();
+    assert_eq!(path.len(), 3);
+    assert_eq!((path[0], path[2]), (a, d));
+    assert_eq!(graph::shortest_path::<DependsOn>(&world, d, a), None);
+    assert_eq!(graph::shortest_path::<DependsOn>(&world, a, f), None);
+
+    let sorted = graph::toposort::<DependsOn>(&world).unwrap();
+    let position = |x| sorted.iter().position(|&y| y == x).unwrap();
+    assert_eq!(sorted.len(), 6);
+    assert!(position(d) < position(b) && position(d) < position(c));
+    assert!(position(b) < position(a) && position(c) < position(a));
+    assert!(position(f) < position(e));
+    assert_eq!(graph::find_cycle::<DependsOn>(&world), None);
+
+    // d -> a closes cycles a -> b -> d -> a and a -> c -> d -> a.
+    world.insert_relation(d, DependsOn, a).unwrap();
+
+    let cycle = graph::find_cycle::<DependsOn>(&world).unwrap();
+    assert_eq!(cycle.len(), 3);
+    assert!(cycle.contains(&a) && cycle.contains(&d));
+
+    let err = graph::toposort::<DependsOn>(&world).unwrap_err();
+    assert_eq!(err.cycle().len(), 3);
+
+    // Hierarchy is acyclic.
+    let root = world.spawn(()).id();
+    let child = world.spawn(()).id();
+    world.insert_relation(child, ChildOf, root).unwrap();
+    assert_eq!(graph::find_cycle::<ChildOf>(&world), None);
+    assert_eq!(
+        graph::toposort::<ChildOf>(&world).unwrap(),
+        vec![root, child]
+    );
+}
+
+#[test]
+fn test_remote_links() {
+    use crate::world::RemoteLinks;
+
+    let mut ui = World::new();
+    let mut sim = World::new();
+
+    let button = ui.spawn(()).id();
+    let label = ui.spawn(()).id();
+    let unit = sim.spawn(()).id();
+
+    ui.link_remote(button, &mut sim, unit).unwrap();
+    ui.link_remote(button, &mut sim, unit).unwrap();
+    ui.link_remote(label, &mut sim, unit).unwrap();
+
+    let to_unit = ui
+        .get::<&RemoteLinks>(button)
+        .unwrap()
+        .iter()
+        .next()
+        .unwrap()
+        .clone();
+    assert_eq!(ui.get::<&RemoteLinks>(button).unwrap().len(), 1);
+    assert_eq!(to_unit.id(), unit);
+    assert!(to_unit.belongs_to(&sim));
+    assert!(!to_unit.belongs_to(&ui));
+
+    let remotes = sim
+        .get::<&RemoteLinks>(unit)
+        .unwrap()
+        .iter()
+        .map(|link| link.id())
+        .collect::<Vec<_>>();
+    assert_eq!(remotes, [button, label]);
+
+    // Unlinking breaks link immediately and cleans other side on action execution.
+    assert_eq!(ui.unlink_remote(label, &to_unit), Ok(false));
+    let to_label = ui
+        .get::<&RemoteLinks>(label)
+        .unwrap()
+        .iter()
+        .next()
+        .unwrap()
+        .clone();
+    assert_eq!(ui.unlink_remote(label, &to_label), Ok(true));
+    assert!(!to_label.is_alive());
+    assert!(ui.get::<&RemoteLinks>(label).is_err());
+    assert_eq!(sim.get::<&RemoteLinks>(unit).unwrap().len(), 2);
+    sim.execute_received_actions();
+    assert_eq!(sim.get::<&RemoteLinks>(unit).unwrap().len(), 1);
+
+    // Despawning either side breaks the link.
+    sim.despawn(unit).unwrap();
+    assert!(!to_unit.is_alive());
+    assert_eq!(ui.get::<&RemoteLinks>(button).unwrap().len(), 1);
+    ui.execute_received_actions();
+    assert!(ui.get::<&RemoteLinks>(button).is_err());
+    assert!(ui.is_alive(button));
+
+    let unit = sim.spawn(()).id();
+    ui.link_remote(button, &mut sim, unit).unwrap();
+    ui.despawn(button).unwrap();
+    sim.execute_received_actions();
+    assert!(sim.get::<&RemoteLinks>(unit).is_err());
+
+    let missing = sim.spawn(()).id();
+    sim.despawn(missing).unwrap();
+    assert!(ui.link_remote(label, &mut sim, missing).is_err());
+}
@@ -339,6 +339,71 @@ where
     ) -> ViewValue<'a, TupleQueryAdd<Q, Related<Write<R>>>, F, B, Extensible> {
         self.extend(Related::<Write<R>>)
     }
+
+    /// Queries for origin entities in relation of type `R`
+    /// that were modified after the `after_epoch`.
+    /// The view will contain shared reference of the relation value
+    /// and the target entity.
+    #[inline]
+    pub fn modified_relates_ref<R: Relation + Sync>(
+        self,
+        after_epoch: EpochId,
+    ) -> ViewValue<'a, TupleQueryAdd<Q, Modified<Relates<&'a R>>>, F, B, Extensible> {
+        self.extend(Modified::<Relates<Read<R>>>::new(after_epoch))
+    }
+
+    /// Queries for origin entities in relation of type `R`
+    /// that were modified after the `after_epoch`.
+    /// The view will contain mutable reference of the relation value
+    /// and the target entity.
+    #[inline]
+    pub fn modified_relates_mut<R: Relation + Send>(
+        self,
+        after_epoch: EpochId,
+    ) -> ViewValue<'a, TupleQueryAdd<Q, Modified<Relates<&'a mut R>>>, F, B, Extensible> {
+        self.extend(Modified::<Relates<Write<R>>>::new(after_epoch))
+    }
+
+    /// Queries for origin entities in relation of type `R` with specified target
+    /// that were modified after the `after_epoch`.
+    /// The view will contain shared reference of the relation value.
+    #[inline]
+    pub fn modified_relates_to<R: Relation + Sync>(
+        self,
+        target: impl Entity,
+        after_epoch: EpochId,
+    ) -> ViewValue<'a, TupleQueryAdd<Q, Modified<RelatesTo<&'a R>>>, F, B, Extensible> {
+        self.extend(Modified::with_query(
+            after_epoch,
+            RelatesTo::<Read<R>>::new(target.id()),
+        ))
+    }
+
+    /// Queries for origin entities in relation of type `R` with specified target
+    /// that were modified after the `after_epoch`.
+    /// The view will contain mutable reference of the relation value.
+    #[inline]
+    pub fn modified_relates_to_mut<R: Relation + Send>(
+        self,
+        target: impl Entity,
+        after_epoch: EpochId,
+    ) -> ViewValue<'a, TupleQueryAdd<Q, Modified<RelatesTo<&'a mut R>>>, F, B, Extensible> {
+        self.extend(Modified::with_query(
+            after_epoch,
+            RelatesTo::<Write<R>>::new(target.id()),
+        ))
+    }
+
+    /// Queries for target entities in relation of type `R`
+    /// that were modified after the `after_epoch`.
+    /// The view will contain origins of the relation.
+    #[inline]
+    pub fn modified_related_ref<R: Relation + Sync>(
+        self,
+        after_epoch: EpochId,
+    ) -> ViewValue<'a, TupleQueryAdd<Q, Modified<Related<&'a R>>>, F, B, Extensible> {
+        self.extend(Modified::<Related<Read<R>>>::new(after_epoch))
+    }
 }
 
 impl<'a, Q, F, B> ViewValue<'a, Q, F, B, Extensible>
@@ -439,4 +504,26 @@ where
     ) -> ViewValue<'a, Q, TupleQueryAdd<F, FilterRelatesTo<R>>, B, Extensible> {
         self.filter(FilterRelatesTo::new(target.id()))
     }
+
+    /// Filters origin entities in relation of type `R`
+    /// which relations were modified after the `after_epoch`.
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    pub fn filter_modified_relates<R: Relation>(
+        self,
+        after_epoch: EpochId,
+    ) -> ViewValue<'a, Q, TupleQueryAdd<F, Modified<Relates<With<R>>>>, B, Extensible> {
+        self.filter(Modified::<Relates<With<R>>>::new(after_epoch))
+    }
+
+    /// Filters target entities in relation of type `R`
+    /// which relations were modified after the `after_epoch`.
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    pub fn filter_modified_related<R: Relation>(
+        self,
+        after_epoch: EpochId,
+    ) -> ViewValue<'a, Q, TupleQueryAdd<F, Modified<Related<With<R>>>>, B, Extensible> {
+        self.filter(Modified::<Related<With<R>>>::new(after_epoch))
+    }
 }
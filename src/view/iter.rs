@@ -395,6 +395,10 @@ where
     ///
     /// Unlike `iter`, this version works for views with mutable queries
     /// since mutable borrow won't allow to iterate the view multiple times simultaneously.
+    ///
+    /// Batches cover all rows of matching archetypes.
+    /// Entities are not filtered within a batch, e.g. [`Modified`](crate::query::Modified)
+    /// yields a [`ModifiedMask`](crate::query::ModifiedMask) of modified rows instead.
     #[inline]
     pub fn iter_batched_mut(&mut self, batch_size: u32) -> ViewBatchIter<'_, Q, F> {
         let epoch = self.epochs.next_if(Q::MUTABLE || F::MUTABLE);
@@ -427,6 +431,10 @@ where
     /// Unlike `iter_mut`, this version only works for views with immutable queries.
     /// Immutable query are guaranteed to not conflict with any other immutable query,
     /// allowing for iterating a view multiple times simultaneously.
+    ///
+    /// Batches cover all rows of matching archetypes.
+    /// Entities are not filtered within a batch, e.g. [`Modified`](crate::query::Modified)
+    /// yields a [`ModifiedMask`](crate::query::ModifiedMask) of modified rows instead.
     #[inline]
     pub fn iter_batched(&self, batch_size: u32) -> ViewBatchIter<'_, Q, F> {
         debug_assert!(!Q::MUTABLE && !F::MUTABLE);
@@ -456,6 +464,10 @@ where
     B: BorrowState,
 {
     /// Returns an iterator over entities with a query `Q` and filter `F`.
+    ///
+    /// Batches cover all rows of matching archetypes.
+    /// Entities are not filtered within a batch, e.g. [`Modified`](crate::query::Modified)
+    /// yields a [`ModifiedMask`](crate::query::ModifiedMask) of modified rows instead.
     #[inline]
     pub fn into_iter_batched(self, batch_size: u32) -> ViewValueBatchIter<'a, Q, F, B> {
         let epoch = self.epochs.next_if(Q::MUTABLE || F::MUTABLE);
@@ -472,7 +484,9 @@ where
 }
 
 /// Iterator over entities with a query `Q`.
-/// Yields query items for every matching entity.
+/// Yields query batches for every matching archetype.
+///
+/// Entities are not filtered within a batch.
 pub struct ViewValueBatchIter<'a, Q: BatchQuery, F: BatchQuery, B: BorrowState> {
     query: Q,
     filter: F,
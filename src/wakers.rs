@@ -0,0 +1,146 @@
+//! Wakers woken when parts of the world change.
+//!
+//! Archetypes, their components and resources keep lists of wakers
+//! registered by waiting flows.
+//! Lists are woken and cleared when entities are added to or removed from
+//! archetype, when components are borrowed mutably
+//! and when resources are inserted, removed or borrowed mutably.
+//! Components also keep per-entity lists woken when component value
+//! of the entity is dropped or replaced.
+
+use core::task::Waker;
+
+#[cfg(feature = "flow")]
+use alloc::sync::Arc;
+use alloc::{sync::Weak, vec::Vec};
+
+use crate::entity::EntityId;
+
+/// Identifies registrations of one waiting future.
+///
+/// Registering a waker with the same token replaces previous registration
+/// in the same list.
+/// Registrations are discarded once the token is dropped.
+#[cfg(feature = "flow")]
+#[derive(Default)]
+pub(crate) struct WakeToken(Arc<()>);
+
+/// List of wakers to wake on a change.
+#[derive(Default)]
+pub(crate) struct Wakers {
+    wakers: Vec<(Weak<()>, Waker)>,
+}
+
+impl Wakers {
+    /// Registers waker to be woken on next change.
+    #[cfg(feature = "flow")]
+    pub fn register(&mut self, token: &WakeToken, waker: &Waker) {
+        // Discard registrations of dropped futures.
+        self.wakers.retain(|(t, _)| t.strong_count() > 0);
+
+        match self
+            .wakers
+            .iter_mut()
+            .find(|(t, _)| t.as_ptr() == Arc::as_ptr(&token.0))
+        {
+            Some((_, w)) => {
+                if !w.will_wake(waker) {
+                    *w = waker.clone();
+                }
+            }
+            None => self.wakers.push((Arc::downgrade(&token.0), waker.clone())),
+        }
+    }
+
+    /// Wakes and removes all registered wakers.
+    #[inline]
+    pub fn wake(&mut self) {
+        if !self.wakers.is_empty() {
+            self.wake_all();
+        }
+    }
+
+    #[cold]
+    fn wake_all(&mut self) {
+        for (token, waker) in self.wakers.drain(..) {
+            if token.strong_count() > 0 {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// List of wakers to wake when component of an entity
+/// is dropped or replaced.
+#[derive(Default)]
+pub(crate) struct EntityWakers {
+    wakers: Vec<(EntityId, Weak<()>, Waker)>,
+}
+
+impl EntityWakers {
+    /// Registers waker to be woken when component of the entity
+    /// is dropped or replaced.
+    #[cfg(feature = "flow")]
+    pub fn register(&mut self, entity: EntityId, token: &WakeToken, waker: &Waker) {
+        // Discard registrations of dropped futures.
+        self.wakers.retain(|(_, t, _)| t.strong_count() > 0);
+
+        match self
+            .wakers
+            .iter_mut()
+            .find(|(e, t, _)| *e == entity && t.as_ptr() == Arc::as_ptr(&token.0))
+        {
+            Some((_, _, w)) => {
+                if !w.will_wake(waker) {
+                    *w = waker.clone();
+                }
+            }
+            None => self
+                .wakers
+                .push((entity, Arc::downgrade(&token.0), waker.clone())),
+        }
+    }
+
+    /// Wakes and removes wakers registered for the entity.
+    #[inline]
+    pub fn wake(&mut self, entity: EntityId) {
+        if !self.wakers.is_empty() {
+            self.wake_entity(entity);
+        }
+    }
+
+    #[cold]
+    fn wake_entity(&mut self, entity: EntityId) {
+        self.wakers.retain(|(e, token, waker)| {
+            if *e != entity {
+                return token.strong_count() > 0;
+            }
+            if token.strong_count() > 0 {
+                waker.wake_by_ref();
+            }
+            false
+        });
+    }
+
+    /// Moves wakers registered for the entity into another list.
+    /// Used when component is moved to another archetype.
+    #[inline]
+    pub fn relocate(&mut self, entity: EntityId, dst: &mut EntityWakers) {
+        if !self.wakers.is_empty() {
+            self.relocate_entity(entity, dst);
+        }
+    }
+
+    #[cold]
+    fn relocate_entity(&mut self, entity: EntityId, dst: &mut EntityWakers) {
+        self.wakers.retain(|(e, token, waker)| {
+            if *e != entity {
+                return token.strong_count() > 0;
+            }
+            if token.strong_count() > 0 {
+                dst.wakers.push((entity, token.clone(), waker.clone()));
+            }
+            false
+        });
+    }
+}
@@ -9,6 +9,7 @@ use crate::{
         ExternalSetHook,
     },
     entity::{EntitySet, IdRangeAllocator},
+    relation::RelationRegistry,
     resources::Resources,
 };
 
@@ -57,6 +58,7 @@ impl WorldBuilder {
             edges: Edges::new(),
             resources: Resources::new(),
             registry: self.registry,
+            relations: RelationRegistry::new(),
             action_buffer: UnsafeCell::new(LocalActionBuffer::new()),
             action_channel: ActionChannel::new(),
 
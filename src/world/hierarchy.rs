@@ -0,0 +1,270 @@
+use alloc::vec::Vec;
+
+use crate::{
+    entity::{Entity, EntityId},
+    epoch::EpochId,
+    query::{Alt, DefaultQuery, Entities, EpochOf, Read, With},
+    relation::{
+        Ancestors, DescendantsBfs, DescendantsDfs, ExclusiveRelation, HierarchyQuery,
+        HierarchyView, ReparentError,
+    },
+    type_id,
+    view::{ViewMut, ViewRef, ViewValue},
+    NoSuchEntity,
+};
+
+use super::World;
+
+impl World {
+    /// Returns view over hierarchy formed by exclusive relation `R`.
+    ///
+    /// Returned view implements [`HierarchyView`] and
+    /// borrows relation components at runtime.
+    #[inline]
+    pub fn hierarchy<R>(&self) -> ViewRef<'_, HierarchyQuery<R>>
+    where
+        R: ExclusiveRelation,
+    {
+        ViewValue::new_ref(self, HierarchyQuery::<R>::default_query(), ())
+    }
+
+    /// Returns iterator over ancestors of the entity
+    /// in hierarchy formed by exclusive relation `R`.
+    ///
+    /// Iteration starts from entity's parent and ends with the root.
+    #[inline]
+    pub fn ancestors<R>(&self, entity: impl Entity) -> Ancestors<ViewRef<'_, HierarchyQuery<R>>, R>
+    where
+        R: ExclusiveRelation,
+    {
+        Ancestors::new(self.hierarchy(), entity.id())
+    }
+
+    /// Returns iterator over descendants of the entity
+    /// in hierarchy formed by exclusive relation `R`
+    /// in depth-first pre-order.
+    #[inline]
+    pub fn descendants_dfs<R>(
+        &self,
+        entity: impl Entity,
+    ) -> DescendantsDfs<ViewRef<'_, HierarchyQuery<R>>, R>
+    where
+        R: ExclusiveRelation,
+    {
+        DescendantsDfs::new(self.hierarchy(), entity.id())
+    }
+
+    /// Returns iterator over descendants of the entity
+    /// in hierarchy formed by exclusive relation `R`
+    /// in breadth-first order.
+    #[inline]
+    pub fn descendants_bfs<R>(
+        &self,
+        entity: impl Entity,
+    ) -> DescendantsBfs<ViewRef<'_, HierarchyQuery<R>>, R>
+    where
+        R: ExclusiveRelation,
+    {
+        DescendantsBfs::new(self.hierarchy(), entity.id())
+    }
+
+    /// Returns depth of the entity in hierarchy formed by exclusive relation `R`.
+    ///
+    /// Roots have depth `0`.
+    #[inline]
+    pub fn depth<R>(&self, entity: impl Entity) -> usize
+    where
+        R: ExclusiveRelation,
+    {
+        self.hierarchy::<R>().depth(entity.id())
+    }
+
+    /// Returns root of the hierarchy formed by exclusive relation `R`
+    /// the entity belongs to.
+    ///
+    /// Returns the entity itself if it has no parent.
+    #[inline]
+    pub fn root<R>(&self, entity: impl Entity) -> EntityId
+    where
+        R: ExclusiveRelation,
+    {
+        self.hierarchy::<R>().root(entity.id())
+    }
+
+    /// Moves `child` under `parent` in hierarchy formed by exclusive relation `R`.
+    ///
+    /// Previous relation of the `child` is replaced as with [`World::insert_relation`].
+    ///
+    /// If either entity is not alive, fails with `Err(ReparentError::NoSuchEntity)`.
+    /// If `parent` is the `child` itself or one of its descendants,
+    /// fails with `Err(ReparentError::Cycle)` and hierarchy is not changed.
+    #[inline]
+    pub fn reparent<R>(
+        &mut self,
+        child: impl Entity,
+        relation: R,
+        parent: impl Entity,
+    ) -> Result<(), ReparentError>
+    where
+        R: ExclusiveRelation,
+    {
+        child.lookup(&self.entities).ok_or(NoSuchEntity)?;
+        parent.lookup(&self.entities).ok_or(NoSuchEntity)?;
+
+        let cycle = {
+            let view: ViewMut<'_, HierarchyQuery<R>> =
+                ViewValue::new_mut(self, HierarchyQuery::<R>::default_query(), ());
+            child.id() == parent.id() || view.is_ancestor(child.id(), parent.id())
+        };
+
+        if cycle {
+            return Err(ReparentError::Cycle);
+        }
+
+        self.insert_relation(child, relation, parent)?;
+        Ok(())
+    }
+
+    /// Propagates data from parents to children
+    /// in hierarchy formed by exclusive relation `R`.
+    ///
+    /// Visits entities that have both `S` and `D` components,
+    /// parents before children,
+    /// and sets `D` of each visited entity to the value returned by `f`,
+    /// called with `D` of the parent and `S` of the entity itself.
+    ///
+    /// Roots of the hierarchy, as well as entities which parent
+    /// lacks either component, get `None` as parent value.
+    /// Descendants of entities that lack either component
+    /// are visited as separate roots.
+    ///
+    /// # Panics
+    ///
+    /// If `S` and `D` are the same type.
+    #[inline]
+    pub fn propagate<R, S, D>(&mut self, f: impl FnMut(Option<&D>, &S) -> D)
+    where
+        R: ExclusiveRelation,
+        S: Sync + 'static,
+        D: Send + 'static,
+    {
+        self.propagate_impl::<R, S, D>(None, f)
+    }
+
+    /// Propagates data from parents to children
+    /// in hierarchy formed by exclusive relation `R`,
+    /// only along subtrees where `S` component was modified after `after_epoch`.
+    ///
+    /// Works as [`World::propagate`], except that `f` is called
+    /// only for entities which `S` component was modified after `after_epoch`
+    /// and for all their descendants.
+    /// `D` component of other entities is left untouched.
+    ///
+    /// # Panics
+    ///
+    /// If `S` and `D` are the same type.
+    #[inline]
+    pub fn propagate_modified<R, S, D>(
+        &mut self,
+        after_epoch: EpochId,
+        f: impl FnMut(Option<&D>, &S) -> D,
+    ) where
+        R: ExclusiveRelation,
+        S: Sync + 'static,
+        D: Send + 'static,
+    {
+        self.propagate_impl::<R, S, D>(Some(after_epoch), f)
+    }
+
+    fn propagate_impl<R, S, D>(
+        &mut self,
+        after_epoch: Option<EpochId>,
+        mut f: impl FnMut(Option<&D>, &S) -> D,
+    ) where
+        R: ExclusiveRelation,
+        S: Sync + 'static,
+        D: Send + 'static,
+    {
+        assert_ne!(
+            type_id::<S>(),
+            type_id::<D>(),
+            "Source and derived components must be different types"
+        );
+
+        self.maintenance();
+
+        let world = &*self;
+
+        // Views are borrow-checked at runtime,
+        // so relation components, `S` and `D` can't alias mutably.
+        let hierarchy: ViewRef<'_, HierarchyQuery<R>> =
+            ViewValue::new_ref(world, HierarchyQuery::<R>::default_query(), ());
+        let nodes: ViewRef<'_, Entities, (With<S>, With<D>)> =
+            ViewValue::new_ref(world, Entities, (With::<S>::new(), With::<D>::new()));
+        let sources: ViewRef<'_, (Read<S>, EpochOf<S>)> =
+            ViewValue::new_ref(world, (Read::<S>::new(), EpochOf::<S>::new()), ());
+
+        // Entities to update, parents before children,
+        // with parent which `D` is passed to `f`.
+        let mut order: Vec<(EntityId, Option<EntityId>)> = Vec::new();
+
+        // Stack of entities to visit with parent
+        // and flag that parent was updated.
+        let mut stack: Vec<(EntityId, Option<EntityId>, bool)> = Vec::new();
+
+        for entity in nodes.iter() {
+            let is_root = match hierarchy.parent(entity.id()) {
+                None => true,
+                Some(parent) => nodes.try_get(parent).is_err(),
+            };
+
+            if is_root {
+                stack.push((entity.id(), None, false));
+            }
+        }
+
+        while let Some((entity, parent, parent_updated)) = stack.pop() {
+            let Ok((_, epoch)) = sources.try_get(entity) else {
+                continue;
+            };
+
+            let updated = parent_updated || after_epoch.is_none_or(|after| epoch.after(after));
+
+            if updated {
+                order.push((entity, parent));
+            }
+
+            for child in hierarchy.children(entity) {
+                if nodes.try_get(child.id()).is_ok() {
+                    stack.push((child.id(), Some(entity), updated));
+                }
+            }
+        }
+
+        drop(hierarchy);
+        drop(nodes);
+
+        // `Alt` marks `D` as modified only when it is written,
+        // so reading parent's `D` leaves it untouched.
+        let mut targets: ViewRef<'_, Alt<D>> = ViewValue::new_ref(world, Alt::<D>::new(), ());
+
+        for (entity, parent) in order {
+            let Ok((source, _)) = sources.try_get(entity) else {
+                continue;
+            };
+
+            // Parent is updated before its children, if at all.
+            let value = match parent {
+                None => f(None, source),
+                Some(parent) => match targets.try_get_mut(parent) {
+                    Ok(parent) => f(Some(&*parent), source),
+                    Err(_) => f(None, source),
+                },
+            };
+
+            if let Ok(mut target) = targets.try_get_mut(entity) {
+                *target = value;
+            }
+        }
+    }
+}
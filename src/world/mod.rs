@@ -31,21 +31,29 @@ use crate::{
     component::{Component, ComponentInfo, ComponentRegistry},
     entity::{AliveEntity, Entity, EntityId, EntityLoc, EntityRef, EntitySet},
     epoch::{EpochCounter, EpochId},
+    relation::RelationRegistry,
     resources::Resources,
-    type_id, NoSuchEntity,
+    type_id,
+    wakers::Wakers,
+    NoSuchEntity,
 };
 
 use self::edges::Edges;
 
 pub(crate) use self::spawn::iter_reserve_hint;
 
-pub use self::builder::WorldBuilder;
+pub use self::{
+    builder::WorldBuilder,
+    remote::{RemoteEntity, RemoteLinks},
+};
 
 mod builder;
 mod edges;
 mod get;
+mod hierarchy;
 mod insert;
 mod relation;
+mod remote;
 mod remove;
 mod resource;
 mod spawn;
@@ -64,6 +72,9 @@ struct ArchetypeSet {
     id: u64,
 
     archetypes: Vec<Archetype>,
+
+    /// Woken when new archetype is added.
+    wakers: Wakers,
 }
 
 impl Deref for ArchetypeSet {
@@ -87,6 +98,7 @@ impl ArchetypeSet {
             // All archetype sets starts the same.
             id: 0,
             archetypes: vec![null_archetype],
+            wakers: Wakers::default(),
         }
     }
 
@@ -105,6 +117,7 @@ impl ArchetypeSet {
         // Update archetype set id to new process-wide unique value.
         // Assume u64 increment won't overflow.
         self.id = NEXT_ARCHETYPE_SET_ID.fetch_add(1, Ordering::Relaxed);
+        self.wakers.wake();
         len
     }
 }
@@ -151,6 +164,8 @@ pub struct World {
 
     registry: ComponentRegistry,
 
+    relations: RelationRegistry,
+
     resources: Resources,
 
     /// Internal action encoder.
@@ -266,6 +281,16 @@ impl World {
         &mut self.archetypes
     }
 
+    /// Registers waker to be woken when new archetype is added.
+    #[cfg(feature = "flow")]
+    pub(crate) fn register_new_archetype_waker(
+        &mut self,
+        token: &crate::wakers::WakeToken,
+        waker: &core::task::Waker,
+    ) {
+        self.archetypes.wakers.register(token, waker);
+    }
+
     /// Returns [`WorldLocal`] referencing this [`World`].
     /// [`WorldLocal`] dereferences to [`World`]
     /// And defines overlapping methods `get_resource` and `get_resource_mut` without `Sync` and `Send` bounds.
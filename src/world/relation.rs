@@ -1,8 +1,14 @@
+use core::{any::TypeId, cmp::Ordering, ops::RangeBounds};
+
 use crate::{
     action::LocalActionEncoder,
-    component::Component,
+    component::{Component, ComponentInfo},
     entity::{Entity, EntityId, Location},
-    relation::{OriginComponent, Relation, TargetComponent},
+    query::With,
+    relation::{
+        Follow, FollowView, OriginComponent, Pair, Related, Relates, Relation, RelationEventKind,
+        RelationEvents, RelationInfo, RelationLinks, RelationPath, TargetComponent,
+    },
     type_id, NoSuchEntity,
 };
 
@@ -37,51 +43,76 @@ impl World {
         origin.lookup(&self.entities).ok_or(NoSuchEntity)?;
         target.lookup(&self.entities).ok_or(NoSuchEntity)?;
 
+        self.register_relation::<R>();
+
+        // Previous target of exclusive relation to record its removal.
+        let old_target = match R::EXCLUSIVE && self.get_resource::<RelationEvents<R>>().is_some() {
+            false => None,
+            true => self
+                .get::<&OriginComponent<R>>(origin.id())
+                .ok()
+                .and_then(|comp| comp.targets().first().map(|r| r.0)),
+        };
+
         self.epoch.next_mut();
 
         if R::SYMMETRIC {
             let set_target = set_relation_component(
                 self,
                 origin.id(),
-                relation,
+                relation.clone(),
                 |relation| OriginComponent::new_relation(target.id(), relation),
                 |component, relation, encoder| {
                     component.insert_relation(origin.id(), target.id(), relation, encoder)
                 },
             );
 
-            if target.id() != origin.id() && set_target {
-                set_relation_component(
-                    self,
-                    target.id(),
-                    relation,
-                    |relation| OriginComponent::new_relation(origin.id(), relation),
-                    |component, relation, encoder| {
-                        component.insert_relation(target.id(), origin.id(), relation, encoder)
-                    },
-                );
+            if set_target {
+                self.relation_inserted(origin.id(), &relation, target.id(), old_target);
+            }
+
+            if target.id() != origin.id() {
+                if set_target {
+                    set_relation_component(
+                        self,
+                        target.id(),
+                        relation,
+                        |relation| OriginComponent::new_relation(origin.id(), relation),
+                        |component, relation, encoder| {
+                            component.insert_relation(target.id(), origin.id(), relation, encoder)
+                        },
+                    );
+                } else if let Ok(component) = self.get::<&mut OriginComponent<R>>(target.id()) {
+                    // Keep value on the other side in sync.
+                    component.set_relation(origin.id(), relation);
+                }
             }
         } else {
             let set_target = set_relation_component(
                 self,
                 origin.id(),
-                relation,
+                relation.clone(),
                 |relation| OriginComponent::new_relation(target.id(), relation),
                 |comp, relation, encoder| {
                     comp.insert_relation(origin.id(), target.id(), relation, encoder)
                 },
             );
             if set_target {
+                self.relation_inserted(origin.id(), &relation, target.id(), old_target);
+
                 set_relation_component(
                     self,
                     target.id(),
-                    (),
-                    |()| TargetComponent::<R>::new(origin.id(), relation),
-                    |comp, (), _| {
+                    relation,
+                    |relation| TargetComponent::<R>::new(origin.id(), relation),
+                    |comp, relation, _| {
                         comp.add(origin.id(), relation);
                         false
                     },
                 );
+            } else if let Ok(comp) = self.get::<&mut TargetComponent<R>>(target.id()) {
+                // Keep value on the target side in sync.
+                comp.set_relation(origin.id(), relation);
             }
         }
 
@@ -89,6 +120,71 @@ impl World {
         Ok(())
     }
 
+    /// Records insertion of new relation and calls [`Relation::on_insert`] hook.
+    fn relation_inserted<R>(
+        &mut self,
+        origin: EntityId,
+        relation: &R,
+        target: EntityId,
+        old_target: Option<EntityId>,
+    ) where
+        R: Relation,
+    {
+        if let Some(mut events) = self.get_resource_mut::<RelationEvents<R>>() {
+            if let Some(old_target) = old_target.filter(|&old_target| old_target != target) {
+                events.push(RelationEventKind::Removed, origin, old_target);
+            }
+            events.push(RelationEventKind::Inserted, origin, target);
+        }
+
+        let encoder = LocalActionEncoder::new(self.action_buffer.get_mut(), &self.entities);
+        relation.on_insert(origin, target, encoder);
+    }
+
+    /// Starts recording insertion and removal events of relation `R`
+    /// into [`RelationEvents<R>`] resource.
+    ///
+    /// Does nothing if events are already recorded.
+    /// Remove the resource to stop recording.
+    #[inline]
+    pub fn track_relation_events<R>(&mut self)
+    where
+        R: Relation,
+    {
+        self.with_default_resource::<RelationEvents<R>>();
+    }
+
+    /// Iterate over info of all relation types that were inserted into the [`World`].
+    pub fn iter_relation_info(&self) -> impl Iterator<Item = &RelationInfo> {
+        self.relations.iter_info()
+    }
+
+    /// Returns info of the relation type with specified [`TypeId`]
+    /// if relation of this type was inserted into the [`World`].
+    pub fn relation_info(&self, ty: TypeId) -> Option<&RelationInfo> {
+        self.relations.get_info(ty)
+    }
+
+    /// Registers relation type and its origin and target components.
+    fn register_relation<R>(&mut self)
+    where
+        R: Relation,
+    {
+        if self.relations.get_info(type_id::<R>()).is_some() {
+            return;
+        }
+
+        let info = *self.relations.get_or_register::<R>();
+        self.registry.get_or_register_raw(
+            ComponentInfo::of::<OriginComponent<R>>().with_relation(info, true),
+        );
+        if !R::SYMMETRIC {
+            self.registry.get_or_register_raw(
+                ComponentInfo::of::<TargetComponent<R>>().with_relation(info, false),
+            );
+        }
+    }
+
     /// Removes relation between two entities in the [`World`].
     ///
     /// If either entity is not alive, fails with `Err(NoSuchEntity)`.
@@ -105,7 +201,7 @@ impl World {
     where
         R: Relation,
     {
-        self._remove_relation(origin, target)
+        self._remove_relation(origin, target, |_| true)
     }
 
     /// Drops relation between two entities in the [`World`].
@@ -123,7 +219,7 @@ impl World {
     where
         R: Relation,
     {
-        if let Some(relation) = self._remove_relation(origin, target)? {
+        if let Some(relation) = self._remove_relation(origin, target, |_: &R| true)? {
             let encoder = LocalActionEncoder::new(self.action_buffer.get_mut(), &self.entities);
 
             R::on_drop(relation, origin.id(), target.id(), encoder);
@@ -132,11 +228,213 @@ impl World {
         Ok(())
     }
 
+    /// Adds relation between two entities to the [`World`]
+    /// and places origin at specified index among origins of the target.
+    ///
+    /// Works as [`World::insert_relation`] followed by [`World::move_relation`].
+    /// If `index` is out of bounds, origin is placed last.
+    ///
+    /// Relation should be [ordered](Relation::ORDERED)
+    /// to keep the order when other relations are removed.
+    #[inline]
+    pub fn insert_relation_at<R>(
+        &mut self,
+        origin: impl Entity,
+        relation: R,
+        target: impl Entity,
+        index: usize,
+    ) -> Result<(), NoSuchEntity>
+    where
+        R: Relation,
+    {
+        self.insert_relation(origin, relation, target)?;
+        self.move_relation::<R>(origin, target, index)?;
+        Ok(())
+    }
+
+    /// Moves origin to specified index among origins of the target
+    /// that are yielded by [`Related`] query.
+    ///
+    /// If `index` is out of bounds, origin is placed last.
+    ///
+    /// If either entity is not alive, fails with `Err(NoSuchEntity)`.
+    /// Returns `Ok(false)` if relation does not exist.
+    #[inline]
+    pub fn move_relation<R>(
+        &mut self,
+        origin: impl Entity,
+        target: impl Entity,
+        index: usize,
+    ) -> Result<bool, NoSuchEntity>
+    where
+        R: Relation,
+    {
+        origin.lookup(&self.entities).ok_or(NoSuchEntity)?;
+        let related = self.related_mut::<R>(target)?;
+
+        let Some(from) = related.iter().position(|r| r.0 == origin.id()) else {
+            return Ok(false);
+        };
+        let to = index.min(related.len() - 1);
+
+        if from < to {
+            related[from..=to].rotate_left(1);
+        } else {
+            related[to..=from].rotate_right(1);
+        }
+        Ok(true)
+    }
+
+    /// Sorts origins of the target that are yielded by [`Related`] query
+    /// with comparator function.
+    ///
+    /// Sort is stable.
+    ///
+    /// If target is not alive, fails with `Err(NoSuchEntity)`.
+    #[inline]
+    pub fn sort_related_by<R>(
+        &mut self,
+        target: impl Entity,
+        compare: impl FnMut(&(EntityId, R), &(EntityId, R)) -> Ordering,
+    ) -> Result<(), NoSuchEntity>
+    where
+        R: Relation,
+    {
+        self.related_mut::<R>(target)?.sort_by(compare);
+        Ok(())
+    }
+
+    /// Returns origins of the target with relation values.
+    fn related_mut<R>(&mut self, target: impl Entity) -> Result<&mut [(EntityId, R)], NoSuchEntity>
+    where
+        R: Relation,
+    {
+        self.maintenance();
+        target.lookup(&self.entities).ok_or(NoSuchEntity)?;
+
+        self.epoch.next_mut();
+
+        if R::SYMMETRIC {
+            match self.get::<&mut OriginComponent<R>>(target.id()) {
+                Ok(comp) => Ok(comp.targets_mut()),
+                Err(_) => Ok(&mut []),
+            }
+        } else {
+            match self.get::<&mut TargetComponent<R>>(target.id()) {
+                Ok(comp) => Ok(comp.origins_mut()),
+                Err(_) => Ok(&mut []),
+            }
+        }
+    }
+
+    /// Adds [`Pair`] of specified kind between two entities to the [`World`].
+    ///
+    /// If either entity is not alive, fails with `Err(NoSuchEntity)`.
+    /// Pairs of different kinds between the same entities coexist.
+    #[inline]
+    pub fn insert_pair(
+        &mut self,
+        origin: impl Entity,
+        kind: impl Entity,
+        target: impl Entity,
+    ) -> Result<(), NoSuchEntity> {
+        self.insert_relation(origin, Pair::new(kind.id()), target)
+    }
+
+    /// Removes [`Pair`] of specified kind between two entities in the [`World`].
+    ///
+    /// If either entity is not alive, fails with `Err(NoSuchEntity)`.
+    /// Returns `Ok(true)` if pair existed and was removed.
+    /// Pairs of other kinds between the same entities are not affected.
+    #[inline]
+    pub fn remove_pair(
+        &mut self,
+        origin: impl Entity,
+        kind: impl Entity,
+        target: impl Entity,
+    ) -> Result<bool, NoSuchEntity> {
+        let kind = kind.id();
+        let removed = self._remove_relation(origin, target, |pair: &Pair| pair.kind() == kind)?;
+        Ok(removed.is_some())
+    }
+
+    /// Returns view that searches paths following relation `R`
+    /// from origins to targets for number of hops in `hops` range
+    /// and continues with `next` step from each reached entity.
+    ///
+    /// `next` is usually [`Matches`](crate::relation::Matches) step that matches entities at the far end,
+    /// `()` to accept any entity or another [`Follow`] step.
+    ///
+    /// View finds first matched path of each entity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, relation::{ChildOf, Matches}, query::With, component::Component};
+    /// #[derive(Component)]
+    /// struct Tag;
+    ///
+    /// let mut world = World::new();
+    /// let a = world.spawn((Tag,)).id();
+    /// let b = world.spawn(()).id();
+    /// let c = world.spawn(()).id();
+    /// world.insert_relation(b, ChildOf, a).unwrap();
+    /// world.insert_relation(c, ChildOf, b).unwrap();
+    ///
+    /// let mut grandparent_tagged = world.follow::<ChildOf, _>(2..=2, Matches(With::<Tag>::new()));
+    /// assert_eq!(grandparent_tagged.get(c), Some(&[c, b, a][..]));
+    /// assert_eq!(grandparent_tagged.get(b), None);
+    /// ```
+    #[inline]
+    pub fn follow<R, N>(
+        &self,
+        hops: impl RangeBounds<usize>,
+        next: N,
+    ) -> FollowView<'_, Relates<With<R>>, N>
+    where
+        R: Relation,
+        N: RelationPath,
+    {
+        self.follow_with(Follow::new(Relates, hops, next))
+    }
+
+    /// Returns view that searches paths following relation `R`
+    /// from targets to origins for number of hops in `hops` range
+    /// and continues with `next` step from each reached entity.
+    ///
+    /// See [`World::follow`].
+    #[inline]
+    pub fn follow_back<R, N>(
+        &self,
+        hops: impl RangeBounds<usize>,
+        next: N,
+    ) -> FollowView<'_, Related<With<R>>, N>
+    where
+        R: Relation,
+        N: RelationPath,
+    {
+        self.follow_with(Follow::new(Related, hops, next))
+    }
+
+    /// Returns view that searches paths of the [`Follow`] step.
+    ///
+    /// See [`World::follow`].
+    #[inline]
+    pub fn follow_with<L, N>(&self, follow: Follow<L, N>) -> FollowView<'_, L, N>
+    where
+        L: RelationLinks,
+        N: RelationPath,
+    {
+        FollowView::new(self, follow)
+    }
+
+    /// Removes first relation between two entities that matches the predicate.
     #[inline]
     pub(crate) fn _remove_relation<R>(
         &mut self,
         origin: impl Entity,
         target: impl Entity,
+        pred: impl Fn(&R) -> bool,
     ) -> Result<Option<R>, NoSuchEntity>
     where
         R: Relation,
@@ -155,22 +453,30 @@ impl World {
                     LocalActionEncoder::new(&mut *self.action_buffer.get(), &self.entities);
 
                 if let Some(relation) =
-                    comp.remove_relation(origin.id(), target.id(), encoder.reborrow())
+                    comp.remove_relation(origin.id(), target.id(), &pred, encoder.reborrow())
                 {
+                    // Remove the same kind of relation on the other side,
+                    // as relations may be stored in different order there.
+                    let same_kind = |r: &R| r.same_kind(&relation);
+
                     if R::SYMMETRIC {
                         if origin.id() != target.id() {
                             let comp = self
                                 .get_unchecked::<&mut OriginComponent<R>>(target)
                                 .unwrap_unchecked();
 
-                            comp.remove_relation(target.id(), origin.id(), encoder);
+                            comp.remove_relation(target.id(), origin.id(), same_kind, encoder);
                         }
                     } else {
                         let comp = self
                             .get_unchecked::<&mut TargetComponent<R>>(target)
                             .unwrap_unchecked();
 
-                        comp.remove_relation(origin.id(), target.id(), encoder);
+                        comp.remove_relation(origin.id(), target.id(), same_kind, encoder);
+                    }
+
+                    if let Some(mut events) = self.get_resource_mut::<RelationEvents<R>>() {
+                        events.push(RelationEventKind::Removed, origin.id(), target.id());
                     }
 
                     removed = Some(relation);
@@ -0,0 +1,253 @@
+use alloc::{sync::Arc, vec, vec::Vec};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{
+    action::ActionSender,
+    component::Component,
+    entity::{Entity, EntityId},
+    NoSuchEntity,
+};
+
+use super::World;
+
+/// Reference to an entity in another [`World`].
+///
+/// Created when entities are linked with [`World::link_remote`]
+/// and accessible through [`RemoteLinks`] component.
+#[derive(Clone)]
+pub struct RemoteEntity {
+    id: EntityId,
+
+    /// Sender to the world of the remote entity.
+    sender: ActionSender,
+
+    /// Flag shared by both sides of the link.
+    linked: Arc<AtomicBool>,
+}
+
+impl RemoteEntity {
+    /// Returns id of the remote entity.
+    #[inline]
+    pub fn id(&self) -> EntityId {
+        self.id
+    }
+
+    /// Returns `true` if link is still alive.
+    ///
+    /// Link is broken as soon as either entity is despawned
+    /// or link is removed on either side,
+    /// even before the other world executes cleanup actions.
+    #[inline]
+    pub fn is_alive(&self) -> bool {
+        self.linked.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if remote entity belongs to the `world`.
+    #[inline]
+    pub fn belongs_to(&self, world: &World) -> bool {
+        self.sender.same_world(&world.new_action_sender())
+    }
+
+    /// Breaks the link and sends action to remove it on the other side.
+    fn unlink(self) {
+        if self.linked.swap(false, Ordering::AcqRel) {
+            let RemoteEntity { id, sender, linked } = self;
+            sender.closure(move |world| world.remove_remote_link(id, &linked));
+        }
+    }
+}
+
+/// Component with links of an entity to entities in other [`World`]s.
+///
+/// Inserted by [`World::link_remote`].
+/// When entity is despawned or this component is dropped,
+/// links are broken and remote entities lose links to this entity
+/// once their worlds execute received actions
+/// with [`World::execute_received_actions`].
+pub struct RemoteLinks {
+    links: Vec<RemoteEntity>,
+}
+
+impl RemoteLinks {
+    /// Returns iterator over linked remote entities.
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, RemoteEntity> {
+        self.links.iter()
+    }
+
+    /// Returns number of links.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.links.len()
+    }
+
+    /// Returns `true` if there are no links.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a RemoteLinks {
+    type Item = &'a RemoteEntity;
+    type IntoIter = core::slice::Iter<'a, RemoteEntity>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Drop for RemoteLinks {
+    fn drop(&mut self) {
+        for link in self.links.drain(..) {
+            link.unlink();
+        }
+    }
+}
+
+impl Component for RemoteLinks {}
+
+impl World {
+    /// Links entity in this world with entity in `other` world.
+    ///
+    /// Both entities get [`RemoteLinks`] component with reference to each other.
+    /// When either entity is despawned the link is broken
+    /// and removed from the other entity when its world
+    /// executes received actions.
+    ///
+    /// Linking already linked entities does nothing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::world::{World, RemoteLinks};
+    /// let mut ui = World::new();
+    /// let mut sim = World::new();
+    ///
+    /// let button = ui.spawn(()).id();
+    /// let unit = sim.spawn(()).id();
+    ///
+    /// ui.link_remote(button, &mut sim, unit).unwrap();
+    ///
+    /// let link = ui.get::<&RemoteLinks>(button).unwrap().iter().next().unwrap().clone();
+    /// assert_eq!(link.id(), unit);
+    /// assert!(link.is_alive());
+    ///
+    /// sim.despawn(unit).unwrap();
+    /// assert!(!link.is_alive());
+    ///
+    /// ui.execute_received_actions();
+    /// assert!(ui.get::<&RemoteLinks>(button).is_err());
+    /// ```
+    pub fn link_remote(
+        &mut self,
+        entity: impl Entity,
+        other: &mut World,
+        other_entity: impl Entity,
+    ) -> Result<(), NoSuchEntity> {
+        let entity = entity.id();
+        let other_entity = other_entity.id();
+
+        if !self.is_alive(entity) || !other.is_alive(other_entity) {
+            return Err(NoSuchEntity);
+        }
+
+        if let Ok(links) = self.get::<&RemoteLinks>(entity) {
+            if links
+                .iter()
+                .any(|link| link.id == other_entity && link.belongs_to(other) && link.is_alive())
+            {
+                return Ok(());
+            }
+        }
+
+        let linked = Arc::new(AtomicBool::new(true));
+
+        self.add_remote_link(
+            entity,
+            RemoteEntity {
+                id: other_entity,
+                sender: other.new_action_sender(),
+                linked: linked.clone(),
+            },
+        );
+
+        other.add_remote_link(
+            other_entity,
+            RemoteEntity {
+                id: entity,
+                sender: self.new_action_sender(),
+                linked,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Removes link between entity in this world and remote entity.
+    ///
+    /// Remote side of the link is removed when its world
+    /// executes received actions.
+    ///
+    /// Returns `Ok(true)` if link was removed.
+    /// Returns `Ok(false)` if entity is not linked to remote entity.
+    pub fn unlink_remote(
+        &mut self,
+        entity: impl Entity,
+        remote: &RemoteEntity,
+    ) -> Result<bool, NoSuchEntity> {
+        let entity = entity.id();
+        if !self.is_alive(entity) {
+            return Err(NoSuchEntity);
+        }
+
+        let Ok(links) = self.get::<&mut RemoteLinks>(entity) else {
+            return Ok(false);
+        };
+
+        let Some(idx) = links
+            .links
+            .iter()
+            .position(|link| Arc::ptr_eq(&link.linked, &remote.linked))
+        else {
+            return Ok(false);
+        };
+
+        let link = links.links.swap_remove(idx);
+        let empty = links.links.is_empty();
+        link.unlink();
+
+        if empty {
+            let _ = self.drop::<RemoteLinks>(entity);
+        }
+        Ok(true)
+    }
+
+    fn add_remote_link(&mut self, entity: EntityId, link: RemoteEntity) {
+        match self.get::<&mut RemoteLinks>(entity) {
+            Ok(links) => {
+                links.links.retain(RemoteEntity::is_alive);
+                links.links.push(link);
+            }
+            Err(_) => {
+                let _ = self.insert(entity, RemoteLinks { links: vec![link] });
+            }
+        }
+    }
+
+    /// Removes broken link from the entity.
+    fn remove_remote_link(&mut self, entity: EntityId, linked: &Arc<AtomicBool>) {
+        let Ok(links) = self.get::<&mut RemoteLinks>(entity) else {
+            return;
+        };
+
+        links
+            .links
+            .retain(|link| !Arc::ptr_eq(&link.linked, linked));
+
+        if links.links.is_empty() {
+            let _ = self.drop::<RemoteLinks>(entity);
+        }
+    }
+}
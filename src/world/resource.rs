@@ -1,6 +1,9 @@
 use core::any::{type_name, TypeId};
 
-use crate::resources::{Res, ResMut};
+use crate::{
+    resources::{Res, ResMut},
+    type_id,
+};
 
 use super::{World, WorldLocal};
 
@@ -30,6 +33,9 @@ impl World {
     /// Returns reference to the resource instance.
     /// Inserts new instance if it does not exist.
     ///
+    /// Unlike [`World::get_resource_mut`] this method changes
+    /// [resource version](World::resource_version) only when resource is inserted.
+    ///
     /// # Examples
     ///
     /// ```
@@ -47,6 +53,9 @@ impl World {
     /// Returns reference to the resource instance.
     /// Inserts new instance if it does not exist.
     ///
+    /// Changes resource version only on insertion,
+    /// see [`World::with_resource`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -355,6 +364,45 @@ impl World {
     pub fn resource_types(&self) -> impl Iterator<Item = TypeId> + '_ {
         self.resources.resource_types()
     }
+
+    /// Returns version of the resource.
+    /// Returns `None` if resource was not found.
+    ///
+    /// Version changes each time resource is inserted or borrowed mutably
+    /// with [`World::get_resource_mut`] or [`World::expect_resource_mut`],
+    /// allowing to detect resource changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use edict::world::World;
+    /// let mut world = World::new();
+    /// assert_eq!(world.resource_version::<i32>(), None);
+    ///
+    /// world.insert_resource(42i32);
+    /// let version = world.resource_version::<i32>().unwrap();
+    ///
+    /// let _ = world.expect_resource::<i32>();
+    /// let _ = world.with_resource(|| 0i32);
+    /// assert_eq!(world.resource_version::<i32>(), Some(version));
+    ///
+    /// *world.expect_resource_mut::<i32>() = 11;
+    /// assert_ne!(world.resource_version::<i32>(), Some(version));
+    /// ```
+    pub fn resource_version<T: 'static>(&self) -> Option<u64> {
+        self.resources.version(type_id::<T>())
+    }
+
+    /// Registers waker to be woken when resource is inserted,
+    /// borrowed mutably or removed.
+    #[cfg(feature = "flow")]
+    pub(crate) fn register_resource_waker<T: 'static>(
+        &mut self,
+        token: &crate::wakers::WakeToken,
+        waker: &core::task::Waker,
+    ) {
+        self.resources.register_waker(type_id::<T>(), token, waker);
+    }
 }
 
 impl WorldLocal {
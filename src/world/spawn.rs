@@ -1,5 +1,7 @@
 use core::{any::type_name, marker::PhantomData};
 
+use alloc::vec::Vec;
+
 use crate::{
     action::LocalActionEncoder,
     archetype::Archetype,
@@ -8,7 +10,7 @@ use crate::{
     component::{Component, ComponentRegistry},
     entity::{Entity, EntityId, EntityLoc, EntityRef, EntitySet, Location},
     epoch::EpochId,
-    type_id, NoSuchEntity,
+    type_id, DespawnError, NoSuchEntity,
 };
 
 use super::{
@@ -529,6 +531,12 @@ impl World {
     /// Despawns an entity with specified id.
     /// Returns [`Err(NoSuchEntity)`] if entity does not exists.
     ///
+    /// Entity protected from despawning by a relation
+    /// with [`DespawnPolicy::Deny`] policy is left alive.
+    /// Use [`World::try_despawn`] to detect this.
+    ///
+    /// [`DespawnPolicy::Deny`]: crate::relation::DespawnPolicy::Deny
+    ///
     /// # Example
     ///
     /// ```
@@ -540,8 +548,37 @@ impl World {
     /// ```
     #[inline]
     pub fn despawn(&mut self, entity: impl Entity) -> Result<(), NoSuchEntity> {
+        match self.try_despawn(entity) {
+            Err(DespawnError::NoSuchEntity) => Err(NoSuchEntity),
+            Ok(()) | Err(DespawnError::Denied) => Ok(()),
+        }
+    }
+
+    /// Despawns an entity with specified id.
+    /// Returns [`Err(DespawnError::NoSuchEntity)`] if entity does not exists.
+    /// Returns [`Err(DespawnError::Denied)`] if entity is protected from despawning
+    /// by a relation with [`DespawnPolicy::Deny`] policy.
+    ///
+    /// [`DespawnPolicy::Deny`]: crate::relation::DespawnPolicy::Deny
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, DespawnError, ExampleComponent};
+    /// let mut world = World::new();
+    /// let entity = world.spawn((ExampleComponent,)).id();
+    /// assert_eq!(world.try_despawn(entity), Ok(()));
+    /// assert_eq!(world.try_despawn(entity), Err(DespawnError::NoSuchEntity));
+    /// ```
+    #[inline]
+    pub fn try_despawn(&mut self, entity: impl Entity) -> Result<(), DespawnError> {
         self.maintenance();
 
+        let loc = entity.lookup(&self.entities).ok_or(NoSuchEntity)?;
+        if self.is_despawn_denied(loc) {
+            return Err(DespawnError::Denied);
+        }
+
         let loc = self.entities.despawn(entity.id()).ok_or(NoSuchEntity)?;
 
         let encoder = LocalActionEncoder::new(self.action_buffer.get_mut(), &self.entities);
@@ -559,6 +596,9 @@ impl World {
 
     /// Despawns batch of entities with specified ids.
     ///
+    /// Skips entities that are not alive
+    /// or protected from despawning by a relation.
+    ///
     /// # Example
     ///
     /// ```
@@ -575,8 +615,62 @@ impl World {
     #[inline]
     pub fn despawn_batch(&mut self, entities: impl IntoIterator<Item = EntityId>) {
         self.maintenance();
+        self.despawn_batch_allowed(entities);
+        self.execute_local_actions();
+    }
 
+    /// Despawns batch of entities with specified ids.
+    ///
+    /// Skips entities that are not alive.
+    /// Returns [`Err(DespawnError::Denied)`] and despawns nothing
+    /// if any of the entities is protected from despawning
+    /// by a relation with [`DespawnPolicy::Deny`] policy.
+    ///
+    /// [`DespawnPolicy::Deny`]: crate::relation::DespawnPolicy::Deny
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use edict::{world::World, ExampleComponent};
+    /// let mut world = World::new();
+    /// let entity1 = world.spawn((ExampleComponent,)).id();
+    /// let entity2 = world.spawn((ExampleComponent,)).id();
+    ///
+    /// world.try_despawn_batch([entity1, entity2]).unwrap();
+    ///
+    /// assert!(world.despawn(entity1).is_err(), "Already despawned");
+    /// assert!(world.despawn(entity2).is_err(), "Already despawned");
+    /// ```
+    pub fn try_despawn_batch(
+        &mut self,
+        entities: impl IntoIterator<Item = EntityId>,
+    ) -> Result<(), DespawnError> {
+        self.maintenance();
+
+        if self.relations.deny_despawn().is_empty() {
+            self.despawn_batch_allowed(entities);
+        } else {
+            let entities = entities.into_iter().collect::<Vec<_>>();
+            for &entity in &entities {
+                match self.entities.get_location(entity) {
+                    Some(loc) if self.is_despawn_denied(loc) => return Err(DespawnError::Denied),
+                    _ => {}
+                }
+            }
+            self.despawn_batch_allowed(entities);
+        }
+
+        self.execute_local_actions();
+        Ok(())
+    }
+
+    /// Despawns entities that are alive and not protected from despawning.
+    fn despawn_batch_allowed(&mut self, entities: impl IntoIterator<Item = EntityId>) {
         for entity in entities {
+            match self.entities.get_location(entity) {
+                Some(loc) if !self.is_despawn_denied(loc) => {}
+                _ => continue,
+            }
             let Some(loc) = self.entities.despawn(entity) else {
                 continue;
             };
@@ -589,18 +683,24 @@ impl World {
                 self.entities.set_location(id, loc)
             }
         }
-
-        self.execute_local_actions();
     }
 
-    /// Special-case despawn method for [`EntityRef::despawn`].
+    /// Special-case despawn method for [`EntityRef::try_despawn`].
     /// This method uses branch elimination for non-existent entity case
     /// and prevents data dependencies between removing entity from
     /// `EntitySet` and `Archetype`.
     #[inline]
-    pub(crate) unsafe fn despawn_ref(&mut self, id: EntityId, loc: Location) {
+    pub(crate) unsafe fn despawn_ref(
+        &mut self,
+        id: EntityId,
+        loc: Location,
+    ) -> Result<(), DespawnError> {
         self.maintenance();
 
+        if self.is_despawn_denied(loc) {
+            return Err(DespawnError::Denied);
+        }
+
         let real_loc = unsafe { self.entities.despawn(id).unwrap_unchecked() };
         debug_assert_eq!(real_loc, loc, "Entity location mismatch");
 
@@ -614,6 +714,22 @@ impl World {
         }
 
         self.execute_local_actions();
+        Ok(())
+    }
+
+    /// Returns `true` if entity at the location has relation component
+    /// that denies despawning.
+    #[inline]
+    fn is_despawn_denied(&self, loc: Location) -> bool {
+        if loc.arch == u32::MAX {
+            return false;
+        }
+
+        let archetype = &self.archetypes[loc.arch as usize];
+        self.relations
+            .deny_despawn()
+            .iter()
+            .any(|&ty| archetype.has_component(ty))
     }
 }
 
@@ -1057,13 +1173,17 @@ impl WorldLocal {
     }
 
     /// Despawns an entity with specified id.
-    /// Returns [`Err(NoSuchEntity)`] if entity does not exists.
     ///
     /// This is deferred version of [`World::despawn`].
     /// It can be used on shared `WorldLocal` reference.
     /// Operation is queued to be executed when [`World::run_deferred`] is called
     /// or when mutable operation is performed on the world.
     ///
+    /// Entity is skipped if it is not alive by then
+    /// or protected from despawning by a relation with [`DespawnPolicy::Deny`] policy.
+    ///
+    /// [`DespawnPolicy::Deny`]: crate::relation::DespawnPolicy::Deny
+    ///
     /// # Example
     ///
     /// ```